@@ -0,0 +1,56 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use risingwave_common::array::{StreamChunk, StreamChunkCompactor, StreamChunkTestExt};
+use risingwave_common::util::hash_util::XxHash64Builder;
+
+const CHUNK_SIZE: usize = 4096;
+/// Small enough that most rows share a key, provoking `Crc32FastBuilder` bucket collisions.
+const DISTINCT_KEYS: usize = 8;
+
+/// A skewed key set: `DISTINCT_KEYS` distinct stream keys, each hit many times, with a unique
+/// `- / +` pair per row so the compactor still has real work to do beyond just hashing.
+fn skewed_chunk() -> StreamChunk {
+    let mut pretty = String::from(" I I\n");
+    for i in 0..CHUNK_SIZE {
+        let key = i % DISTINCT_KEYS;
+        pretty.push_str(&format!("- {} {}\n", key, i));
+        pretty.push_str(&format!("+ {} {}\n", key, i + 1));
+    }
+    StreamChunk::from_pretty(&pretty)
+}
+
+fn bench_compact_chunk_hasher(c: &mut Criterion) {
+    let chunk = skewed_chunk();
+
+    c.bench_function("compact skewed chunk: crc32 (default)", |b| {
+        b.iter(|| {
+            let mut compactor = StreamChunkCompactor::new(vec![0]);
+            compactor.push_chunk(chunk.clone());
+            let _ = compactor.into_compacted_chunks().collect::<Vec<_>>();
+        })
+    });
+
+    c.bench_function("compact skewed chunk: xxhash64", |b| {
+        b.iter(|| {
+            let mut compactor = StreamChunkCompactor::with_hasher(vec![0], XxHash64Builder);
+            compactor.push_chunk(chunk.clone());
+            let _ = compactor.into_compacted_chunks().collect::<Vec<_>>();
+        })
+    });
+}
+
+criterion_group!(benches, bench_compact_chunk_hasher);
+criterion_main!(benches);
@@ -0,0 +1,61 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use risingwave_common::array::{StreamChunk, StreamChunkCompactor, StreamChunkTestExt};
+
+const CHUNK_SIZE: usize = 4096;
+const KEY_COLUMNS: usize = 10;
+
+/// `KEY_COLUMNS`-wide composite key, with a unique `- / +` pair per row so the compactor still
+/// has real work to do beyond just hashing.
+fn wide_key_chunk() -> StreamChunk {
+    let mut pretty = " I".repeat(KEY_COLUMNS + 1);
+    pretty.push('\n');
+    for i in 0..CHUNK_SIZE {
+        for (prefix, offset) in [("-", 0), ("+", 1)] {
+            pretty.push_str(prefix);
+            for col in 0..KEY_COLUMNS {
+                pretty.push_str(&format!(" {}", i * KEY_COLUMNS + col));
+            }
+            pretty.push_str(&format!(" {}\n", i + offset));
+        }
+    }
+    StreamChunk::from_pretty(&pretty)
+}
+
+fn bench_compact_chunk_wide_key(c: &mut Criterion) {
+    let chunk = wide_key_chunk();
+    let key_indices: Vec<usize> = (0..KEY_COLUMNS).collect();
+
+    c.bench_function("compact 10-column key chunk: full key equality (default)", |b| {
+        b.iter(|| {
+            let mut compactor = StreamChunkCompactor::new(key_indices.clone());
+            compactor.push_chunk(chunk.clone());
+            let _ = compactor.into_compacted_chunks().collect::<Vec<_>>();
+        })
+    });
+
+    c.bench_function("compact 10-column key chunk: hash-only dedup", |b| {
+        b.iter(|| {
+            let mut compactor = StreamChunkCompactor::new(key_indices.clone())
+                .unsafe_with_hash_only_dedup();
+            compactor.push_chunk(chunk.clone());
+            let _ = compactor.into_compacted_chunks().collect::<Vec<_>>();
+        })
+    });
+}
+
+criterion_group!(benches, bench_compact_chunk_wide_key);
+criterion_main!(benches);
@@ -0,0 +1,54 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use risingwave_common::array::{StreamChunk, StreamChunkCompactor, StreamChunkTestExt};
+
+const NUM_KEYS: usize = 256;
+/// How many `(+, -)` pairs are replayed for the same key before it settles on a final value.
+/// A larger churn count means more rows get cancelled out by `OpRowMutRefTuple::push` before the
+/// final visibility bits are applied.
+const CHURN_PER_KEY: &[usize] = &[1, 8, 64];
+
+/// Builds a chunk where each of `NUM_KEYS` keys is inserted and deleted `churn` times in a row,
+/// then inserted once more, so only the final insert survives compaction.
+fn churned_chunk(churn: usize) -> StreamChunk {
+    let mut pretty = String::from(" I I\n");
+    for key in 0..NUM_KEYS {
+        let mut value = 0;
+        for _ in 0..churn {
+            pretty.push_str(&format!("+ {} {}\n", key, value));
+            pretty.push_str(&format!("- {} {}\n", key, value));
+            value += 1;
+        }
+        pretty.push_str(&format!("+ {} {}\n", key, value));
+    }
+    StreamChunk::from_pretty(&pretty)
+}
+
+fn bench_stream_chunk_compactor_cancel(c: &mut Criterion) {
+    for &churn in CHURN_PER_KEY {
+        let chunk = churned_chunk(churn);
+        c.bench_function(&format!("compact chunk: churn {}", churn), |b| {
+            b.iter(|| {
+                let mut compactor = StreamChunkCompactor::new(vec![0]);
+                compactor.push_chunk(chunk.clone());
+                let _ = compactor.into_compacted_chunks().collect::<Vec<_>>();
+            })
+        });
+    }
+}
+
+criterion_group!(benches, bench_stream_chunk_compactor_cancel);
+criterion_main!(benches);
@@ -13,17 +13,20 @@
 // limitations under the License.
 
 use std::collections::hash_map::Entry;
-use std::collections::HashMap;
-use std::hash::BuildHasherDefault;
-use std::mem;
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, BuildHasherDefault};
 
 use itertools::Itertools;
 use prehash::{new_prehashed_map_with_capacity, Passthru, Prehashed};
 
 use super::stream_chunk::{OpRowMutRef, StreamChunkMut};
-use crate::array::{Op, RowRef, StreamChunk};
+use crate::array::stream_chunk_builder::StreamChunkBuilder;
+use crate::array::{DataChunk, Op, RowRef, StreamChunk};
+use crate::buffer::Bitmap;
 use crate::row::{Project, RowExt};
-use crate::util::hash_util::Crc32FastBuilder;
+use crate::types::DataType;
+use crate::util::hash_util::{Crc32FastBuilder, XxHash64Builder};
+use crate::util::iter_util::ZipEqFast;
 
 /// Compact the stream chunks with just modify the `Ops` and visibility of the chunk. Currently, two
 /// transformation will be applied
@@ -31,9 +34,24 @@ use crate::util::hash_util::Crc32FastBuilder;
 ///   have three kind of patterns Insert, Delete or Update.
 /// - For the update (-old row, +old row), when old row is exactly same. The two rowOp will be
 ///   removed.
-pub struct StreamChunkCompactor {
+///
+/// There's intentionally no constructor or `push_*` method that only borrows its chunks.
+/// `compact_impl` always converts every pushed chunk into a [`StreamChunkMut`] to flip ops and
+/// visibility bits in place, which requires owning it; a borrowing variant would therefore still
+/// have to clone internally before that conversion; it would just defer the clone instead of
+/// avoiding it, at the cost of a second, confusingly similar API. Callers that only have a
+/// `&[StreamChunk]` and want to keep the originals should clone at the call site with
+/// [`StreamChunk::clone`] before calling [`Self::push_chunk`].
+pub struct StreamChunkCompactor<H: BuildHasher + Copy = Crc32FastBuilder> {
     chunks: Vec<StreamChunk>,
     stream_key: Vec<usize>,
+    /// When set, [`StreamChunkCompactor::into_compacted_chunks`] emits one extra zero-row marker
+    /// chunk if every input chunk fully cancelled out, so a downstream operator that needs at
+    /// least a heartbeat to advance still sees something even though no data survived compaction.
+    emit_empty_marker_when_fully_cancelled: bool,
+    /// See [`StreamChunkCompactor::unsafe_with_hash_only_dedup`].
+    hash_only: bool,
+    hash_builder: H,
 }
 
 struct OpRowMutRefTuple<'a> {
@@ -42,32 +60,45 @@ struct OpRowMutRefTuple<'a> {
 }
 
 impl<'a> OpRowMutRefTuple<'a> {
-    /// return true if no row left
-    fn push(&mut self, mut op_row: OpRowMutRef<'a>) -> bool {
+    /// Push a newly-seen row for this stream key into the tuple.
+    ///
+    /// Rather than calling `set_vis(false)` on cancelled-out rows immediately, this hands them to
+    /// `to_hide` and lets the caller flip their visibility bits in one final pass. This matters
+    /// for chunks with many redundant inserts/deletes for the same key (e.g. hot keys under heavy
+    /// churn), where the naive approach touches the same underlying `StreamChunkMut` bitmap
+    /// repeatedly while the dedup loop is still running.
+    ///
+    /// Returns `None` if no row is left for this key (the caller should drop the entry).
+    fn push(mut self, op_row: OpRowMutRef<'a>, to_hide: &mut Vec<OpRowMutRef<'a>>) -> Option<Self> {
         debug_assert!(self.latest.vis());
         match (self.latest.op(), op_row.op()) {
             (Op::Insert, Op::Insert) => panic!("receive duplicated insert on the stream"),
             (Op::Delete, Op::Delete) => panic!("receive duplicated delete on the stream"),
             (Op::Insert, Op::Delete) => {
-                self.latest.set_vis(false);
-                op_row.set_vis(false);
-                self.latest = if let Some(prev) = self.previous.take() {
-                    prev
-                } else {
-                    return true;
-                }
+                to_hide.push(self.latest);
+                to_hide.push(op_row);
+                self.previous.take().map(|prev| OpRowMutRefTuple {
+                    previous: None,
+                    latest: prev,
+                })
             }
             (Op::Delete, Op::Insert) => {
                 // The operation for the key must be (+, -, +) or (-, +). And the (+, -) must has
                 // been filtered.
                 debug_assert!(self.previous.is_none());
-                self.previous = Some(mem::replace(&mut self.latest, op_row));
+                Some(OpRowMutRefTuple {
+                    previous: Some(self.latest),
+                    latest: op_row,
+                })
             }
-            // `all the updateDelete` and `updateInsert` should be normalized to `delete`
-            // and`insert`
-            _ => unreachable!(),
-        };
-        false
+            // all `UpdateDelete` and `UpdateInsert` should have been normalized to `Delete` and
+            // `Insert` via `Op::normalize_update` before being pushed here.
+            (latest_op, op) => unreachable!(
+                "unnormalized update op reached OpRowMutRefTuple::push: latest={:?}, new={:?}; \
+                 caller must call `.op().normalize_update()` on every row before pushing",
+                latest_op, op
+            ),
+        }
     }
 
     fn as_update_op(&mut self) -> Option<(&mut OpRowMutRef<'a>, &mut OpRowMutRef<'a>)> {
@@ -84,16 +115,56 @@ type OpRowMap<'a, 'b> =
 
 impl StreamChunkCompactor {
     pub fn new(stream_key: Vec<usize>) -> Self {
+        Self::with_hasher(stream_key, Crc32FastBuilder)
+    }
+}
+
+impl<H: BuildHasher + Copy> StreamChunkCompactor<H> {
+    /// Like [`StreamChunkCompactor::new`], but with an explicit dedup-key hash builder instead of
+    /// the default [`Crc32FastBuilder`]. Useful for adversarial key distributions that cause
+    /// excessive CRC32 collisions, e.g. [`XxHash64Builder`](crate::util::hash_util::XxHash64Builder).
+    pub fn with_hasher(stream_key: Vec<usize>, hash_builder: H) -> Self {
         Self {
             stream_key,
             chunks: vec![],
+            emit_empty_marker_when_fully_cancelled: false,
+            hash_only: false,
+            hash_builder,
         }
     }
 
+    pub fn with_emit_empty_marker(mut self) -> Self {
+        self.emit_empty_marker_when_fully_cancelled = true;
+        self
+    }
+
+    /// Dedup rows by a 128-bit fingerprint of the stream key alone (the configured hasher's value
+    /// combined with an `XxHash64` value), skipping the full key-equality check that
+    /// `compact_impl` otherwise does to disambiguate hash collisions.
+    ///
+    /// This is unsafe in the sense that two genuinely different stream keys can hash to the same
+    /// fingerprint and get incorrectly merged or cancelled against each other. With the default
+    /// [`Crc32FastBuilder`], whose values only use 32 bits, the fingerprint carries 96 bits of
+    /// hash, so the probability is on the order of 2^-96. Only turn this on for workloads with very wide composite keys (where
+    /// the full equality check is the bottleneck) that can tolerate that vanishingly small but
+    /// nonzero risk.
+    pub fn unsafe_with_hash_only_dedup(mut self) -> Self {
+        self.hash_only = true;
+        self
+    }
+
     pub fn into_inner(self) -> (Vec<StreamChunk>, Vec<usize>) {
         (self.chunks, self.stream_key)
     }
 
+    /// Cheap pre-check used by [`Self::compact_impl`] to skip compaction entirely when no two
+    /// rows can share a stream key. See the call site for why a hash collision is the only way
+    /// this can be wrong, and why that's safe.
+    fn hash_values_are_pairwise_distinct(hash_values: &[Vec<u64>]) -> bool {
+        let mut seen = HashSet::with_capacity(hash_values.iter().map(|v| v.len()).sum());
+        hash_values.iter().flatten().all(|&hash| seen.insert(hash))
+    }
+
     pub fn push_chunk(&mut self, c: StreamChunk) {
         self.chunks.push(c);
     }
@@ -102,59 +173,229 @@ impl StreamChunkCompactor {
     /// and UPDATE DELETE will be converted to INSERT and DELETE, and dropped according to
     /// certain rules (see `merge_insert` and `merge_delete` for more details).
     pub fn into_compacted_chunks(self) -> impl Iterator<Item = StreamChunk> {
-        let (chunks, key_indices) = self.into_inner();
+        let emit_empty_marker_when_fully_cancelled = self.emit_empty_marker_when_fully_cancelled;
+        let (compacted_chunks, _stats) = self.compact_impl();
 
-        let estimate_size = chunks.iter().map(|c| c.cardinality()).sum();
-        let mut chunks: Vec<(Vec<u64>, StreamChunkMut)> = chunks
-            .into_iter()
+        let empty_marker = if emit_empty_marker_when_fully_cancelled
+            && !compacted_chunks.is_empty()
+            && compacted_chunks.iter().all(|c| c.cardinality() == 0)
+        {
+            Some(empty_chunk_like(&compacted_chunks[0]))
+        } else {
+            None
+        };
+
+        compacted_chunks.into_iter().chain(empty_marker)
+    }
+
+    /// Like [`Self::into_compacted_chunks`], but also re-chunks the visible rows into
+    /// `chunk_size`-sized [`StreamChunk`]s and reports [`CompactionStats`] about how much
+    /// redundancy was removed. Useful for operators that compact then immediately consume, since
+    /// it avoids a second pass over the compacted output to gather the same numbers.
+    pub fn compact_with_stats(
+        self,
+        chunk_size: usize,
+        data_types: Vec<DataType>,
+    ) -> (Vec<StreamChunk>, CompactionStats) {
+        let (compacted_chunks, stats) = self.compact_impl();
+
+        let mut builder = StreamChunkBuilder::new(chunk_size, data_types);
+        let mut chunks = Vec::new();
+        for chunk in &compacted_chunks {
+            for (op, row) in chunk.rows() {
+                if let Some(c) = builder.append_row(op, row) {
+                    chunks.push(c);
+                }
+            }
+        }
+        if let Some(c) = builder.take() {
+            chunks.push(c);
+        }
+
+        (chunks, stats)
+    }
+
+    /// Shared compaction pass used by both [`Self::into_compacted_chunks`] and
+    /// [`Self::compact_with_stats`].
+    fn compact_impl(self) -> (Vec<StreamChunk>, CompactionStats) {
+        let hash_builder = self.hash_builder;
+        let hash_only = self.hash_only;
+        let (chunks, key_indices) = (self.chunks, self.stream_key);
+
+        let hash_values = chunks
+            .iter()
             .map(|c| {
-                let hash_values = c
-                    .data_chunk()
-                    .get_hash_values(&key_indices, Crc32FastBuilder)
+                c.data_chunk()
+                    .get_hash_values(&key_indices, hash_builder)
                     .into_iter()
                     .map(|hash| hash.value())
-                    .collect_vec();
-                (hash_values, StreamChunkMut::from(c))
+                    .collect_vec()
+            })
+            .collect_vec();
+
+        // Fast path: two rows with the same stream key always hash identically, so pairwise
+        // distinct hash values imply pairwise distinct keys. In that case no row can possibly be
+        // merged or cancelled with another, and if no row carries an `UpdateDelete`/`UpdateInsert`
+        // op either (which the dedup loop would normalize), the dedup loop below would be a
+        // no-op -- skip it and return the chunks completely unmodified. This can only trigger the
+        // (harmless) slow path on a hash collision between two actually-distinct keys; it can
+        // never wrongly skip compaction when a genuine duplicate key exists, since that duplicate
+        // is guaranteed to show up as a hash collision too.
+        if chunks
+            .iter()
+            .all(|c| c.ops().iter().all(|op| matches!(op, Op::Insert | Op::Delete)))
+            && Self::hash_values_are_pairwise_distinct(&hash_values)
+        {
+            return (chunks, CompactionStats::default());
+        }
+
+        let estimate_size = chunks.iter().map(|c| c.cardinality()).sum();
+        let mut chunks: Vec<(Vec<u64>, Vec<u64>, StreamChunkMut)> = chunks
+            .into_iter()
+            .zip_eq_fast(hash_values)
+            .map(|(c, hash_values)| {
+                // Only computed in hash-only mode (see `unsafe_with_hash_only_dedup`), where it's
+                // combined with `hash_values` into a 128-bit fingerprint so the dedup map can skip
+                // the full key-equality check below.
+                let wide_hash_values = if hash_only {
+                    c.data_chunk()
+                        .get_hash_values(&key_indices, XxHash64Builder)
+                        .into_iter()
+                        .map(|hash| hash.value())
+                        .collect_vec()
+                } else {
+                    Vec::new()
+                };
+                (hash_values, wide_hash_values, StreamChunkMut::from(c))
             })
             .collect_vec();
 
-        let mut op_row_map: OpRowMap<'_, '_> = new_prehashed_map_with_capacity(estimate_size);
-        for (hash_values, c) in &mut chunks {
-            for (row, mut op_row) in c.to_rows_mut() {
-                op_row.set_op(op_row.op().normalize_update());
-                let hash = hash_values[row.index()];
-                let stream_key = row.project(&key_indices);
-                match op_row_map.entry(Prehashed::new(stream_key, hash)) {
-                    Entry::Vacant(v) => {
-                        v.insert(OpRowMutRefTuple {
-                            previous: None,
-                            latest: op_row,
-                        });
+        let mut stats = CompactionStats::default();
+        // Rows cancelled out by `OpRowMutRefTuple::push` are collected here instead of having
+        // their visibility flipped immediately, so the bitmap mutations all happen in one pass
+        // below rather than being interleaved with the dedup loop.
+        let mut to_hide: Vec<OpRowMutRef<'_>> = Vec::new();
+
+        if hash_only {
+            let mut op_row_map: HashMap<u128, OpRowMutRefTuple<'_>> =
+                HashMap::with_capacity(estimate_size);
+            for (hash_values, wide_hash_values, c) in &mut chunks {
+                for (row, mut op_row) in c.to_rows_mut() {
+                    op_row.set_op(op_row.op().normalize_update());
+                    let idx = row.index();
+                    let key = ((hash_values[idx] as u128) << 64) | (wide_hash_values[idx] as u128);
+                    match op_row_map.entry(key) {
+                        Entry::Vacant(v) => {
+                            v.insert(OpRowMutRefTuple {
+                                previous: None,
+                                latest: op_row,
+                            });
+                        }
+                        Entry::Occupied(o) => {
+                            let (key, tuple) = o.remove_entry();
+                            if let Some(tuple) = tuple.push(op_row, &mut to_hide) {
+                                op_row_map.insert(key, tuple);
+                            }
+                        }
                     }
-                    Entry::Occupied(mut o) => {
-                        if o.get_mut().push(op_row) {
-                            o.remove_entry();
+                }
+            }
+            stats.rows_removed += to_hide.len();
+            for mut op_row in to_hide {
+                op_row.set_vis(false);
+            }
+            for tuple in op_row_map.values_mut() {
+                if let Some((prev, latest)) = tuple.as_update_op() {
+                    if prev.row_ref() == latest.row_ref() {
+                        prev.set_vis(false);
+                        latest.set_vis(false);
+                        stats.rows_removed += 2;
+                        stats.noop_updates_dropped += 1;
+                    } else if prev.same_chunk(latest) && prev.index() + 1 == latest.index() {
+                        prev.set_op(Op::UpdateDelete);
+                        latest.set_op(Op::UpdateInsert);
+                        stats.updates_collapsed += 1;
+                    }
+                }
+            }
+        } else {
+            let mut op_row_map: OpRowMap<'_, '_> = new_prehashed_map_with_capacity(estimate_size);
+            for (hash_values, _, c) in &mut chunks {
+                for (row, mut op_row) in c.to_rows_mut() {
+                    op_row.set_op(op_row.op().normalize_update());
+                    let hash = hash_values[row.index()];
+                    let stream_key = row.project(&key_indices);
+                    match op_row_map.entry(Prehashed::new(stream_key, hash)) {
+                        Entry::Vacant(v) => {
+                            v.insert(OpRowMutRefTuple {
+                                previous: None,
+                                latest: op_row,
+                            });
+                        }
+                        Entry::Occupied(o) => {
+                            let (key, tuple) = o.remove_entry();
+                            if let Some(tuple) = tuple.push(op_row, &mut to_hide) {
+                                op_row_map.insert(key, tuple);
+                            }
                         }
                     }
                 }
             }
-        }
-        for tuple in op_row_map.values_mut() {
-            if let Some((prev, latest)) = tuple.as_update_op() {
-                if prev.row_ref() == latest.row_ref() {
-                    prev.set_vis(false);
-                    latest.set_vis(false);
-                } else if prev.same_chunk(latest) && prev.index() + 1 == latest.index() {
-                    // TODO(st1page): use next_one check in bitmap
-                    prev.set_op(Op::UpdateDelete);
-                    latest.set_op(Op::UpdateInsert);
+            stats.rows_removed += to_hide.len();
+            for mut op_row in to_hide {
+                op_row.set_vis(false);
+            }
+            for tuple in op_row_map.values_mut() {
+                if let Some((prev, latest)) = tuple.as_update_op() {
+                    if prev.row_ref() == latest.row_ref() {
+                        prev.set_vis(false);
+                        latest.set_vis(false);
+                        stats.rows_removed += 2;
+                        stats.noop_updates_dropped += 1;
+                    } else if prev.same_chunk(latest) && prev.index() + 1 == latest.index() {
+                        // TODO(st1page): use next_one check in bitmap
+                        prev.set_op(Op::UpdateDelete);
+                        latest.set_op(Op::UpdateInsert);
+                        stats.updates_collapsed += 1;
+                    }
                 }
             }
         }
-        chunks.into_iter().map(|(_, c)| c.into())
+
+        let compacted_chunks = chunks
+            .into_iter()
+            .map(|(_, _, c)| StreamChunk::from(c))
+            .collect_vec();
+
+        (compacted_chunks, stats)
     }
 }
 
+/// Stats about a [`StreamChunkCompactor`] pass, as returned by
+/// [`StreamChunkCompactor::compact_with_stats`].
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct CompactionStats {
+    /// Rows dropped entirely because they were cancelled out by a later op on the same stream
+    /// key (e.g. a `(+, -)` pair, or a no-op update — see `noop_updates_dropped`).
+    pub rows_removed: usize,
+    /// `(-old, +new)` pairs on the same key collapsed into a single `UpdateDelete`/`UpdateInsert`
+    /// pair.
+    pub updates_collapsed: usize,
+    /// `(-old, +new)` pairs on the same key dropped entirely because `old == new`.
+    pub noop_updates_dropped: usize,
+}
+
+/// Builds a zero-row, zero-capacity [`StreamChunk`] with the same column types as `chunk`.
+fn empty_chunk_like(chunk: &StreamChunk) -> StreamChunk {
+    let columns = chunk
+        .columns()
+        .iter()
+        .map(|col| col.create_builder(0).finish().into_ref())
+        .collect_vec();
+    let data_chunk = DataChunk::from_parts(columns.into(), Bitmap::ones(0));
+    StreamChunk::from_parts(Vec::<Op>::new(), data_chunk)
+}
+
 pub fn merge_chunk_row(stream_chunk: StreamChunk, pk_indices: &[usize]) -> StreamChunk {
     let mut compactor = StreamChunkCompactor::new(pk_indices.to_vec());
     compactor.push_chunk(stream_chunk);
@@ -213,4 +454,191 @@ mod tests {
 
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn test_empty_marker_when_fully_cancelled() {
+        let pk_indices = [0, 1];
+
+        // Without the flag, a fully-cancelled chunk still yields its (all-invisible) chunk, but
+        // no extra marker.
+        let mut compactor = StreamChunkCompactor::new(pk_indices.to_vec());
+        compactor.push_chunk(StreamChunk::from_pretty(
+            " I I I
+            + 1 1 1
+            - 1 1 1",
+        ));
+        let chunks = compactor.into_compacted_chunks().collect_vec();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].cardinality(), 0);
+
+        // With the flag, an extra zero-capacity marker chunk is appended.
+        let mut compactor = StreamChunkCompactor::new(pk_indices.to_vec()).with_emit_empty_marker();
+        compactor.push_chunk(StreamChunk::from_pretty(
+            " I I I
+            + 1 1 1
+            - 1 1 1",
+        ));
+        let chunks = compactor.into_compacted_chunks().collect_vec();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[1].cardinality(), 0);
+        assert_eq!(chunks[1].capacity(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "unnormalized update op reached OpRowMutRefTuple::push")]
+    fn test_push_unnormalized_update_op_panics() {
+        let chunk = StreamChunk::from_pretty(
+            " I
+            U- 1
+            U+ 2",
+        );
+        let mut chunk_mut = StreamChunkMut::from(chunk);
+        let mut rows_mut = chunk_mut.to_rows_mut();
+        let (_, latest) = rows_mut.next().unwrap();
+        let (_, next) = rows_mut.next().unwrap();
+        let tuple = OpRowMutRefTuple {
+            previous: None,
+            latest,
+        };
+        // `push` is only ever called with normalized ops in `into_compacted_chunks`; feeding it an
+        // un-normalized `UpdateInsert` directly should panic with a diagnostic naming both ops,
+        // rather than the plain `unreachable!()` this used to be.
+        let mut to_hide = Vec::new();
+        tuple.push(next, &mut to_hide);
+    }
+
+    #[test]
+    fn test_push_batches_cancelled_rows_into_to_hide() {
+        // Many redundant (+, -) pairs on the same key: every push cancels the previous row
+        // instead of touching the bitmap immediately, they should all end up in `to_hide` and
+        // be applied in a single pass.
+        let pk_indices = [0];
+        let mut compactor = StreamChunkCompactor::new(pk_indices.to_vec());
+        compactor.push_chunk(StreamChunk::from_pretty(
+            " I I
+            + 1 1
+            - 1 1
+            + 1 2
+            - 1 2
+            + 1 3
+            - 1 3
+            + 1 4",
+        ));
+        let chunks = compactor.into_compacted_chunks().collect_vec();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(
+            chunks[0].clone().compact(),
+            StreamChunk::from_pretty(
+                " I I
+                + 1 4",
+            )
+        );
+    }
+
+    #[test]
+    fn test_unique_key_fast_path_skips_compaction() {
+        let pk_indices = [0];
+        let chunk = StreamChunk::from_pretty(
+            " I I
+            + 1 10
+            + 2 20
+            - 3 30
+            + 4 40",
+        );
+        let mut compactor = StreamChunkCompactor::new(pk_indices.to_vec());
+        compactor.push_chunk(chunk.clone());
+        let chunks = compactor.into_compacted_chunks().collect_vec();
+
+        // No two rows share key `1`/`2`/`3`/`4`, so the fast path should kick in and leave the
+        // chunk completely untouched -- not just logically equivalent after compaction, but
+        // identical (same ops, same visibility).
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0], chunk);
+    }
+
+    #[test]
+    fn test_hash_only_dedup() {
+        let pk_indices = [0, 1];
+        let mut compactor =
+            StreamChunkCompactor::new(pk_indices.to_vec()).unsafe_with_hash_only_dedup();
+        compactor.push_chunk(StreamChunk::from_pretty(
+            " I I I
+            - 1 1 1
+            + 1 1 2
+            + 2 5 7
+            + 4 9 2
+            - 2 5 7
+            + 2 5 5
+            - 6 6 9
+            + 6 6 9
+            - 9 9 1",
+        ));
+        let mut iter = compactor.into_compacted_chunks();
+        assert_eq!(
+            iter.next().unwrap().compact(),
+            StreamChunk::from_pretty(
+                " I I I
+                U- 1 1 1
+                U+ 1 1 2
+                + 4 9 2
+                + 2 5 5
+                - 9 9 1",
+            )
+        );
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_compact_with_stats() {
+        let pk_indices = [0, 1];
+        let mut compactor = StreamChunkCompactor::new(pk_indices.to_vec());
+        compactor.push_chunk(StreamChunk::from_pretty(
+            " I I I
+            - 1 1 1
+            + 1 1 2
+            + 2 5 7
+            + 4 9 2
+            - 2 5 7
+            + 2 5 5
+            - 6 6 9
+            + 6 6 9
+            - 9 9 1",
+        ));
+        compactor.push_chunk(StreamChunk::from_pretty(
+            " I I I
+            - 6 6 9
+            + 9 9 9
+            - 9 9 4
+            + 2 2 2
+            + 9 9 1",
+        ));
+        let (chunks, stats) = compactor.compact_with_stats(1024, vec![DataType::Int64; 3]);
+
+        // (1 1 1) -> (1 1 2): collapsed into an update pair.
+        // (9 9 1) -> (9 9 1) (with (9 9 9)/(9 9 4) cancelled in between): a no-op update, both
+        // rows dropped.
+        // (2 5 7) inserted then deleted: cancelled out.
+        // (6 6 9): deleted, inserted, then deleted again: only the first delete survives.
+        assert_eq!(stats.updates_collapsed, 1);
+        assert_eq!(stats.noop_updates_dropped, 1);
+        assert!(stats.rows_removed > 0);
+
+        let total_visible: usize = chunks.iter().map(|c| c.cardinality()).sum();
+        assert_eq!(
+            total_visible,
+            chunks.iter().map(|c| c.compact().cardinality()).sum::<usize>()
+        );
+        assert_eq!(
+            chunks.into_iter().next().unwrap().compact(),
+            StreamChunk::from_pretty(
+                " I I I
+                U- 1 1 1
+                U+ 1 1 2
+                + 4 9 2
+                + 2 5 5
+                - 6 6 9
+                + 2 2 2",
+            )
+        );
+    }
 }
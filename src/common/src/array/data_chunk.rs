@@ -160,6 +160,20 @@ impl DataChunk {
         }
     }
 
+    /// `null_ratio` returns the ratio of null values in the given column, ignoring
+    /// row visibility. This is cheap (reuses the column's null `Bitmap` population count)
+    /// and is meant to help encoders decide between dense and sparse serialization.
+    pub fn null_ratio(&self, column_idx: usize) -> f64 {
+        let null_bitmap = self.columns[column_idx].null_bitmap();
+        if null_bitmap.is_empty() {
+            0.0
+        } else if null_bitmap.all() {
+            0.0
+        } else {
+            (null_bitmap.len() - null_bitmap.count_ones()) as f64 / null_bitmap.len() as f64
+        }
+    }
+
     pub fn with_visibility(&self, visibility: impl Into<Bitmap>) -> Self {
         DataChunk {
             columns: self.columns.clone(),
@@ -1075,4 +1089,19 @@ mod tests {
             .estimated_heap_size()
         );
     }
+
+    #[test]
+    fn test_null_ratio() {
+        let chunk = DataChunk::new(
+            vec![
+                Arc::new(I64Array::from_iter([Some(1), Some(2), Some(3), Some(4)]).into()),
+                Arc::new(I64Array::from_iter([Some(6), None, Some(7), None]).into()),
+                Arc::new(I64Array::from_iter([None, None, None, None]).into()),
+            ],
+            4,
+        );
+        assert_eq!(chunk.null_ratio(0), 0.0);
+        assert_eq!(chunk.null_ratio(1), 0.5);
+        assert_eq!(chunk.null_ratio(2), 1.0);
+    }
 }
@@ -31,6 +31,7 @@ use crate::buffer::{Bitmap, BitmapBuilder};
 use crate::catalog::Schema;
 use crate::estimate_size::EstimateSize;
 use crate::field_generator::VarcharProperty;
+use crate::hash::VirtualNode;
 use crate::row::Row;
 use crate::types::{DataType, DefaultOrdered, ToText};
 
@@ -197,6 +198,37 @@ impl StreamChunk {
         outputs
     }
 
+    /// Splits this chunk into per-vnode sub-chunks, keyed by the [`VirtualNode`] each row would
+    /// be routed to under RisingWave's hash distribution on `dist_key_indices`. Useful for sinks
+    /// that want to mirror RisingWave's own vnode layout on a partitioned downstream.
+    ///
+    /// Each sub-chunk shares `self`'s shape and capacity; a row is visible in exactly one
+    /// sub-chunk (the one keyed by its vnode), or none if it was already invisible in `self`.
+    /// Vnodes with no visible row in `self` are omitted.
+    pub fn split_by_vnode(
+        &self,
+        dist_key_indices: &[usize],
+    ) -> std::collections::HashMap<VirtualNode, Self> {
+        let vnodes = VirtualNode::compute_chunk(self.data_chunk(), dist_key_indices);
+
+        let mut vis_builders: std::collections::HashMap<VirtualNode, BitmapBuilder> =
+            std::collections::HashMap::new();
+        for (i, vnode) in vnodes.into_iter().enumerate() {
+            if !self.visibility().is_set(i) {
+                continue;
+            }
+            vis_builders
+                .entry(vnode)
+                .or_insert_with(|| BitmapBuilder::zeroed(self.capacity()))
+                .set(i, true);
+        }
+
+        vis_builders
+            .into_iter()
+            .map(|(vnode, builder)| (vnode, self.clone_with_vis(builder.finish())))
+            .collect()
+    }
+
     pub fn into_parts(self) -> (DataChunk, Arc<[Op]>) {
         (self.data, self.ops)
     }
@@ -860,4 +892,25 @@ mod tests {
 +---+---+---+"
         );
     }
+
+    #[test]
+    fn test_split_by_vnode() {
+        let chunk = StreamChunk::from_pretty(
+            "  I I
+             + 1 6
+             + 2 7
+             - 3 8
+             + 4 9",
+        );
+        let sub_chunks = chunk.split_by_vnode(&[0]);
+
+        let total_cardinality: usize = sub_chunks.values().map(|c| c.cardinality()).sum();
+        assert_eq!(total_cardinality, chunk.cardinality());
+
+        for (vnode, sub_chunk) in &sub_chunks {
+            for (_, row) in sub_chunk.rows() {
+                assert_eq!(VirtualNode::compute_row(row, &[0]), *vnode);
+            }
+        }
+    }
 }
@@ -198,6 +198,12 @@ pub struct MetaConfig {
     #[serde(default = "default::meta::periodic_compaction_interval_sec")]
     pub periodic_compaction_interval_sec: u64,
 
+    /// Compaction groups excluded from the periodic compaction triggers (dynamic, space-reclaim,
+    /// TTL and tombstone-reclaim). Useful when certain groups are managed by an external policy;
+    /// they can still be scheduled on demand via other paths (e.g. `try_sched_compaction`).
+    #[serde(default = "default::meta::periodic_compaction_schedule_excluded_groups")]
+    pub periodic_compaction_schedule_excluded_groups: Vec<u64>,
+
     /// Interval of invoking a vacuum job, to remove stale metadata from meta store and objects
     /// from object store.
     #[serde(default = "default::meta::vacuum_interval_sec")]
@@ -846,6 +852,20 @@ pub struct StreamingDeveloperConfig {
     #[serde(default = "default::developer::stream_enable_executor_row_count")]
     pub enable_executor_row_count: bool,
 
+    /// Set to true to group all actors' tracing spans for the same epoch into a single span on
+    /// the barrier-send path, so their activity can be inspected together in a trace. This
+    /// significantly increases the overhead of tracing, so it's recommended to only turn it on
+    /// temporarily for debugging.
+    #[serde(default = "default::developer::stream_enable_barrier_aggregation")]
+    pub enable_barrier_aggregation: bool,
+
+    /// The timeout in milliseconds for `LocalBarrierWorker` to gather additional actor failures
+    /// before picking a root cause to report, after the first one is observed. A longer timeout
+    /// gives slower-to-fail actors more of a chance to report their error, which can otherwise be
+    /// masked by faster but less informative errors (e.g. a downstream actor's channel closing).
+    #[serde(default = "default::developer::stream_actor_failure_gather_timeout_ms")]
+    pub actor_failure_gather_timeout_ms: u64,
+
     /// The capacity of the chunks in the channel that connects between `ConnectorSource` and
     /// `SourceExecutor`.
     #[serde(default = "default::developer::connector_message_buffer_size")]
@@ -1059,6 +1079,10 @@ pub mod default {
             60
         }
 
+        pub fn periodic_compaction_schedule_excluded_groups() -> Vec<u64> {
+            vec![]
+        }
+
         pub fn vacuum_interval_sec() -> u64 {
             30
         }
@@ -1463,6 +1487,14 @@ pub mod default {
             false
         }
 
+        pub fn stream_enable_barrier_aggregation() -> bool {
+            false
+        }
+
+        pub fn stream_actor_failure_gather_timeout_ms() -> u64 {
+            3000
+        }
+
         pub fn connector_message_buffer_size() -> usize {
             16
         }
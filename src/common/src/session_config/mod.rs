@@ -90,7 +90,9 @@ pub struct ConfigMap {
     batch_enable_lookup_join: bool,
 
     /// Enable usage of sortAgg instead of hash agg when order property is satisfied in batch
-    /// execution
+    /// execution. Set to `false` to force `BatchHashAgg` even when the input happens to be
+    /// sorted on the group keys; there is no corresponding way to force `BatchSortAgg` when the
+    /// input isn't actually sorted, since that would produce incorrect results.
     #[parameter(default = true, rename = "rw_batch_enable_sort_agg")]
     batch_enable_sort_agg: bool,
 
@@ -31,6 +31,7 @@ pub mod prost;
 pub mod query_log;
 pub mod resource_util;
 pub mod row_id;
+pub mod row_op_map;
 pub mod row_serde;
 pub mod runtime;
 pub mod scan_range;
@@ -17,7 +17,9 @@ use std::time::Duration;
 
 use anyhow::{anyhow, Context};
 use aws_config::timeout::TimeoutConfig;
+use aws_sdk_s3::operation::get_object::GetObjectError;
 use aws_sdk_s3::{client as s3_client, config as s3_config};
+use aws_smithy_runtime_api::client::result::SdkError;
 use url::Url;
 
 use crate::common::AwsAuthProps;
@@ -115,7 +117,10 @@ pub async fn load_file_descriptor_from_s3(
         .path()
         .strip_prefix('/')
         .ok_or_else(|| anyhow!("s3 url {location} should have a '/' at the start of path."))?;
-    let sdk_config = config.build_config().await?;
+    let sdk_config = config
+        .build_config()
+        .await
+        .with_context(|| format!("failed to resolve AWS credentials for `{}`", location))?;
     let s3_client = s3_client(&sdk_config, Some(default_conn_config()));
     let response = s3_client
         .get_object()
@@ -123,7 +128,17 @@ pub async fn load_file_descriptor_from_s3(
         .key(key)
         .send()
         .await
-        .with_context(|| format!("failed to get file from s3 at `{}`", location))?;
+        .map_err(|e| match &e {
+            SdkError::ServiceError(service_err)
+                if matches!(service_err.err(), GetObjectError::NoSuchKey(_)) =>
+            {
+                anyhow!(e).context(format!("no such object at s3 location `{}`", location))
+            }
+            _ => anyhow!(e).context(format!(
+                "failed to get file from s3 at `{}`, check AWS credentials and permissions",
+                location
+            )),
+        })?;
 
     let body = response
         .body
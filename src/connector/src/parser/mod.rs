@@ -27,6 +27,7 @@ pub use json_parser::*;
 pub use protobuf::*;
 use risingwave_common::array::{ArrayBuilderImpl, Op, StreamChunk};
 use risingwave_common::bail;
+use risingwave_common::buffer::BitmapBuilder;
 use risingwave_common::catalog::{KAFKA_TIMESTAMP_COLUMN_NAME, TABLE_NAME_COLUMN_NAME};
 use risingwave_common::log::LogSuppresser;
 use risingwave_common::metrics::GLOBAL_ERROR_METRICS;
@@ -87,6 +88,7 @@ pub struct SourceStreamChunkBuilder {
     descs: Vec<SourceColumnDesc>,
     builders: Vec<ArrayBuilderImpl>,
     op_builder: Vec<Op>,
+    vis_builder: BitmapBuilder,
 }
 
 impl SourceStreamChunkBuilder {
@@ -100,6 +102,7 @@ impl SourceStreamChunkBuilder {
             descs,
             builders,
             op_builder: Vec::with_capacity(cap),
+            vis_builder: BitmapBuilder::with_capacity(cap),
         }
     }
 
@@ -108,18 +111,21 @@ impl SourceStreamChunkBuilder {
             descs: &self.descs,
             builders: &mut self.builders,
             op_builder: &mut self.op_builder,
+            vis_builder: &mut self.vis_builder,
+            visible: true,
             row_meta: None,
         }
     }
 
     /// Consumes the builder and returns a [`StreamChunk`].
     pub fn finish(self) -> StreamChunk {
-        StreamChunk::new(
+        StreamChunk::with_visibility(
             self.op_builder,
             self.builders
                 .into_iter()
                 .map(|builder| builder.finish().into())
                 .collect(),
+            self.vis_builder.finish(),
         )
     }
 
@@ -154,6 +160,13 @@ pub struct SourceStreamChunkRowWriter<'a> {
     descs: &'a [SourceColumnDesc],
     builders: &'a mut [ArrayBuilderImpl],
     op_builder: &'a mut Vec<Op>,
+    vis_builder: &'a mut BitmapBuilder,
+
+    /// Whether the row(s) written next are visible to downstream consumers. Set to `false` via
+    /// [`Self::invisible`] for a heartbeat-only row, whose offset/split-id columns should still
+    /// be recorded (so the split's committed offset advances) without the row itself showing up
+    /// in the output.
+    visible: bool,
 
     /// An optional meta data of the original message.
     ///
@@ -316,6 +329,16 @@ impl<'a> SourceStreamChunkRowWriter<'a> {
             ..self
         }
     }
+
+    /// Marks the row(s) written by this writer as invisible to downstream consumers. Used for a
+    /// heartbeat message, which carries no data but whose offset/split-id columns (filled in via
+    /// `with_meta`) should still be recorded so the split's committed offset advances.
+    fn invisible(self) -> Self {
+        Self {
+            visible: false,
+            ..self
+        }
+    }
 }
 
 impl SourceStreamChunkRowWriter<'_> {
@@ -448,7 +471,13 @@ impl SourceStreamChunkRowWriter<'_> {
 
         match result {
             Ok(_) => {
+                let op_count_before = self.op_builder.len();
                 A::finish(self);
+                // `A::finish` pushes one op per row it writes (two for `OpActionUpdate`); mark
+                // each of them with this writer's visibility.
+                for _ in op_count_before..self.op_builder.len() {
+                    self.vis_builder.append(self.visible);
+                }
                 Ok(())
             }
             Err(e) => {
@@ -657,7 +686,30 @@ async fn into_chunk_stream<P: ByteStreamSourceParser>(mut parser: P, data_stream
         let process_time_ms = chrono::Utc::now().timestamp_millis();
         for (i, msg) in batch.into_iter().enumerate() {
             if msg.key.is_none() && msg.payload.is_none() {
-                tracing::debug!(offset = msg.offset, "skip parsing of heartbeat message");
+                tracing::debug!(offset = msg.offset, "handling heartbeat message");
+                // No data to parse, but we still record an invisible row carrying the offset, so
+                // that the split's committed offset advances instead of stalling until the next
+                // data message arrives.
+                let old_op_num = builder.op_num();
+                if let Err(error) = builder
+                    .row_writer()
+                    .with_meta(MessageMeta {
+                        meta: &msg.meta,
+                        split_id: &msg.split_id,
+                        offset: &msg.offset,
+                    })
+                    .invisible()
+                    .insert(|_column| Ok(None))
+                {
+                    tracing::warn!(
+                        error = %error.as_report(),
+                        offset = msg.offset,
+                        "failed to record heartbeat offset"
+                    );
+                }
+                if let Some(Transaction { len, .. }) = &mut current_transaction {
+                    *len += builder.op_num() - old_op_num;
+                }
                 continue;
             }
 
@@ -88,6 +88,28 @@ impl SchemaLoader {
         self.load_schema::<Out, false>(self.val_record_name.as_deref())
             .await
     }
+
+    /// The subject the key schema would be registered/looked up under, without actually fetching
+    /// it. Useful for ops tooling that wants to know what a sink will reference up front.
+    pub fn key_subject(&self) -> Result<String, InvalidOptionError> {
+        get_subject_by_strategy(
+            &self.name_strategy,
+            &self.topic,
+            self.key_record_name.as_deref(),
+            true,
+        )
+    }
+
+    /// The subject the value schema would be registered/looked up under, without actually
+    /// fetching it.
+    pub fn val_subject(&self) -> Result<String, InvalidOptionError> {
+        get_subject_by_strategy(
+            &self.name_strategy,
+            &self.topic,
+            self.val_record_name.as_deref(),
+            false,
+        )
+    }
 }
 
 pub trait LoadedSchema: Sized {
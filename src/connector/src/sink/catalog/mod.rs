@@ -134,6 +134,7 @@ pub enum SinkEncode {
     Protobuf,
     Avro,
     Template,
+    Csv,
 }
 
 impl SinkFormatDesc {
@@ -180,6 +181,7 @@ impl SinkFormatDesc {
             SinkEncode::Protobuf => E::Protobuf,
             SinkEncode::Avro => E::Avro,
             SinkEncode::Template => E::Template,
+            SinkEncode::Csv => E::Csv,
         };
         let options = self
             .options
@@ -222,7 +224,8 @@ impl TryFrom<PbSinkFormatDesc> for SinkFormatDesc {
             E::Protobuf => SinkEncode::Protobuf,
             E::Template => SinkEncode::Template,
             E::Avro => SinkEncode::Avro,
-            e @ (E::Unspecified | E::Native | E::Csv | E::Bytes | E::None) => {
+            E::Csv => SinkEncode::Csv,
+            e @ (E::Unspecified | E::Native | E::Bytes | E::None) => {
                 return Err(SinkError::Config(anyhow!(
                     "sink encode unsupported: {}",
                     e.as_str_name()
@@ -0,0 +1,223 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use anyhow::anyhow;
+use risingwave_common::catalog::Schema;
+use risingwave_common::row::Row;
+use risingwave_common::types::ToText;
+use thiserror_ext::AsReport;
+
+use super::{Result, RowEncoder};
+use crate::sink::SinkError;
+
+/// Mirrors [`csv::QuoteStyle`], expressible as a `format_desc` option value.
+#[derive(Clone, Copy, Default)]
+pub enum CsvQuoteStyle {
+    /// Quote fields only when necessary, e.g. when they contain the delimiter, a quote
+    /// character, or a line terminator.
+    #[default]
+    Necessary,
+    Always,
+    Never,
+}
+
+impl CsvQuoteStyle {
+    pub const OPTION_KEY: &'static str = "csv.quote_style";
+
+    pub fn from_options(options: &BTreeMap<String, String>) -> Result<Self> {
+        match options.get(Self::OPTION_KEY).map(std::ops::Deref::deref) {
+            Some("necessary") | None => Ok(Self::Necessary),
+            Some("always") => Ok(Self::Always),
+            Some("never") => Ok(Self::Never),
+            Some(v) => Err(SinkError::Config(anyhow!(
+                "unrecognized {} value {}",
+                Self::OPTION_KEY,
+                v
+            ))),
+        }
+    }
+
+    fn into_csv_quote_style(self) -> csv::QuoteStyle {
+        match self {
+            Self::Necessary => csv::QuoteStyle::Necessary,
+            Self::Always => csv::QuoteStyle::Always,
+            Self::Never => csv::QuoteStyle::Never,
+        }
+    }
+}
+
+/// Encodes a row as a single CSV line (no trailing line terminator; the sink's transport is
+/// expected to supply its own record boundaries).
+pub struct CsvEncoder {
+    schema: Schema,
+    col_indices: Option<Vec<usize>>,
+    delimiter: u8,
+    quote_style: CsvQuoteStyle,
+    null_string: String,
+}
+
+impl CsvEncoder {
+    pub const DELIMITER_OPTION_KEY: &'static str = "csv.delimiter";
+    pub const NULL_STRING_OPTION_KEY: &'static str = "null.string";
+
+    pub fn new(
+        schema: Schema,
+        col_indices: Option<Vec<usize>>,
+        delimiter: u8,
+        quote_style: CsvQuoteStyle,
+        null_string: String,
+    ) -> Self {
+        Self {
+            schema,
+            col_indices,
+            delimiter,
+            quote_style,
+            null_string,
+        }
+    }
+
+    /// Reads [`Self::DELIMITER_OPTION_KEY`] from `options`, defaulting to `,`. The value must be
+    /// exactly one byte, matching the `csv` crate's own delimiter requirement.
+    pub fn delimiter_from_options(options: &BTreeMap<String, String>) -> Result<u8> {
+        match options.get(Self::DELIMITER_OPTION_KEY) {
+            None => Ok(b','),
+            Some(s) => {
+                let bytes = s.as_bytes();
+                if bytes.len() != 1 {
+                    return Err(SinkError::Config(anyhow!(
+                        "{} must be a single byte, got `{}`",
+                        Self::DELIMITER_OPTION_KEY,
+                        s
+                    )));
+                }
+                Ok(bytes[0])
+            }
+        }
+    }
+
+    /// Reads [`Self::NULL_STRING_OPTION_KEY`] from `options`, defaulting to the empty string.
+    pub fn null_string_from_options(options: &BTreeMap<String, String>) -> String {
+        options
+            .get(Self::NULL_STRING_OPTION_KEY)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+impl RowEncoder for CsvEncoder {
+    type Output = String;
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn col_indices(&self) -> Option<&[usize]> {
+        self.col_indices.as_deref()
+    }
+
+    fn encode_cols(
+        &self,
+        row: impl Row,
+        col_indices: impl Iterator<Item = usize>,
+    ) -> Result<Self::Output> {
+        let mut writer = csv::WriterBuilder::new()
+            .delimiter(self.delimiter)
+            .quote_style(self.quote_style.into_csv_quote_style())
+            .has_headers(false)
+            .from_writer(vec![]);
+
+        let record = col_indices
+            .map(|idx| match row.datum_at(idx) {
+                None => self.null_string.clone(),
+                Some(scalar) => scalar.to_text_with_type(&self.schema[idx].data_type),
+            })
+            .collect::<Vec<_>>();
+        writer
+            .write_record(&record)
+            .map_err(|e| SinkError::Encode(e.to_report_string()))?;
+
+        let mut bytes = writer
+            .into_inner()
+            .map_err(|e| SinkError::Encode(e.to_report_string()))?;
+        // The `csv` writer always terminates a record with `\r\n`; each encoded row is a
+        // self-contained line for the sink, so the terminator itself is not part of the output.
+        while matches!(bytes.last(), Some(b'\n' | b'\r')) {
+            bytes.pop();
+        }
+        String::from_utf8(bytes).map_err(|e| SinkError::Encode(e.to_report_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::Field;
+    use risingwave_common::row::OwnedRow;
+    use risingwave_common::types::{DataType, ScalarImpl};
+
+    use super::*;
+
+    fn test_schema() -> Schema {
+        Schema::new(vec![
+            Field::with_name(DataType::Int32, "v1"),
+            Field::with_name(DataType::Varchar, "v2"),
+        ])
+    }
+
+    #[test]
+    fn test_encode_row_with_default_options() {
+        let encoder = CsvEncoder::new(
+            test_schema(),
+            None,
+            b',',
+            CsvQuoteStyle::Necessary,
+            "".to_owned(),
+        );
+        let row = OwnedRow::new(vec![
+            Some(ScalarImpl::Int32(1)),
+            Some(ScalarImpl::Utf8("a,b".into())),
+        ]);
+        assert_eq!(encoder.encode(row).unwrap(), "1,\"a,b\"");
+    }
+
+    #[test]
+    fn test_encode_null_uses_configured_null_string() {
+        let encoder = CsvEncoder::new(
+            test_schema(),
+            None,
+            b',',
+            CsvQuoteStyle::Necessary,
+            "\\N".to_owned(),
+        );
+        let row = OwnedRow::new(vec![Some(ScalarImpl::Int32(1)), None]);
+        assert_eq!(encoder.encode(row).unwrap(), "1,\\N");
+    }
+
+    #[test]
+    fn test_custom_delimiter() {
+        let encoder = CsvEncoder::new(
+            test_schema(),
+            None,
+            b'|',
+            CsvQuoteStyle::Necessary,
+            "".to_owned(),
+        );
+        let row = OwnedRow::new(vec![
+            Some(ScalarImpl::Int32(1)),
+            Some(ScalarImpl::Utf8("a".into())),
+        ]);
+        assert_eq!(encoder.encode(row).unwrap(), "1|a");
+    }
+}
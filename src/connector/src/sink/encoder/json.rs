@@ -41,9 +41,14 @@ pub struct JsonEncoder {
     time_handling_mode: TimeHandlingMode,
     date_handling_mode: DateHandlingMode,
     timestamp_handling_mode: TimestampHandlingMode,
+    timestamp_handling_mode_overrides: HashMap<String, TimestampHandlingMode>,
     timestamptz_handling_mode: TimestamptzHandlingMode,
     custom_json_type: CustomJsonType,
     kafka_connect: Option<KafkaConnectParamsRef>,
+    /// An extra `(key, value)` pair inserted into every encoded object on top of the row's own
+    /// columns, e.g. `__source` identifying which sink wrote the record. See
+    /// [`Self::with_extra_field`].
+    extra_field: Option<(String, Value)>,
 }
 
 impl JsonEncoder {
@@ -61,9 +66,11 @@ impl JsonEncoder {
             time_handling_mode,
             date_handling_mode,
             timestamp_handling_mode,
+            timestamp_handling_mode_overrides: HashMap::new(),
             timestamptz_handling_mode,
             custom_json_type: CustomJsonType::None,
             kafka_connect: None,
+            extra_field: None,
         }
     }
 
@@ -74,9 +81,11 @@ impl JsonEncoder {
             time_handling_mode: TimeHandlingMode::String,
             date_handling_mode: DateHandlingMode::String,
             timestamp_handling_mode: TimestampHandlingMode::String,
+            timestamp_handling_mode_overrides: HashMap::new(),
             timestamptz_handling_mode: TimestamptzHandlingMode::UtcWithoutSuffix,
             custom_json_type: CustomJsonType::Es,
             kafka_connect: None,
+            extra_field: None,
         }
     }
 
@@ -91,9 +100,11 @@ impl JsonEncoder {
             time_handling_mode: TimeHandlingMode::Milli,
             date_handling_mode: DateHandlingMode::String,
             timestamp_handling_mode: TimestampHandlingMode::String,
+            timestamp_handling_mode_overrides: HashMap::new(),
             timestamptz_handling_mode: TimestamptzHandlingMode::UtcWithoutSuffix,
             custom_json_type: CustomJsonType::Doris(map),
             kafka_connect: None,
+            extra_field: None,
         }
     }
 
@@ -108,9 +119,11 @@ impl JsonEncoder {
             time_handling_mode: TimeHandlingMode::Milli,
             date_handling_mode: DateHandlingMode::String,
             timestamp_handling_mode: TimestampHandlingMode::String,
+            timestamp_handling_mode_overrides: HashMap::new(),
             timestamptz_handling_mode: TimestamptzHandlingMode::UtcWithoutSuffix,
             custom_json_type: CustomJsonType::StarRocks(map),
             kafka_connect: None,
+            extra_field: None,
         }
     }
 
@@ -121,9 +134,11 @@ impl JsonEncoder {
             time_handling_mode: TimeHandlingMode::Milli,
             date_handling_mode: DateHandlingMode::String,
             timestamp_handling_mode: TimestampHandlingMode::String,
+            timestamp_handling_mode_overrides: HashMap::new(),
             timestamptz_handling_mode: TimestamptzHandlingMode::UtcString,
             custom_json_type: CustomJsonType::BigQuery,
             kafka_connect: None,
+            extra_field: None,
         }
     }
 
@@ -133,6 +148,27 @@ impl JsonEncoder {
             ..self
         }
     }
+
+    /// Adds a `key: value` pair to every object this encoder produces, alongside the row's own
+    /// columns. Used e.g. to stamp each record with the sink it came from.
+    pub fn with_extra_field(self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        Self {
+            extra_field: Some((key.into(), value.into())),
+            ..self
+        }
+    }
+
+    /// Overrides [`Self`]'s global `timestamp_handling_mode` for specific columns, keyed by
+    /// column name. See [`TimestampHandlingMode::column_overrides_from_options`].
+    pub fn with_timestamp_handling_mode_overrides(
+        self,
+        timestamp_handling_mode_overrides: HashMap<String, TimestampHandlingMode>,
+    ) -> Self {
+        Self {
+            timestamp_handling_mode_overrides,
+            ..self
+        }
+    }
 }
 
 impl RowEncoder for JsonEncoder {
@@ -156,11 +192,16 @@ impl RowEncoder for JsonEncoder {
         for idx in &col_indices {
             let field = &self.schema[*idx];
             let key = field.name.clone();
+            let timestamp_handling_mode = self
+                .timestamp_handling_mode_overrides
+                .get(&field.name)
+                .copied()
+                .unwrap_or(self.timestamp_handling_mode);
             let value = datum_to_json_object(
                 field,
                 row.datum_at(*idx),
                 self.date_handling_mode,
-                self.timestamp_handling_mode,
+                timestamp_handling_mode,
                 self.timestamptz_handling_mode,
                 self.time_handling_mode,
                 &self.custom_json_type,
@@ -169,6 +210,10 @@ impl RowEncoder for JsonEncoder {
             mappings.insert(key, value);
         }
 
+        if let Some((key, value)) = &self.extra_field {
+            mappings.insert(key.clone(), value.clone());
+        }
+
         Ok(if let Some(param) = &self.kafka_connect {
             json_converter_with_schema(
                 Value::Object(mappings),
@@ -300,6 +345,7 @@ fn datum_to_json_object(
         },
         (DataType::Timestamp, ScalarRefImpl::Timestamp(v)) => match timestamp_handling_mode {
             TimestampHandlingMode::Milli => json!(v.0.timestamp_millis()),
+            TimestampHandlingMode::Micro => json!(v.0.timestamp_micros()),
             TimestampHandlingMode::String => json!(v.0.format("%Y-%m-%d %H:%M:%S%.6f").to_string()),
         },
         (DataType::Bytea, ScalarRefImpl::Bytea(v)) => {
@@ -470,8 +516,8 @@ fn type_as_json_schema(rw_type: &DataType) -> Map<String, Value> {
 mod tests {
 
     use risingwave_common::types::{
-        DataType, Date, Interval, Scalar, ScalarImpl, StructRef, StructType, StructValue, Time,
-        Timestamp,
+        DataType, Date, Interval, ListValue, Scalar, ScalarImpl, StructRef, StructType,
+        StructValue, Time, Timestamp,
     };
 
     use super::*;
@@ -601,6 +647,24 @@ mod tests {
         .unwrap();
         assert_eq!(ts_value, json!("1970-01-01 00:16:40.000000".to_string()));
 
+        let ts_value = datum_to_json_object(
+            &Field {
+                data_type: DataType::Timestamp,
+                ..mock_field.clone()
+            },
+            Some(
+                ScalarImpl::Timestamp(Timestamp::from_timestamp_uncheck(1000, 0))
+                    .as_scalar_ref_impl(),
+            ),
+            DateHandlingMode::FromCe,
+            TimestampHandlingMode::Micro,
+            TimestamptzHandlingMode::UtcString,
+            TimeHandlingMode::Milli,
+            &CustomJsonType::None,
+        )
+        .unwrap();
+        assert_eq!(ts_value, json!(1000 * 1_000_000i64));
+
         // Represents the number of milliseconds past midnigh, org.apache.kafka.connect.data.Time
         let time_value = datum_to_json_object(
             &Field {
@@ -727,6 +791,32 @@ mod tests {
         assert_eq!(interval_value, json!("{\"v3\":3,\"v2\":2,\"v1\":1}"));
     }
 
+    #[test]
+    fn test_list_encodes_as_json_array_with_null_elements() {
+        let mock_field = Field {
+            data_type: DataType::Boolean,
+            name: Default::default(),
+            sub_fields: Default::default(),
+            type_name: Default::default(),
+        };
+        let list_value = ListValue::from_iter([Some(1), None, Some(3)]);
+
+        let list_json = datum_to_json_object(
+            &Field {
+                data_type: DataType::List(Box::new(DataType::Int32)),
+                ..mock_field
+            },
+            Some(ScalarImpl::List(list_value).as_scalar_ref_impl()),
+            DateHandlingMode::FromCe,
+            TimestampHandlingMode::Milli,
+            TimestamptzHandlingMode::UtcString,
+            TimeHandlingMode::Milli,
+            &CustomJsonType::None,
+        )
+        .unwrap();
+        assert_eq!(list_json, json!([1, null, 3]));
+    }
+
     #[test]
     fn test_generate_json_converter_schema() {
         let mock_field = Field {
@@ -857,4 +947,59 @@ mod tests {
         let ans = r#"{"fields":[{"field":"v1","optional":true,"type":"boolean"},{"field":"v2","optional":true,"type":"int16"},{"field":"v3","optional":true,"type":"int32"},{"field":"v4","optional":true,"type":"float"},{"field":"v5","optional":true,"type":"string"},{"field":"v6","optional":true,"type":"int32"},{"field":"v7","optional":true,"type":"string"},{"field":"v8","optional":true,"type":"int64"},{"field":"v9","optional":true,"type":"string"},{"field":"v10","fields":[{"field":"a","optional":true,"type":"int64"},{"field":"b","optional":true,"type":"string"},{"field":"c","fields":[{"field":"aa","optional":true,"type":"int64"},{"field":"bb","optional":true,"type":"double"}],"optional":true,"type":"struct"}],"optional":true,"type":"struct"},{"field":"v11","items":{"items":{"fields":[{"field":"aa","optional":true,"type":"int64"},{"field":"bb","optional":true,"type":"double"}],"optional":true,"type":"struct"},"optional":true,"type":"array"},"optional":true,"type":"array"},{"field":"12","optional":true,"type":"string"},{"field":"13","optional":true,"type":"int32"},{"field":"14","optional":true,"type":"string"}],"name":"test","optional":false,"type":"struct"}"#;
         assert_eq!(schema, ans);
     }
+
+    #[test]
+    fn test_timestamp_handling_mode_overrides_take_precedence_per_column() {
+        use risingwave_common::row::OwnedRow;
+
+        let schema = Schema::new(vec![
+            Field::with_name(DataType::Timestamp, "ts_a"),
+            Field::with_name(DataType::Timestamp, "ts_b"),
+        ]);
+        let mut overrides = HashMap::new();
+        overrides.insert("ts_a".to_owned(), TimestampHandlingMode::String);
+        let encoder = JsonEncoder::new(
+            schema,
+            None,
+            DateHandlingMode::FromCe,
+            TimestampHandlingMode::Milli,
+            TimestamptzHandlingMode::UtcString,
+            TimeHandlingMode::Milli,
+        )
+        .with_timestamp_handling_mode_overrides(overrides);
+
+        let ts = Timestamp::from_timestamp_uncheck(1000, 0);
+        let row = OwnedRow::new(vec![
+            Some(ScalarImpl::Timestamp(ts)),
+            Some(ScalarImpl::Timestamp(ts)),
+        ]);
+        let encoded = encoder.encode(row).unwrap();
+
+        // `ts_a` has a per-column override to `string`, so it ignores the encoder-wide `milli`
+        // mode that `ts_b` still uses.
+        assert_eq!(encoded["ts_a"], json!("1970-01-01 00:16:40.000000"));
+        assert_eq!(encoded["ts_b"], json!(1000 * 1000));
+    }
+
+    #[test]
+    fn test_with_extra_field_adds_configured_key() {
+        use risingwave_common::row::OwnedRow;
+
+        let schema = Schema::new(vec![Field::with_name(DataType::Int32, "v1")]);
+        let encoder = JsonEncoder::new(
+            schema,
+            None,
+            DateHandlingMode::FromCe,
+            TimestampHandlingMode::Milli,
+            TimestamptzHandlingMode::UtcString,
+            TimeHandlingMode::Milli,
+        )
+        .with_extra_field("__source", "my_mv");
+
+        let row = OwnedRow::new(vec![Some(ScalarImpl::Int32(1))]);
+        let encoded = encoder.encode(row).unwrap();
+
+        assert_eq!(encoded["v1"], json!(1));
+        assert_eq!(encoded["__source"], json!("my_mv"));
+    }
 }
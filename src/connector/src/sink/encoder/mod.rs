@@ -18,14 +18,16 @@ use std::sync::Arc;
 use risingwave_common::catalog::Schema;
 use risingwave_common::row::Row;
 
-use crate::sink::Result;
+use crate::sink::{Result, SinkError};
 
 mod avro;
+mod csv;
 mod json;
 mod proto;
 pub mod template;
 
 pub use avro::{AvroEncoder, AvroHeader};
+pub use csv::{CsvEncoder, CsvQuoteStyle};
 pub use json::JsonEncoder;
 pub use proto::{ProtoEncoder, ProtoHeader};
 
@@ -45,7 +47,16 @@ pub trait RowEncoder {
     fn col_indices(&self) -> Option<&[usize]>;
 
     fn encode(&self, row: impl Row) -> Result<Self::Output> {
-        assert_eq!(row.len(), self.schema().len());
+        if row.len() != self.schema().len() {
+            // A mismatch here means a `StreamChunk` with the wrong column count reached the
+            // encoder, e.g. via an upstream bug. Surface it as a descriptive sink error instead
+            // of panicking deep inside encoding, where the cause would be much less obvious.
+            return Err(SinkError::Encode(format!(
+                "row has {} columns, but sink schema expects {}",
+                row.len(),
+                self.schema().len()
+            )));
+        }
         match self.col_indices() {
             Some(col_indices) => self.encode_cols(row, col_indices.iter().copied()),
             None => self.encode_cols(row, 0..self.schema().len()),
@@ -94,9 +105,64 @@ pub enum DateHandlingMode {
 #[derive(Clone, Copy)]
 pub enum TimestampHandlingMode {
     Milli,
+    Micro,
     String,
 }
 
+impl TimestampHandlingMode {
+    pub const OPTION_KEY: &'static str = "timestamp.handling.mode";
+
+    pub fn from_options(options: &BTreeMap<String, String>) -> Result<Self> {
+        match options.get(Self::OPTION_KEY).map(std::ops::Deref::deref) {
+            Some("milli") | None => Ok(Self::Milli),
+            Some("micro") => Ok(Self::Micro),
+            Some("string") => Ok(Self::String),
+            Some(v) => Err(SinkError::Config(anyhow::anyhow!(
+                "unrecognized {} value {}",
+                Self::OPTION_KEY,
+                v
+            ))),
+        }
+    }
+
+    fn from_option_value(option_key: &str, v: &str) -> Result<Self> {
+        match v {
+            "milli" => Ok(Self::Milli),
+            "micro" => Ok(Self::Micro),
+            "string" => Ok(Self::String),
+            v => Err(SinkError::Config(anyhow::anyhow!(
+                "unrecognized {} value {}",
+                option_key,
+                v
+            ))),
+        }
+    }
+
+    /// Parses per-column overrides of the form `json.column.<column name>.timestamp = <mode>`,
+    /// which take precedence over the sink-wide [`Self::from_options`] mode for that column. This
+    /// lets a sink with multiple timestamp columns give each one a different representation, e.g.
+    /// `json.column.ts_a.timestamp = string` alongside a global `milli` default for every other
+    /// timestamp column.
+    pub fn column_overrides_from_options(
+        options: &BTreeMap<String, String>,
+    ) -> Result<HashMap<String, Self>> {
+        const PREFIX: &str = "json.column.";
+        const SUFFIX: &str = ".timestamp";
+
+        options
+            .iter()
+            .filter_map(|(k, v)| {
+                k.strip_prefix(PREFIX)
+                    .and_then(|rest| rest.strip_suffix(SUFFIX))
+                    .map(|column_name| (column_name, k, v))
+            })
+            .map(|(column_name, option_key, v)| {
+                Ok((column_name.to_owned(), Self::from_option_value(option_key, v)?))
+            })
+            .collect()
+    }
+}
+
 #[derive(Clone, Copy)]
 pub enum TimeHandlingMode {
     Milli,
@@ -194,3 +260,73 @@ pub struct KafkaConnectParams {
 }
 
 type KafkaConnectParamsRef = Arc<KafkaConnectParams>;
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::Field;
+    use risingwave_common::row::OwnedRow;
+    use risingwave_common::types::{DataType, ScalarImpl};
+
+    use super::*;
+
+    #[test]
+    fn test_encode_rejects_mismatched_column_count() {
+        let schema = risingwave_common::catalog::Schema::new(vec![
+            Field::with_name(DataType::Int32, "v1"),
+            Field::with_name(DataType::Int32, "v2"),
+        ]);
+        let encoder = JsonEncoder::new(
+            schema,
+            None,
+            DateHandlingMode::FromCe,
+            TimestampHandlingMode::Milli,
+            TimestamptzHandlingMode::UtcString,
+            TimeHandlingMode::Milli,
+        );
+
+        // Only one column, but the schema expects two.
+        let row = OwnedRow::new(vec![Some(ScalarImpl::Int32(1))]);
+        let err = encoder.encode(row).unwrap_err();
+        assert!(matches!(err, SinkError::Encode(_)));
+        assert!(err.to_string().contains("1 columns"));
+        assert!(err.to_string().contains("expects 2"));
+    }
+
+    #[test]
+    fn test_timestamp_handling_mode_from_options() {
+        let mode = |v: &str| {
+            let mut options = BTreeMap::new();
+            options.insert(TimestampHandlingMode::OPTION_KEY.to_owned(), v.to_owned());
+            TimestampHandlingMode::from_options(&options)
+        };
+
+        assert!(matches!(mode("milli"), Ok(TimestampHandlingMode::Milli)));
+        assert!(matches!(mode("micro"), Ok(TimestampHandlingMode::Micro)));
+        assert!(matches!(mode("string"), Ok(TimestampHandlingMode::String)));
+        assert!(mode("nanos").is_err());
+
+        // Unset defaults to `Milli`, preserving the historical behavior.
+        assert!(matches!(
+            TimestampHandlingMode::from_options(&BTreeMap::new()),
+            Ok(TimestampHandlingMode::Milli)
+        ));
+    }
+
+    #[test]
+    fn test_timestamp_handling_mode_column_overrides_from_options() {
+        let mut options = BTreeMap::new();
+        options.insert(TimestampHandlingMode::OPTION_KEY.to_owned(), "milli".to_owned());
+        options.insert("json.column.ts_a.timestamp".to_owned(), "string".to_owned());
+        options.insert("json.column.ts_b.timestamp".to_owned(), "micro".to_owned());
+        options.insert("not_a_column_override".to_owned(), "string".to_owned());
+
+        let overrides = TimestampHandlingMode::column_overrides_from_options(&options).unwrap();
+        assert_eq!(overrides.len(), 2);
+        assert!(matches!(overrides["ts_a"], TimestampHandlingMode::String));
+        assert!(matches!(overrides["ts_b"], TimestampHandlingMode::Micro));
+
+        let mut bad_options = BTreeMap::new();
+        bad_options.insert("json.column.ts_a.timestamp".to_owned(), "nanos".to_owned());
+        assert!(TimestampHandlingMode::column_overrides_from_options(&bad_options).is_err());
+    }
+}
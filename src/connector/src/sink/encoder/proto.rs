@@ -461,6 +461,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_encode_proto_confluent_schema_registry_header() {
+        let pool_path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("src/test_data/proto_recursive/recursive.pb");
+        let pool_bytes = std::fs::read(pool_path).unwrap();
+        let pool = prost_reflect::DescriptorPool::decode(pool_bytes.as_ref()).unwrap();
+        let descriptor = pool.get_message_by_name("recursive.AllTypes").unwrap();
+
+        let schema = Schema::new(vec![Field::with_name(DataType::Boolean, "bool_field")]);
+        let row = OwnedRow::new(vec![Some(ScalarImpl::Bool(true))]);
+
+        let encoder =
+            ProtoEncoder::new(schema, None, descriptor, ProtoHeader::ConfluentSchemaRegistry(42))
+                .unwrap();
+        let encoded: Vec<u8> = encoder.encode(row).unwrap().ser_to().unwrap();
+
+        // A Confluent wire-format message is `[magic byte][4-byte big-endian schema id][message
+        // index(es)][proto-encoded message]`. `recursive.AllTypes` is the second top-level message
+        // in its file (index 1), which `MessageIndexes` zigzag-varint-encodes as `[len=1, idx=1]`,
+        // i.e. the two bytes `2, 2`.
+        let (header, body) = encoded.split_at(7);
+        assert_eq!(header, [0, 0, 0, 0, 42, 2, 2]);
+        assert_eq!(body, [104, 1]); // `bool_field` (proto field number 13) = `true`
+    }
+
     #[test]
     fn test_encode_proto_repeated() {
         let pool_path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
@@ -13,9 +13,12 @@
 // limitations under the License.
 
 use risingwave_common::array::Op;
+use risingwave_common::row::Row;
+use risingwave_common::types::ScalarRefImpl;
+use serde_json::{Map, Value};
 
 use super::{Result, SinkFormatter, StreamChunk};
-use crate::sink::encoder::RowEncoder;
+use crate::sink::encoder::{JsonEncoder, RowEncoder};
 use crate::tri;
 
 pub struct AppendOnlyFormatter<KE, VE> {
@@ -32,6 +35,214 @@ impl<KE, VE> AppendOnlyFormatter<KE, VE> {
     }
 }
 
+impl<KE: RowEncoder> AppendOnlyFormatter<KE, JsonEncoder> {
+    /// Wraps this formatter so it can be used for an append-only sink whose upstream is CDC.
+    /// Instead of silently dropping `Op::UpdateInsert`/`Op::Delete` rows the way
+    /// [`SinkFormatter::format_chunk`] does above, the returned formatter forwards them as well
+    /// and stamps each JSON value with an `"op"` column (`"c"`/`"u"`/`"d"`, matching
+    /// [`DebeziumJsonFormatter`](super::DebeziumJsonFormatter)'s convention) so the downstream
+    /// consumer can still tell inserts from updates and deletes.
+    pub fn with_op_column(self) -> AppendOnlyWithOpColumnFormatter<KE> {
+        AppendOnlyWithOpColumnFormatter { inner: self }
+    }
+}
+
+impl<KE, VE> AppendOnlyFormatter<KE, VE> {
+    /// Wraps this formatter so each record is additionally tagged with a partition token derived
+    /// from bucketing `timestamp_col_idx` at `granularity`, e.g. `dt=2024-01-01` for
+    /// [`PartitionGranularity::Day`]. Intended for file/object-storage sinks that lay out their
+    /// output as `dt=2024-01-01/...` directories.
+    pub fn with_partitioning(
+        self,
+        timestamp_col_idx: usize,
+        granularity: PartitionGranularity,
+    ) -> PartitionedAppendOnlyFormatter<KE, VE> {
+        PartitionedAppendOnlyFormatter {
+            inner: self,
+            timestamp_col_idx,
+            granularity,
+        }
+    }
+}
+
+/// Time-bucket granularity for [`PartitionedAppendOnlyFormatter`]'s partition token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartitionGranularity {
+    Hour,
+    Day,
+}
+
+impl PartitionGranularity {
+    pub fn from_option_str(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "hour" => Some(Self::Hour),
+            "day" => Some(Self::Day),
+            _ => None,
+        }
+    }
+}
+
+/// An [`AppendOnlyFormatter`] wrapper that additionally computes a partition path token per
+/// record, for file sinks writing partitioned object storage layouts (e.g. `dt=2024-01-01/`).
+/// This is additive metadata alongside the `(K, V)` pair `SinkFormatter` produces; the partition
+/// token itself is not part of the encoded key or value.
+pub struct PartitionedAppendOnlyFormatter<KE, VE> {
+    inner: AppendOnlyFormatter<KE, VE>,
+    timestamp_col_idx: usize,
+    granularity: PartitionGranularity,
+}
+
+impl<KE: RowEncoder, VE: RowEncoder> PartitionedAppendOnlyFormatter<KE, VE> {
+    /// Like [`SinkFormatter::format_chunk`], but yields a partition token alongside each `(K, V)`
+    /// pair. The token is `None` for rows whose timestamp column is `NULL`.
+    pub fn format_chunk_with_partition(
+        &self,
+        chunk: &StreamChunk,
+    ) -> impl Iterator<Item = Result<(Option<KE::Output>, Option<VE::Output>, Option<String>)>> + '_
+    {
+        let timestamp_col_idx = self.timestamp_col_idx;
+        let granularity = self.granularity;
+        std::iter::from_coroutine(move || {
+            for (op, row) in chunk.rows() {
+                if op != Op::Insert {
+                    continue;
+                }
+                let event_key_object = match &self.inner.key_encoder {
+                    Some(key_encoder) => Some(tri!(key_encoder.encode(row))),
+                    None => None,
+                };
+                let event_object = Some(tri!(self.inner.val_encoder.encode(row)));
+                let partition_token = partition_token(row.datum_at(timestamp_col_idx), granularity);
+
+                yield Ok((event_key_object, event_object, partition_token))
+            }
+        })
+    }
+}
+
+/// Computes the partition token for a single timestamp-like datum, e.g. `dt=2024-01-01` for
+/// [`PartitionGranularity::Day`] or `dt=2024-01-01/hour=07` for [`PartitionGranularity::Hour`].
+/// Returns `None` for a `NULL` datum or a non-timestamp-like type.
+fn partition_token(
+    datum: Option<ScalarRefImpl<'_>>,
+    granularity: PartitionGranularity,
+) -> Option<String> {
+    let naive = match datum? {
+        ScalarRefImpl::Timestamp(t) => t.0,
+        ScalarRefImpl::Timestamptz(t) => t.to_datetime_utc().naive_utc(),
+        _ => return None,
+    };
+    Some(match granularity {
+        PartitionGranularity::Day => naive.format("dt=%Y-%m-%d").to_string(),
+        PartitionGranularity::Hour => naive.format("dt=%Y-%m-%d/hour=%H").to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::StreamChunkTestExt;
+    use risingwave_common::catalog::{Field, Schema};
+    use risingwave_common::types::DataType;
+
+    use super::*;
+    use crate::sink::encoder::{
+        DateHandlingMode, JsonEncoder, TimeHandlingMode, TimestampHandlingMode,
+        TimestamptzHandlingMode,
+    };
+
+    fn test_schema() -> Schema {
+        Schema::new(vec![
+            Field::with_name(DataType::Int32, "v1"),
+            Field::with_name(DataType::Timestamp, "ts"),
+        ])
+    }
+
+    fn json_encoder(schema: Schema) -> JsonEncoder {
+        JsonEncoder::new(
+            schema,
+            None,
+            DateHandlingMode::FromCe,
+            TimestampHandlingMode::Milli,
+            TimestamptzHandlingMode::UtcString,
+            TimeHandlingMode::Milli,
+        )
+    }
+
+    #[test]
+    fn test_different_days_produce_different_partition_tokens() {
+        let schema = test_schema();
+        let formatter = AppendOnlyFormatter::new(None, json_encoder(schema))
+            .with_partitioning(1, PartitionGranularity::Day);
+
+        let chunk = StreamChunk::from_pretty(
+            "  i TS
+             + 1 2024-01-01T08:00:00
+             + 2 2024-01-02T23:00:00",
+        );
+
+        let results = formatter
+            .format_chunk_with_partition(&chunk)
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].2.as_deref(), Some("dt=2024-01-01"));
+        assert_eq!(results[1].2.as_deref(), Some("dt=2024-01-02"));
+        assert_ne!(results[0].2, results[1].2);
+    }
+
+    #[test]
+    fn test_hour_granularity_partition_token() {
+        let schema = test_schema();
+        let formatter = AppendOnlyFormatter::new(None, json_encoder(schema))
+            .with_partitioning(1, PartitionGranularity::Hour);
+
+        let chunk = StreamChunk::from_pretty(
+            "  i TS
+             + 1 2024-01-01T08:30:00",
+        );
+
+        let results = formatter
+            .format_chunk_with_partition(&chunk)
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(results[0].2.as_deref(), Some("dt=2024-01-01/hour=08"));
+    }
+}
+
+pub struct AppendOnlyWithOpColumnFormatter<KE> {
+    inner: AppendOnlyFormatter<KE, JsonEncoder>,
+}
+
+impl<KE: RowEncoder> SinkFormatter for AppendOnlyWithOpColumnFormatter<KE> {
+    type K = KE::Output;
+    type V = Map<String, Value>;
+
+    fn format_chunk(
+        &self,
+        chunk: &StreamChunk,
+    ) -> impl Iterator<Item = Result<(Option<Self::K>, Option<Self::V>)>> {
+        std::iter::from_coroutine(|| {
+            for (op, row) in chunk.rows() {
+                let op_column = match op {
+                    Op::Insert => "c",
+                    Op::UpdateInsert => "u",
+                    Op::Delete => "d",
+                    // The "before" value is superseded by the `UpdateInsert` that follows it.
+                    Op::UpdateDelete => continue,
+                };
+                let event_key_object = match &self.inner.key_encoder {
+                    Some(key_encoder) => Some(tri!(key_encoder.encode(row))),
+                    None => None,
+                };
+                let mut event_object = tri!(self.inner.val_encoder.encode(row));
+                event_object.insert("op".to_owned(), Value::String(op_column.to_owned()));
+
+                yield Ok((event_key_object, Some(event_object)))
+            }
+        })
+    }
+}
+
 impl<KE: RowEncoder, VE: RowEncoder> SinkFormatter for AppendOnlyFormatter<KE, VE> {
     type K = KE::Output;
     type V = VE::Output;
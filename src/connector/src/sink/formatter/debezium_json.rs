@@ -29,7 +29,7 @@ use crate::tri;
 const DEBEZIUM_NAME_FIELD_PREFIX: &str = "RisingWave";
 
 pub struct DebeziumAdapterOpts {
-    gen_tombstone: bool,
+    pub(crate) gen_tombstone: bool,
 }
 
 impl Default for DebeziumAdapterOpts {
@@ -115,6 +115,7 @@ impl SinkFormatter for DebeziumJsonFormatter {
             let source_field = json!({
                 // todo: still some missing fields in source field
                 // ref https://debezium.io/documentation/reference/2.4/connectors/postgresql.html#postgresql-create-events
+                "connector": "risingwave",
                 "db": db_name,
                 "table": sink_from_name,
                 "ts_ms": ts_ms,
@@ -217,6 +218,11 @@ pub(crate) fn schema_to_json(schema: &Schema, db_name: &str, sink_from_name: &st
         "optional": false,
         "name": concat_debezium_name_field(db_name, sink_from_name, "Source"),
         "fields": vec![
+            json!({
+                "type": "string",
+                "optional": false,
+                "field": "connector"
+            }),
             json!({
                 "type": "string",
                 "optional": false,
@@ -335,7 +341,7 @@ mod tests {
     use super::*;
     use crate::sink::utils::chunk_to_json;
 
-    const SCHEMA_JSON_RESULT: &str = r#"{"fields":[{"field":"before","fields":[{"field":"v1","optional":true,"type":"int32"},{"field":"v2","optional":true,"type":"float"},{"field":"v3","optional":true,"type":"string"}],"name":"RisingWave.test_db.test_table.Key","optional":true,"type":"struct"},{"field":"after","fields":[{"field":"v1","optional":true,"type":"int32"},{"field":"v2","optional":true,"type":"float"},{"field":"v3","optional":true,"type":"string"}],"name":"RisingWave.test_db.test_table.Key","optional":true,"type":"struct"},{"field":"source","fields":[{"field":"db","optional":false,"type":"string"},{"field":"table","optional":true,"type":"string"},{"field":"ts_ms","optional":false,"type":"int64"}],"name":"RisingWave.test_db.test_table.Source","optional":false,"type":"struct"},{"field":"op","optional":false,"type":"string"},{"field":"ts_ms","optional":false,"type":"int64"}],"name":"RisingWave.test_db.test_table.Envelope","optional":false,"type":"struct"}"#;
+    const SCHEMA_JSON_RESULT: &str = r#"{"fields":[{"field":"before","fields":[{"field":"v1","optional":true,"type":"int32"},{"field":"v2","optional":true,"type":"float"},{"field":"v3","optional":true,"type":"string"}],"name":"RisingWave.test_db.test_table.Key","optional":true,"type":"struct"},{"field":"after","fields":[{"field":"v1","optional":true,"type":"int32"},{"field":"v2","optional":true,"type":"float"},{"field":"v3","optional":true,"type":"string"}],"name":"RisingWave.test_db.test_table.Key","optional":true,"type":"struct"},{"field":"source","fields":[{"field":"connector","optional":false,"type":"string"},{"field":"db","optional":false,"type":"string"},{"field":"table","optional":true,"type":"string"},{"field":"ts_ms","optional":false,"type":"int64"}],"name":"RisingWave.test_db.test_table.Source","optional":false,"type":"struct"},{"field":"op","optional":false,"type":"string"},{"field":"ts_ms","optional":false,"type":"int64"}],"name":"RisingWave.test_db.test_table.Envelope","optional":false,"type":"struct"}"#;
 
     #[test]
     fn test_chunk_to_json() -> Result<()> {
@@ -412,4 +418,40 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_format_chunk_delete_sets_after_null_and_op_d() {
+        let schema = Schema::new(vec![Field {
+            data_type: DataType::Int32,
+            name: "v1".into(),
+            sub_fields: vec![],
+            type_name: "".into(),
+        }]);
+        let formatter = DebeziumJsonFormatter::new(
+            schema,
+            vec![0],
+            "test_db".to_owned(),
+            "test_table".to_owned(),
+            DebeziumAdapterOpts {
+                gen_tombstone: false,
+            },
+        );
+
+        let chunk = StreamChunk::from_pretty(
+            "  i
+             - 1",
+        );
+
+        let results = formatter
+            .format_chunk(&chunk)
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        let payload = &results[0].1.as_ref().unwrap()["payload"];
+        assert_eq!(payload["after"], Value::Null);
+        assert_eq!(payload["op"], "d");
+        assert_eq!(payload["source"]["connector"], "risingwave");
+        assert_eq!(payload["source"]["db"], "test_db");
+        assert_eq!(payload["source"]["table"], "test_table");
+    }
 }
@@ -12,16 +12,26 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
 use anyhow::anyhow;
+use itertools::Itertools;
 use risingwave_common::array::StreamChunk;
 
+use crate::common::AwsAuthProps;
+use crate::sink::encoder::SerTo;
 use crate::sink::{Result, SinkError};
 
 mod append_only;
 mod debezium_json;
 mod upsert;
 
-pub use append_only::AppendOnlyFormatter;
+pub use append_only::{
+    AppendOnlyFormatter, AppendOnlyWithOpColumnFormatter, PartitionGranularity,
+    PartitionedAppendOnlyFormatter,
+};
 pub use debezium_json::{DebeziumAdapterOpts, DebeziumJsonFormatter};
 use risingwave_common::catalog::Schema;
 pub use upsert::UpsertFormatter;
@@ -33,7 +43,8 @@ use super::encoder::{
 };
 use super::redis::{KEY_FORMAT, VALUE_FORMAT};
 use crate::sink::encoder::{
-    AvroEncoder, AvroHeader, JsonEncoder, ProtoEncoder, ProtoHeader, TimestampHandlingMode,
+    AvroEncoder, AvroHeader, CsvEncoder, CsvQuoteStyle, JsonEncoder, ProtoEncoder, ProtoHeader,
+    TimestampHandlingMode,
 };
 
 /// Transforms a `StreamChunk` into a sequence of key-value pairs according a specific format,
@@ -52,6 +63,98 @@ pub trait SinkFormatter {
     ) -> impl Iterator<Item = Result<(Option<Self::K>, Option<Self::V>)>>;
 }
 
+/// Whether a [`SinkFormatterImpl`] variant preserves per-key ordering of the records it emits,
+/// so the sink writer can configure its producer accordingly (e.g. a Kafka producer needs
+/// `max.in.flight.requests.per.connection=1` to preserve ordering for [`OrderingGuarantee::PerKey`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderingGuarantee {
+    /// Records may be delivered out of order, e.g. append-only with no key set, which lets the
+    /// producer partition records round-robin.
+    None,
+    /// Records sharing the same key are always delivered in the order they were produced, e.g.
+    /// upsert and Debezium formats, where out-of-order delivery of updates to the same key would
+    /// corrupt the downstream view of that row.
+    PerKey,
+}
+
+/// A single sampled measurement of [`SamplingFormatter::format_chunk`], handed to the
+/// `on_sample` callback so the caller can record it however it likes (e.g. into a Prometheus
+/// histogram/counter pair).
+#[derive(Debug, Clone, Copy)]
+pub struct FormatSample {
+    /// Wall-clock time spent formatting (and, for the purpose of this measurement,
+    /// serializing) the sampled chunk.
+    pub duration: Duration,
+    /// Total serialized size, in bytes, of all key/value pairs produced for the sampled chunk.
+    pub serialized_bytes: usize,
+}
+
+/// A [`SinkFormatter`] wrapper that, every `sample_interval` chunks, measures the time spent
+/// formatting the chunk and the total serialized size of its output, and reports the
+/// measurement via `on_sample`. All other chunks go through `inner` directly, without collecting
+/// or serializing its output; the only added cost is boxing the returned iterator.
+pub struct SamplingFormatter<F> {
+    inner: F,
+    sample_interval: usize,
+    sampled_chunks: AtomicUsize,
+    on_sample: Arc<dyn Fn(FormatSample) + Send + Sync>,
+}
+
+impl<F> SamplingFormatter<F> {
+    /// `sample_interval` of 0 disables sampling entirely (equivalent to using `inner` directly).
+    pub fn new(
+        inner: F,
+        sample_interval: usize,
+        on_sample: Arc<dyn Fn(FormatSample) + Send + Sync>,
+    ) -> Self {
+        Self {
+            inner,
+            sample_interval,
+            sampled_chunks: AtomicUsize::new(0),
+            on_sample,
+        }
+    }
+}
+
+impl<F: SinkFormatter> SinkFormatter for SamplingFormatter<F>
+where
+    F::V: Clone + SerTo<Vec<u8>>,
+{
+    type K = F::K;
+    type V = F::V;
+
+    fn format_chunk(
+        &self,
+        chunk: &StreamChunk,
+    ) -> impl Iterator<Item = Result<(Option<Self::K>, Option<Self::V>)>> {
+        let should_sample = self.sample_interval != 0
+            && self.sampled_chunks.fetch_add(1, Ordering::Relaxed) % self.sample_interval == 0;
+
+        if !should_sample {
+            return Box::new(self.inner.format_chunk(chunk))
+                as Box<dyn Iterator<Item = Result<(Option<Self::K>, Option<Self::V>)>>>;
+        }
+
+        let start = Instant::now();
+        let results = self.inner.format_chunk(chunk).collect_vec();
+        let mut serialized_bytes = 0;
+        for result in &results {
+            if let Ok((_, Some(v))) = result {
+                // Only used to measure size; the formatted value itself is returned unchanged.
+                if let Ok(bytes) = v.clone().ser_to() {
+                    serialized_bytes += bytes.len();
+                }
+            }
+        }
+        (self.on_sample)(FormatSample {
+            duration: start.elapsed(),
+            serialized_bytes,
+        });
+
+        Box::new(results.into_iter())
+    }
+}
+
 /// `tri!` in generators yield `Err` and return `()`
 /// `?` in generators return `Err`
 #[macro_export]
@@ -69,14 +172,122 @@ macro_rules! tri {
 
 pub enum SinkFormatterImpl {
     AppendOnlyJson(AppendOnlyFormatter<JsonEncoder, JsonEncoder>),
+    AppendOnlyJsonWithOpColumn(AppendOnlyWithOpColumnFormatter<JsonEncoder>),
     AppendOnlyProto(AppendOnlyFormatter<JsonEncoder, ProtoEncoder>),
+    AppendOnlyCsv(AppendOnlyFormatter<JsonEncoder, CsvEncoder>),
     UpsertJson(UpsertFormatter<JsonEncoder, JsonEncoder>),
     UpsertAvro(UpsertFormatter<AvroEncoder, AvroEncoder>),
+    UpsertAvroKeyJsonValue(UpsertFormatter<AvroEncoder, JsonEncoder>),
+    UpsertProto(UpsertFormatter<JsonEncoder, ProtoEncoder>),
     DebeziumJson(DebeziumJsonFormatter),
     AppendOnlyTemplate(AppendOnlyFormatter<TemplateEncoder, TemplateEncoder>),
     UpsertTemplate(UpsertFormatter<TemplateEncoder, TemplateEncoder>),
 }
 
+/// Option key letting `upsert` sinks use a different encode for the key than for the value (e.g.
+/// an Avro key from a schema-registry-managed source paired with a JSON value). Unset (the
+/// default) uses `format_desc.encode` for both, matching the historical behavior. See
+/// [`SinkFormatterImpl::UpsertAvroKeyJsonValue`].
+const KEY_ENCODE: &str = "key.encode";
+
+/// Option key controlling how many extra attempts a resilience-sensitive startup fetch (e.g. the
+/// protobuf descriptor lookup in [`SinkFormatterImpl::new`]) makes before giving up. Defaults to
+/// `0`, i.e. fail-fast on the first error, matching the historical behavior.
+const FETCH_RETRY_MAX_RETRIES: &str = "fetch.retry.max_retries";
+const FETCH_RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
+fn fetch_retry_max_retries(format_desc: &SinkFormatDesc) -> usize {
+    format_desc
+        .options
+        .get(FETCH_RETRY_MAX_RETRIES)
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Option key controlling whether deletes are dropped entirely instead of being emitted as a
+/// Kafka log-compaction tombstone (a null-value message with the row's key). Defaults to `false`,
+/// i.e. tombstones are emitted, matching the historical behavior. Applies to [`SinkFormat::Upsert`]
+/// (via [`UpsertFormatter::with_suppress_tombstones`]) and [`SinkFormat::Debezium`] (via
+/// [`DebeziumAdapterOpts::gen_tombstone`]).
+const SUPPRESS_TOMBSTONES: &str = "suppress_tombstones";
+
+fn suppress_tombstones(format_desc: &SinkFormatDesc) -> bool {
+    format_desc
+        .options
+        .get(SUPPRESS_TOMBSTONES)
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(false)
+}
+
+/// Name of the extra field [`INCLUDE_SOURCE_NAME`] adds to each JSON value object.
+const SOURCE_FIELD_NAME: &str = "__source";
+
+/// Option key controlling whether a [`SOURCE_FIELD_NAME`] field, set to `sink_from_name` (the
+/// upstream MV/table name passed into [`SinkFormatterImpl::new`]), is added to every JSON value
+/// object. Useful for multi-tenant sinks writing to a shared topic, where a consumer otherwise
+/// can't tell which MV a record came from. Defaults to `false`. Debezium already includes this
+/// information in its `source` field, so this only applies to the non-Debezium JSON-valued
+/// formats.
+const INCLUDE_SOURCE_NAME: &str = "include_source_name";
+
+fn include_source_name(format_desc: &SinkFormatDesc) -> bool {
+    format_desc
+        .options
+        .get(INCLUDE_SOURCE_NAME)
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(false)
+}
+
+/// Option key controlling how often (in seconds) a sink flushes buffered records even without a
+/// barrier. Unset (the default) preserves the historical barrier-only flush behavior. See
+/// [`crate::sink::writer::AsyncTruncateLogSinkerOf::with_flush_interval`].
+const FLUSH_INTERVAL_SEC: &str = "flush_interval_sec";
+
+impl SinkFormatterImpl {
+    /// Reads [`FLUSH_INTERVAL_SEC`] out of `format_desc`, for sinks that want to offer a
+    /// time-based flush in addition to the default barrier-triggered one.
+    pub fn flush_interval(format_desc: &SinkFormatDesc) -> Option<Duration> {
+        format_desc
+            .options
+            .get(FLUSH_INTERVAL_SEC)
+            .and_then(|s| s.parse().ok())
+            .map(Duration::from_secs)
+    }
+}
+
+/// Retries `fetch` up to `max_retries` additional times, waiting `backoff` between attempts,
+/// instead of giving up on the first error. `max_retries == 0` (the default, see
+/// [`FETCH_RETRY_MAX_RETRIES`]) behaves exactly like calling `fetch` once, so sinks that don't
+/// opt in keep today's fail-fast startup behavior.
+async fn fetch_with_retry<F, Fut, T, E>(
+    mut fetch: F,
+    max_retries: usize,
+    backoff: Duration,
+) -> std::result::Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = std::result::Result<T, E>>,
+    E: std::fmt::Display,
+{
+    let mut attempt = 0;
+    loop {
+        match fetch().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < max_retries => {
+                attempt += 1;
+                tracing::warn!(
+                    attempt,
+                    max_retries,
+                    error = %e,
+                    "sink startup fetch failed, retrying"
+                );
+                tokio::time::sleep(backoff).await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
 impl SinkFormatterImpl {
     pub async fn new(
         format_desc: &SinkFormatDesc,
@@ -85,6 +296,7 @@ impl SinkFormatterImpl {
         db_name: String,
         sink_from_name: String,
         topic: &str,
+        aws_auth_props: Option<&AwsAuthProps>,
     ) -> Result<Self> {
         let err_unsupported = || {
             Err(SinkError::Config(anyhow!(
@@ -93,7 +305,20 @@ impl SinkFormatterImpl {
                 format_desc.encode,
             )))
         };
+        for &pk_index in &pk_indices {
+            if pk_index >= schema.len() {
+                return Err(SinkError::Config(anyhow!(
+                    "invalid pk index {} for schema with {} columns",
+                    pk_index,
+                    schema.len()
+                )));
+            }
+        }
+
         let timestamptz_mode = TimestamptzHandlingMode::from_options(&format_desc.options)?;
+        let timestamp_mode = TimestampHandlingMode::from_options(&format_desc.options)?;
+        let timestamp_mode_overrides =
+            TimestampHandlingMode::column_overrides_from_options(&format_desc.options)?;
 
         match format_desc.format {
             SinkFormat::AppendOnly => {
@@ -102,7 +327,7 @@ impl SinkFormatterImpl {
                         schema.clone(),
                         Some(pk_indices.clone()),
                         DateHandlingMode::FromCe,
-                        TimestampHandlingMode::Milli,
+                        timestamp_mode,
                         timestamptz_mode,
                         TimeHandlingMode::Milli,
                     )
@@ -110,23 +335,41 @@ impl SinkFormatterImpl {
 
                 match format_desc.encode {
                     SinkEncode::Json => {
-                        let val_encoder = JsonEncoder::new(
+                        let mut val_encoder = JsonEncoder::new(
                             schema,
                             None,
                             DateHandlingMode::FromCe,
-                            TimestampHandlingMode::Milli,
+                            timestamp_mode,
                             timestamptz_mode,
                             TimeHandlingMode::Milli,
-                        );
+                        )
+                        .with_timestamp_handling_mode_overrides(timestamp_mode_overrides);
+                        if include_source_name(format_desc) {
+                            val_encoder = val_encoder
+                                .with_extra_field(SOURCE_FIELD_NAME, sink_from_name.clone());
+                        }
                         let formatter = AppendOnlyFormatter::new(key_encoder, val_encoder);
-                        Ok(SinkFormatterImpl::AppendOnlyJson(formatter))
+                        if format_desc.options.get("with_op_column").map(String::as_str)
+                            == Some("true")
+                        {
+                            Ok(SinkFormatterImpl::AppendOnlyJsonWithOpColumn(
+                                formatter.with_op_column(),
+                            ))
+                        } else {
+                            Ok(SinkFormatterImpl::AppendOnlyJson(formatter))
+                        }
                     }
                     SinkEncode::Protobuf => {
-                        // By passing `None` as `aws_auth_props`, reading from `s3://` not supported yet.
-                        let (descriptor, sid) = crate::schema::protobuf::fetch_descriptor(
-                            &format_desc.options,
-                            topic,
-                            None,
+                        let (descriptor, sid) = fetch_with_retry(
+                            || {
+                                crate::schema::protobuf::fetch_descriptor(
+                                    &format_desc.options,
+                                    topic,
+                                    aws_auth_props,
+                                )
+                            },
+                            fetch_retry_max_retries(format_desc),
+                            FETCH_RETRY_BACKOFF,
                         )
                         .await
                         .map_err(|e| SinkError::Config(anyhow!(e)))?;
@@ -161,6 +404,16 @@ impl SinkFormatterImpl {
                             AppendOnlyFormatter::new(Some(key_encoder), val_encoder),
                         ))
                     }
+                    SinkEncode::Csv => {
+                        let delimiter = CsvEncoder::delimiter_from_options(&format_desc.options)?;
+                        let quote_style = CsvQuoteStyle::from_options(&format_desc.options)?;
+                        let null_string =
+                            CsvEncoder::null_string_from_options(&format_desc.options);
+                        let val_encoder =
+                            CsvEncoder::new(schema, None, delimiter, quote_style, null_string);
+                        let formatter = AppendOnlyFormatter::new(key_encoder, val_encoder);
+                        Ok(SinkFormatterImpl::AppendOnlyCsv(formatter))
+                    }
                 }
             }
             SinkFormat::Debezium => {
@@ -173,17 +426,51 @@ impl SinkFormatterImpl {
                     pk_indices,
                     db_name,
                     sink_from_name,
-                    DebeziumAdapterOpts::default(),
+                    DebeziumAdapterOpts {
+                        gen_tombstone: !suppress_tombstones(format_desc),
+                    },
                 )))
             }
             SinkFormat::Upsert => {
                 match format_desc.encode {
+                    SinkEncode::Json
+                        if format_desc.options.get(KEY_ENCODE).map(String::as_str)
+                            == Some("avro") =>
+                    {
+                        // Mixed encoding: an Avro key (e.g. to match a schema-registry-managed
+                        // key) paired with a JSON value, rather than the same encoder for both.
+                        let (key_schema, _) =
+                            crate::schema::avro::fetch_schema(&format_desc.options, topic)
+                                .await
+                                .map_err(|e| SinkError::Config(anyhow!(e)))?;
+                        let key_encoder = AvroEncoder::new(
+                            schema.clone(),
+                            Some(pk_indices),
+                            key_schema.schema,
+                            AvroHeader::ConfluentSchemaRegistry(key_schema.id),
+                        )?;
+                        let mut val_encoder = JsonEncoder::new(
+                            schema,
+                            None,
+                            DateHandlingMode::FromCe,
+                            timestamp_mode,
+                            timestamptz_mode,
+                            TimeHandlingMode::Milli,
+                        );
+                        if include_source_name(format_desc) {
+                            val_encoder = val_encoder
+                                .with_extra_field(SOURCE_FIELD_NAME, sink_from_name.clone());
+                        }
+                        let formatter = UpsertFormatter::new(key_encoder, val_encoder)
+                            .with_suppress_tombstones(suppress_tombstones(format_desc));
+                        Ok(SinkFormatterImpl::UpsertAvroKeyJsonValue(formatter))
+                    }
                     SinkEncode::Json => {
                         let mut key_encoder = JsonEncoder::new(
                             schema.clone(),
                             Some(pk_indices),
                             DateHandlingMode::FromCe,
-                            TimestampHandlingMode::Milli,
+                            timestamp_mode,
                             timestamptz_mode,
                             TimeHandlingMode::Milli,
                         );
@@ -191,10 +478,15 @@ impl SinkFormatterImpl {
                             schema,
                             None,
                             DateHandlingMode::FromCe,
-                            TimestampHandlingMode::Milli,
+                            timestamp_mode,
                             timestamptz_mode,
                             TimeHandlingMode::Milli,
-                        );
+                        )
+                        .with_timestamp_handling_mode_overrides(timestamp_mode_overrides);
+                        if include_source_name(format_desc) {
+                            val_encoder = val_encoder
+                                .with_extra_field(SOURCE_FIELD_NAME, sink_from_name.clone());
+                        }
 
                         if let Some(s) = format_desc.options.get("schemas.enable") {
                             match s.to_lowercase().parse::<bool>() {
@@ -217,7 +509,8 @@ impl SinkFormatterImpl {
                         };
 
                         // Initialize the upsert_stream
-                        let formatter = UpsertFormatter::new(key_encoder, val_encoder);
+                        let formatter = UpsertFormatter::new(key_encoder, val_encoder)
+                            .with_suppress_tombstones(suppress_tombstones(format_desc));
                         Ok(SinkFormatterImpl::UpsertJson(formatter))
                     }
                     SinkEncode::Template => {
@@ -238,10 +531,9 @@ impl SinkFormatterImpl {
                             key_format.clone(),
                         );
                         let val_encoder = TemplateEncoder::new(schema, None, value_format.clone());
-                        Ok(SinkFormatterImpl::UpsertTemplate(UpsertFormatter::new(
-                            key_encoder,
-                            val_encoder,
-                        )))
+                        let formatter = UpsertFormatter::new(key_encoder, val_encoder)
+                            .with_suppress_tombstones(suppress_tombstones(format_desc));
+                        Ok(SinkFormatterImpl::UpsertTemplate(formatter))
                     }
                     SinkEncode::Avro => {
                         let (key_schema, val_schema) =
@@ -260,14 +552,131 @@ impl SinkFormatterImpl {
                             val_schema.schema,
                             AvroHeader::ConfluentSchemaRegistry(val_schema.id),
                         )?;
-                        let formatter = UpsertFormatter::new(key_encoder, val_encoder);
+                        let formatter = UpsertFormatter::new(key_encoder, val_encoder)
+                            .with_suppress_tombstones(suppress_tombstones(format_desc));
                         Ok(SinkFormatterImpl::UpsertAvro(formatter))
                     }
-                    SinkEncode::Protobuf => err_unsupported(),
+                    SinkEncode::Protobuf => {
+                        let key_encoder = JsonEncoder::new(
+                            schema.clone(),
+                            Some(pk_indices),
+                            DateHandlingMode::FromCe,
+                            timestamp_mode,
+                            timestamptz_mode,
+                            TimeHandlingMode::Milli,
+                        );
+                        let (descriptor, sid) = fetch_with_retry(
+                            || {
+                                crate::schema::protobuf::fetch_descriptor(
+                                    &format_desc.options,
+                                    topic,
+                                    aws_auth_props,
+                                )
+                            },
+                            fetch_retry_max_retries(format_desc),
+                            FETCH_RETRY_BACKOFF,
+                        )
+                        .await
+                        .map_err(|e| SinkError::Config(anyhow!(e)))?;
+                        let header = match sid {
+                            None => ProtoHeader::None,
+                            Some(sid) => ProtoHeader::ConfluentSchemaRegistry(sid),
+                        };
+                        let val_encoder = ProtoEncoder::new(schema, None, descriptor, header)?;
+                        let formatter = UpsertFormatter::new(key_encoder, val_encoder)
+                            .with_suppress_tombstones(suppress_tombstones(format_desc));
+                        Ok(SinkFormatterImpl::UpsertProto(formatter))
+                    }
+                    SinkEncode::Csv => err_unsupported(),
                 }
             }
         }
     }
+
+    /// Enumerates the schema registry subjects that constructing a formatter for `format_desc`
+    /// would reference (key subject, then value subject if applicable), without actually fetching
+    /// any schema. Returns an empty list for encodes that don't use a schema registry (JSON,
+    /// Template, ...), or for protobuf sinks configured with `schema.location` instead of
+    /// `schema.registry`. This lets ops tooling pre-register or validate schemas before the sink
+    /// actually starts.
+    pub fn required_schema_registry_subjects(
+        format_desc: &SinkFormatDesc,
+        topic: &str,
+    ) -> Result<Vec<String>> {
+        let make_loader = || {
+            crate::schema::SchemaLoader::from_format_options(topic, &format_desc.options)
+                .map_err(|e| SinkError::Config(anyhow!(e)))
+        };
+
+        match format_desc.encode {
+            SinkEncode::Avro => {
+                let loader = make_loader()?;
+                Ok(vec![
+                    loader.key_subject().map_err(|e| SinkError::Config(anyhow!(e)))?,
+                    loader.val_subject().map_err(|e| SinkError::Config(anyhow!(e)))?,
+                ])
+            }
+            // Mixed encoding: `key.encode=avro` overrides the key's encode to Avro while the value
+            // stays JSON (see `SinkFormatterImpl::UpsertAvroKeyJsonValue`), so only the key subject
+            // is registered.
+            SinkEncode::Json if format_desc.options.get(KEY_ENCODE).map(String::as_str) == Some("avro") =>
+            {
+                let loader = make_loader()?;
+                Ok(vec![loader
+                    .key_subject()
+                    .map_err(|e| SinkError::Config(anyhow!(e)))?])
+            }
+            SinkEncode::Protobuf if format_desc.options.contains_key("schema.registry") => {
+                let loader = make_loader()?;
+                Ok(vec![loader
+                    .val_subject()
+                    .map_err(|e| SinkError::Config(anyhow!(e)))?])
+            }
+            SinkEncode::Json | SinkEncode::Protobuf | SinkEncode::Template | SinkEncode::Csv => {
+                Ok(vec![])
+            }
+        }
+    }
+
+    /// Whether this formatter's output preserves per-key ordering; see [`OrderingGuarantee`].
+    pub fn ordering_guarantee(&self) -> OrderingGuarantee {
+        match self {
+            SinkFormatterImpl::AppendOnlyJson(_)
+            | SinkFormatterImpl::AppendOnlyJsonWithOpColumn(_)
+            | SinkFormatterImpl::AppendOnlyProto(_)
+            | SinkFormatterImpl::AppendOnlyCsv(_)
+            | SinkFormatterImpl::AppendOnlyTemplate(_) => OrderingGuarantee::None,
+            SinkFormatterImpl::UpsertJson(_)
+            | SinkFormatterImpl::UpsertAvro(_)
+            | SinkFormatterImpl::UpsertAvroKeyJsonValue(_)
+            | SinkFormatterImpl::UpsertProto(_)
+            | SinkFormatterImpl::DebeziumJson(_)
+            | SinkFormatterImpl::UpsertTemplate(_) => OrderingGuarantee::PerKey,
+        }
+    }
+
+    /// The MIME content type of this formatter's encoded output, for sinks (e.g. HTTP,
+    /// object-storage) that need to set a `Content-Type` header or object metadata rather than
+    /// guessing it from the sink's own configuration. `Template` variants have no single encoding
+    /// (the template string is free-form), so they fall back to `application/octet-stream`.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            SinkFormatterImpl::AppendOnlyJson(_)
+            | SinkFormatterImpl::AppendOnlyJsonWithOpColumn(_)
+            | SinkFormatterImpl::UpsertJson(_)
+            // The value (the sink's body/object content) is JSON even though the key is Avro.
+            | SinkFormatterImpl::UpsertAvroKeyJsonValue(_)
+            | SinkFormatterImpl::DebeziumJson(_) => "application/json",
+            SinkFormatterImpl::AppendOnlyProto(_) | SinkFormatterImpl::UpsertProto(_) => {
+                "application/x-protobuf"
+            }
+            SinkFormatterImpl::AppendOnlyCsv(_) => "text/csv",
+            SinkFormatterImpl::UpsertAvro(_) => "application/avro",
+            SinkFormatterImpl::AppendOnlyTemplate(_) | SinkFormatterImpl::UpsertTemplate(_) => {
+                "application/octet-stream"
+            }
+        }
+    }
 }
 
 #[macro_export]
@@ -275,9 +684,13 @@ macro_rules! dispatch_sink_formatter_impl {
     ($impl:expr, $name:ident, $body:expr) => {
         match $impl {
             SinkFormatterImpl::AppendOnlyJson($name) => $body,
+            SinkFormatterImpl::AppendOnlyJsonWithOpColumn($name) => $body,
             SinkFormatterImpl::AppendOnlyProto($name) => $body,
+            SinkFormatterImpl::AppendOnlyCsv($name) => $body,
             SinkFormatterImpl::UpsertJson($name) => $body,
             SinkFormatterImpl::UpsertAvro($name) => $body,
+            SinkFormatterImpl::UpsertAvroKeyJsonValue($name) => $body,
+            SinkFormatterImpl::UpsertProto($name) => $body,
             SinkFormatterImpl::DebeziumJson($name) => $body,
             SinkFormatterImpl::AppendOnlyTemplate($name) => $body,
             SinkFormatterImpl::UpsertTemplate($name) => $body,
@@ -290,12 +703,334 @@ macro_rules! dispatch_sink_formatter_str_key_impl {
     ($impl:expr, $name:ident, $body:expr) => {
         match $impl {
             SinkFormatterImpl::AppendOnlyJson($name) => $body,
+            SinkFormatterImpl::AppendOnlyJsonWithOpColumn($name) => $body,
             SinkFormatterImpl::AppendOnlyProto($name) => $body,
+            SinkFormatterImpl::AppendOnlyCsv($name) => $body,
             SinkFormatterImpl::UpsertJson($name) => $body,
             SinkFormatterImpl::UpsertAvro(_) => unreachable!(),
+            SinkFormatterImpl::UpsertAvroKeyJsonValue(_) => unreachable!(),
+            SinkFormatterImpl::UpsertProto($name) => $body,
             SinkFormatterImpl::DebeziumJson($name) => $body,
             SinkFormatterImpl::AppendOnlyTemplate($name) => $body,
             SinkFormatterImpl::UpsertTemplate($name) => $body,
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use risingwave_common::array::StreamChunkTestExt;
+
+    use super::*;
+    use crate::sink::encoder::template::TemplateEncoder;
+
+    struct MockFormatter;
+
+    impl SinkFormatter for MockFormatter {
+        type K = String;
+        type V = String;
+
+        fn format_chunk(
+            &self,
+            _chunk: &StreamChunk,
+        ) -> impl Iterator<Item = Result<(Option<Self::K>, Option<Self::V>)>> {
+            std::iter::once(Ok((Some("k".to_owned()), Some("v".to_owned()))))
+        }
+    }
+
+    #[test]
+    fn test_sampling_formatter_fires_at_interval() {
+        let samples: Arc<Mutex<Vec<FormatSample>>> = Arc::new(Mutex::new(vec![]));
+        let samples_clone = samples.clone();
+        let formatter = SamplingFormatter::new(
+            MockFormatter,
+            3,
+            Arc::new(move |sample| samples_clone.lock().unwrap().push(sample)),
+        );
+
+        let chunk = StreamChunk::from_pretty(
+            "  I
+             + 1",
+        );
+
+        for _ in 0..7 {
+            let results = formatter.format_chunk(&chunk).collect_vec();
+            assert_eq!(results.len(), 1);
+        }
+
+        // Chunks 0 and 3 and 6 (0-indexed) trigger a sample: 3 total.
+        let samples = samples.lock().unwrap();
+        assert_eq!(samples.len(), 3);
+        for sample in samples.iter() {
+            // "v" serializes to 1 byte via the identity/`String -> Vec<u8>` blanket impl.
+            assert_eq!(sample.serialized_bytes, 1);
+        }
+    }
+
+    #[test]
+    fn test_sampling_formatter_disabled() {
+        let samples: Arc<Mutex<Vec<FormatSample>>> = Arc::new(Mutex::new(vec![]));
+        let samples_clone = samples.clone();
+        let formatter = SamplingFormatter::new(
+            MockFormatter,
+            0,
+            Arc::new(move |sample| samples_clone.lock().unwrap().push(sample)),
+        );
+
+        let chunk = StreamChunk::from_pretty(
+            "  I
+             + 1",
+        );
+        for _ in 0..5 {
+            formatter.format_chunk(&chunk).for_each(drop);
+        }
+        assert!(samples.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_with_retry_recovers_after_transient_failures() {
+        let attempts = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let attempts_clone = attempts.clone();
+        let result: std::result::Result<&str, String> = fetch_with_retry(
+            move || {
+                let attempts = attempts_clone.clone();
+                async move {
+                    if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                        Err("transient failure".to_owned())
+                    } else {
+                        Ok("descriptor")
+                    }
+                }
+            },
+            5,
+            Duration::from_millis(0),
+        )
+        .await;
+
+        assert_eq!(result, Ok("descriptor"));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_with_retry_fails_fast_by_default() {
+        let attempts = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let attempts_clone = attempts.clone();
+        let result: std::result::Result<&str, String> = fetch_with_retry(
+            move || {
+                let attempts = attempts_clone.clone();
+                async move {
+                    attempts.fetch_add(1, Ordering::SeqCst);
+                    Err("permanent failure".to_owned())
+                }
+            },
+            0,
+            Duration::from_millis(0),
+        )
+        .await;
+
+        assert_eq!(result, Err("permanent failure".to_owned()));
+        // `max_retries == 0` (the default) makes only a single attempt.
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_required_schema_registry_subjects_avro_upsert() {
+        let mut options = std::collections::BTreeMap::new();
+        options.insert(
+            "schema.registry".to_owned(),
+            "http://localhost:8081".to_owned(),
+        );
+        let format_desc = SinkFormatDesc {
+            format: SinkFormat::Upsert,
+            encode: SinkEncode::Avro,
+            options,
+        };
+
+        let subjects =
+            SinkFormatterImpl::required_schema_registry_subjects(&format_desc, "my_topic")
+                .unwrap();
+        assert_eq!(
+            subjects,
+            vec!["my_topic-key".to_owned(), "my_topic-value".to_owned()]
+        );
+    }
+
+    #[test]
+    fn test_required_schema_registry_subjects_json_is_empty() {
+        let format_desc = SinkFormatDesc {
+            format: SinkFormat::Upsert,
+            encode: SinkEncode::Json,
+            options: std::collections::BTreeMap::new(),
+        };
+
+        let subjects =
+            SinkFormatterImpl::required_schema_registry_subjects(&format_desc, "my_topic")
+                .unwrap();
+        assert!(subjects.is_empty());
+    }
+
+    #[test]
+    fn test_ordering_guarantee_per_variant() {
+        let schema = Schema::new(vec![risingwave_common::catalog::Field::with_name(
+            risingwave_common::types::DataType::Int32,
+            "v1",
+        )]);
+        let json_encoder = |schema: Schema| {
+            JsonEncoder::new(
+                schema,
+                None,
+                DateHandlingMode::FromCe,
+                TimestampHandlingMode::Milli,
+                TimestamptzHandlingMode::UtcString,
+                TimeHandlingMode::Milli,
+            )
+        };
+
+        let append_only_json =
+            SinkFormatterImpl::AppendOnlyJson(AppendOnlyFormatter::new(
+                None,
+                json_encoder(schema.clone()),
+            ));
+        assert_eq!(
+            append_only_json.ordering_guarantee(),
+            OrderingGuarantee::None
+        );
+
+        let append_only_json_with_op_column = SinkFormatterImpl::AppendOnlyJsonWithOpColumn(
+            AppendOnlyFormatter::new(None, json_encoder(schema.clone())).with_op_column(),
+        );
+        assert_eq!(
+            append_only_json_with_op_column.ordering_guarantee(),
+            OrderingGuarantee::None
+        );
+
+        let append_only_template = SinkFormatterImpl::AppendOnlyTemplate(AppendOnlyFormatter::new(
+            None,
+            TemplateEncoder::new(schema.clone(), None, "{v1}".to_owned()),
+        ));
+        assert_eq!(
+            append_only_template.ordering_guarantee(),
+            OrderingGuarantee::None
+        );
+
+        let upsert_json = SinkFormatterImpl::UpsertJson(UpsertFormatter::new(
+            json_encoder(schema.clone()),
+            json_encoder(schema.clone()),
+        ));
+        assert_eq!(upsert_json.ordering_guarantee(), OrderingGuarantee::PerKey);
+
+        let upsert_template = SinkFormatterImpl::UpsertTemplate(UpsertFormatter::new(
+            TemplateEncoder::new(schema.clone(), None, "{v1}".to_owned()),
+            TemplateEncoder::new(schema.clone(), None, "{v1}".to_owned()),
+        ));
+        assert_eq!(
+            upsert_template.ordering_guarantee(),
+            OrderingGuarantee::PerKey
+        );
+
+        let debezium_json = SinkFormatterImpl::DebeziumJson(DebeziumJsonFormatter::new(
+            schema,
+            vec![0],
+            "db".to_owned(),
+            "table".to_owned(),
+            DebeziumAdapterOpts::default(),
+        ));
+        assert_eq!(debezium_json.ordering_guarantee(), OrderingGuarantee::PerKey);
+
+        // `AppendOnlyProto` and `UpsertAvro` aren't exercised here (their encoders need a schema
+        // registry/descriptor), but `ordering_guarantee`'s exhaustive match still covers them:
+        // `AppendOnlyProto` groups with the other append-only variants (`None`) and `UpsertAvro`
+        // with the other upsert variants (`PerKey`).
+    }
+
+    #[test]
+    fn test_content_type_per_variant() {
+        let schema = Schema::new(vec![risingwave_common::catalog::Field::with_name(
+            risingwave_common::types::DataType::Int32,
+            "v1",
+        )]);
+        let json_encoder = |schema: Schema| {
+            JsonEncoder::new(
+                schema,
+                None,
+                DateHandlingMode::FromCe,
+                TimestampHandlingMode::Milli,
+                TimestamptzHandlingMode::UtcString,
+                TimeHandlingMode::Milli,
+            )
+        };
+
+        let append_only_json =
+            SinkFormatterImpl::AppendOnlyJson(AppendOnlyFormatter::new(
+                None,
+                json_encoder(schema.clone()),
+            ));
+        assert_eq!(append_only_json.content_type(), "application/json");
+
+        let debezium_json = SinkFormatterImpl::DebeziumJson(DebeziumJsonFormatter::new(
+            schema.clone(),
+            vec![0],
+            "db".to_owned(),
+            "table".to_owned(),
+            DebeziumAdapterOpts::default(),
+        ));
+        assert_eq!(debezium_json.content_type(), "application/json");
+
+        let append_only_csv = SinkFormatterImpl::AppendOnlyCsv(AppendOnlyFormatter::new(
+            None,
+            CsvEncoder::new(
+                schema.clone(),
+                None,
+                b',',
+                CsvQuoteStyle::Necessary,
+                "".to_owned(),
+            ),
+        ));
+        assert_eq!(append_only_csv.content_type(), "text/csv");
+
+        let append_only_template = SinkFormatterImpl::AppendOnlyTemplate(AppendOnlyFormatter::new(
+            None,
+            TemplateEncoder::new(schema.clone(), None, "{v1}".to_owned()),
+        ));
+        assert_eq!(
+            append_only_template.content_type(),
+            "application/octet-stream"
+        );
+
+        // `AppendOnlyProto`, `UpsertProto` and `UpsertAvro` aren't exercised here (their encoders
+        // need a schema registry/descriptor), but `content_type`'s exhaustive match still covers
+        // them (`application/x-protobuf` and `application/avro` respectively).
+    }
+
+    #[tokio::test]
+    async fn test_new_rejects_out_of_bounds_pk_index() {
+        let schema = Schema::new(vec![risingwave_common::catalog::Field::with_name(
+            risingwave_common::types::DataType::Int32,
+            "v1",
+        )]);
+        let format_desc = SinkFormatDesc {
+            format: SinkFormat::AppendOnly,
+            encode: SinkEncode::Json,
+            options: std::collections::BTreeMap::new(),
+        };
+
+        let err = SinkFormatterImpl::new(
+            &format_desc,
+            schema,
+            vec![1],
+            "db".to_owned(),
+            "table".to_owned(),
+            "my_topic",
+            None,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "config error: invalid pk index 1 for schema with 1 columns"
+        );
+    }
+}
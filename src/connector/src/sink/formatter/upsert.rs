@@ -21,6 +21,9 @@ use crate::tri;
 pub struct UpsertFormatter<KE, VE> {
     key_encoder: KE,
     val_encoder: VE,
+    /// If `true`, deletes are dropped entirely instead of being emitted as a tombstone (a
+    /// null-value message with the row's key). See [`UpsertFormatter::with_suppress_tombstones`].
+    suppress_tombstones: bool,
 }
 
 impl<KE, VE> UpsertFormatter<KE, VE> {
@@ -28,8 +31,16 @@ impl<KE, VE> UpsertFormatter<KE, VE> {
         Self {
             key_encoder,
             val_encoder,
+            suppress_tombstones: false,
         }
     }
+
+    /// For sinks where deletes are meaningless (e.g. append-only-oriented consumers of a nominally
+    /// upsert topic), drop delete records entirely rather than emitting a null-value tombstone.
+    pub fn with_suppress_tombstones(mut self, suppress_tombstones: bool) -> Self {
+        self.suppress_tombstones = suppress_tombstones;
+        self
+    }
 }
 
 impl<KE: RowEncoder, VE: RowEncoder> SinkFormatter for UpsertFormatter<KE, VE> {
@@ -42,20 +53,201 @@ impl<KE: RowEncoder, VE: RowEncoder> SinkFormatter for UpsertFormatter<KE, VE> {
     ) -> impl Iterator<Item = Result<(Option<Self::K>, Option<Self::V>)>> {
         std::iter::from_coroutine(|| {
             for (op, row) in chunk.rows() {
-                let event_key_object = Some(tri!(self.key_encoder.encode(row)));
-
                 let event_object = match op {
                     Op::Insert | Op::UpdateInsert => Some(tri!(self.val_encoder.encode(row))),
-                    // Empty value with a key
-                    Op::Delete => None,
+                    Op::Delete => {
+                        if self.suppress_tombstones {
+                            continue;
+                        }
+                        // Empty value with a key
+                        None
+                    }
                     Op::UpdateDelete => {
                         // upsert semantic does not require update delete event
                         continue;
                     }
                 };
 
+                let event_key_object = Some(tri!(self.key_encoder.encode(row)));
                 yield Ok((event_key_object, event_object))
             }
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::StreamChunkTestExt;
+    use risingwave_common::catalog::{Field, Schema};
+    use risingwave_common::types::DataType;
+
+    use super::*;
+    use crate::sink::encoder::{
+        AvroEncoder, AvroHeader, DateHandlingMode, JsonEncoder, ProtoEncoder, ProtoHeader, SerTo,
+        TimeHandlingMode, TimestampHandlingMode, TimestamptzHandlingMode,
+    };
+
+    fn test_schema() -> Schema {
+        Schema::new(vec![risingwave_common::catalog::Field::with_name(
+            DataType::Int32,
+            "v1",
+        )])
+    }
+
+    fn json_encoder(schema: Schema, key_indices: Option<Vec<usize>>) -> JsonEncoder {
+        JsonEncoder::new(
+            schema,
+            key_indices,
+            DateHandlingMode::FromCe,
+            TimestampHandlingMode::Milli,
+            TimestamptzHandlingMode::UtcString,
+            TimeHandlingMode::Milli,
+        )
+    }
+
+    #[test]
+    fn test_suppress_tombstones_drops_deletes() {
+        let schema = test_schema();
+        let formatter = UpsertFormatter::new(
+            json_encoder(schema.clone(), Some(vec![0])),
+            json_encoder(schema, None),
+        )
+        .with_suppress_tombstones(true);
+
+        let chunk = StreamChunk::from_pretty(
+            "  i
+             + 1
+             - 2",
+        );
+
+        let results = formatter
+            .format_chunk(&chunk)
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        // Only the insert survives; the delete is dropped rather than becoming a
+        // `(Some(key), None)` tombstone.
+        assert_eq!(results.len(), 1);
+        assert!(results[0].0.is_some());
+        assert!(results[0].1.is_some());
+    }
+
+    #[test]
+    fn test_tombstones_emitted_by_default() {
+        let schema = test_schema();
+        let formatter = UpsertFormatter::new(
+            json_encoder(schema.clone(), Some(vec![0])),
+            json_encoder(schema, None),
+        );
+
+        let chunk = StreamChunk::from_pretty(
+            "  i
+             - 2",
+        );
+
+        let results = formatter
+            .format_chunk(&chunk)
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].0.is_some());
+        assert!(results[0].1.is_none());
+    }
+
+    #[test]
+    fn test_upsert_proto_value_emits_tombstone_for_delete() {
+        let pool_path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("src/test_data/proto_recursive/recursive.pb");
+        let pool_bytes = std::fs::read(pool_path).unwrap();
+        let pool = prost_reflect::DescriptorPool::decode(pool_bytes.as_ref()).unwrap();
+        let descriptor = pool.get_message_by_name("recursive.AllTypes").unwrap();
+
+        let schema = Schema::new(vec![
+            Field::with_name(DataType::Int32, "id"),
+            Field::with_name(DataType::Int32, "int32_field"),
+        ]);
+        let key_encoder = json_encoder(schema.clone(), Some(vec![0]));
+        let val_encoder =
+            ProtoEncoder::new(schema, Some(vec![1]), descriptor, ProtoHeader::None).unwrap();
+        let formatter = UpsertFormatter::new(key_encoder, val_encoder);
+
+        let chunk = StreamChunk::from_pretty(
+            "  i  i
+             + 1  100
+             - 2  200",
+        );
+
+        let results = formatter
+            .format_chunk(&chunk)
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(results.len(), 2);
+
+        // The inserted row is keyed by its JSON-encoded pk and gets a protobuf-encoded value.
+        assert_eq!(
+            results[0].0.as_ref().unwrap().get("id").unwrap(),
+            &serde_json::json!(1)
+        );
+        let value: Vec<u8> = results[0].1.clone().unwrap().ser_to().unwrap();
+        assert!(!value.is_empty());
+
+        // The deleted row is still keyed, but its value is `None`, i.e. a log-compaction
+        // tombstone, not a protobuf-encoded "all fields default" message.
+        assert_eq!(
+            results[1].0.as_ref().unwrap().get("id").unwrap(),
+            &serde_json::json!(2)
+        );
+        assert!(results[1].1.is_none());
+    }
+
+    #[test]
+    fn test_upsert_avro_key_json_value() {
+        // Mixed encoding: an Avro-encoded key (as used by `UpsertAvroKeyJsonValue`) alongside a
+        // plain JSON value.
+        let avro_schema = apache_avro::Schema::parse_str(
+            r#"{"type": "record", "name": "Root", "fields": [
+                {"name": "id", "type": "int"}
+            ]}"#,
+        )
+        .unwrap();
+
+        let schema = Schema::new(vec![
+            Field::with_name(DataType::Int32, "id"),
+            Field::with_name(DataType::Int32, "v1"),
+        ]);
+        let key_encoder =
+            AvroEncoder::new(schema.clone(), Some(vec![0]), avro_schema.into(), AvroHeader::None)
+                .unwrap();
+        let val_encoder = json_encoder(schema, None);
+        let formatter = UpsertFormatter::new(key_encoder, val_encoder);
+
+        let chunk = StreamChunk::from_pretty(
+            "  i  i
+             + 1  100
+             - 2  200",
+        );
+
+        let results = formatter
+            .format_chunk(&chunk)
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(results.len(), 2);
+
+        // The inserted row's key decodes as an Avro record, and its value is plain JSON.
+        assert_eq!(
+            results[0].0.as_ref().unwrap().value,
+            apache_avro::types::Value::Record(vec![("id".into(), apache_avro::types::Value::Int(1))])
+        );
+        assert_eq!(
+            results[0].1.as_ref().unwrap().get("id").unwrap(),
+            &serde_json::json!(1)
+        );
+
+        // The deleted row is still keyed by its Avro-encoded pk, but its value is `None`, i.e. a
+        // log-compaction tombstone.
+        assert_eq!(
+            results[1].0.as_ref().unwrap().value,
+            apache_avro::types::Value::Record(vec![("id".into(), apache_avro::types::Value::Int(2))])
+        );
+        assert!(results[1].1.is_none());
+    }
+}
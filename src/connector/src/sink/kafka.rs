@@ -318,6 +318,7 @@ impl Sink for KafkaSink {
             self.db_name.clone(),
             self.sink_from_name.clone(),
             &self.config.common.topic,
+            None,
         )
         .await?;
         let max_delivery_buffer_size = (self
@@ -329,9 +330,11 @@ impl Sink for KafkaSink {
             .unwrap_or(KAFKA_WRITER_MAX_QUEUE_SIZE) as f32
             * KAFKA_WRITER_MAX_QUEUE_SIZE_RATIO) as usize;
 
+        let flush_interval = SinkFormatterImpl::flush_interval(&self.format_desc);
         Ok(KafkaSinkWriter::new(self.config.clone(), formatter)
             .await?
-            .into_log_sinker(max_delivery_buffer_size))
+            .into_log_sinker(max_delivery_buffer_size)
+            .with_flush_interval(flush_interval))
     }
 
     async fn validate(&self) -> Result<()> {
@@ -350,6 +353,7 @@ impl Sink for KafkaSink {
             self.db_name.clone(),
             self.sink_from_name.clone(),
             &self.config.common.topic,
+            None,
         )
         .await?;
 
@@ -95,6 +95,7 @@ impl Sink for KinesisSink {
             self.db_name.clone(),
             self.sink_from_name.clone(),
             &self.config.common.stream_name,
+            None,
         )
         .await?;
 
@@ -167,6 +168,7 @@ impl KinesisSinkWriter {
             db_name,
             sink_from_name,
             &config.common.stream_name,
+            None,
         )
         .await?;
         let client = config
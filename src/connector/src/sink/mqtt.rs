@@ -166,6 +166,7 @@ impl MqttSinkWriter {
             db_name,
             sink_from_name,
             &config.common.topic,
+            None,
         )
         .await?;
 
@@ -202,6 +202,7 @@ impl Sink for PulsarSink {
             self.db_name.clone(),
             self.sink_from_name.clone(),
             &self.config.common.topic,
+            Some(&self.config.aws_auth_props),
         )
         .await?;
 
@@ -256,6 +257,7 @@ impl PulsarSinkWriter {
             db_name,
             sink_from_name,
             &config.common.topic,
+            Some(&config.aws_auth_props),
         )
         .await?;
         let pulsar = config
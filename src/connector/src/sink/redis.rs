@@ -234,6 +234,7 @@ impl RedisSinkWriter {
             db_name,
             sink_from_name,
             "NO_TOPIC",
+            None,
         )
         .await?;
 
@@ -259,6 +260,7 @@ impl RedisSinkWriter {
             "d1".to_string(),
             "t1".to_string(),
             "NO_TOPIC",
+            None,
         )
         .await?;
         Ok(Self {
@@ -13,16 +13,13 @@
 // limitations under the License.
 
 use std::future::{Future, Ready};
-use std::pin::pin;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
-use futures::future::{select, Either};
 use futures::TryFuture;
 use risingwave_common::array::StreamChunk;
 use risingwave_common::buffer::Bitmap;
-use rw_futures_util::drop_either_future;
 
 use crate::sink::encoder::SerTo;
 use crate::sink::formatter::SinkFormatter;
@@ -231,6 +228,8 @@ where
 pub struct AsyncTruncateLogSinkerOf<W: AsyncTruncateSinkWriter> {
     writer: W,
     future_manager: DeliveryFutureManager<W::DeliveryFuture>,
+    /// See [`Self::with_flush_interval`].
+    flush_interval: Option<Duration>,
 }
 
 impl<W: AsyncTruncateSinkWriter> AsyncTruncateLogSinkerOf<W> {
@@ -238,23 +237,33 @@ impl<W: AsyncTruncateSinkWriter> AsyncTruncateLogSinkerOf<W> {
         AsyncTruncateLogSinkerOf {
             writer,
             future_manager: DeliveryFutureManager::new(max_future_count),
+            flush_interval: None,
         }
     }
+
+    /// Sinks otherwise only flush (i.e. call [`AsyncTruncateSinkWriter::barrier`]) when a real
+    /// barrier comes through the log store, which on a low-throughput stream with a long barrier
+    /// interval can delay delivery. Setting `flush_interval` additionally flushes on a timer,
+    /// trading some batching for freshness. `None` (the default) preserves the historical,
+    /// barrier-only behavior.
+    pub fn with_flush_interval(mut self, flush_interval: Option<Duration>) -> Self {
+        self.flush_interval = flush_interval;
+        self
+    }
 }
 
 #[async_trait]
 impl<W: AsyncTruncateSinkWriter> LogSinker for AsyncTruncateLogSinkerOf<W> {
     async fn consume_log_and_sink(mut self, log_reader: &mut impl SinkLogReader) -> Result<()> {
+        let mut flush_ticker = self.flush_interval.map(tokio::time::interval);
+        if let Some(flush_ticker) = &mut flush_ticker {
+            // A slow `write_chunk`/`barrier` call should not cause a burst of catch-up ticks.
+            flush_ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        }
+
         loop {
-            let select_result = drop_either_future(
-                select(
-                    pin!(log_reader.next_item()),
-                    pin!(self.future_manager.next_truncate_offset()),
-                )
-                .await,
-            );
-            match select_result {
-                Either::Left(item_result) => {
+            tokio::select! {
+                item_result = log_reader.next_item() => {
                     let (epoch, item) = item_result?;
                     match item {
                         LogStoreReadItem::StreamChunk { chunk_id, chunk } => {
@@ -268,10 +277,15 @@ impl<W: AsyncTruncateSinkWriter> LogSinker for AsyncTruncateLogSinkerOf<W> {
                         LogStoreReadItem::UpdateVnodeBitmap(_) => {}
                     }
                 }
-                Either::Right(offset_result) => {
+                offset_result = self.future_manager.next_truncate_offset() => {
                     let offset = offset_result?;
                     log_reader.truncate(offset).await?;
                 }
+                // Only polled when `flush_ticker` is `Some`; `next_truncate_offset`/`next_item`
+                // above otherwise drive the loop on their own.
+                _ = async { flush_ticker.as_mut().unwrap().tick().await }, if flush_ticker.is_some() => {
+                    self.writer.barrier(false).await?;
+                }
             }
         }
     }
@@ -286,3 +300,81 @@ where
         AsyncTruncateLogSinkerOf::new(self, max_future_count)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::sink::log_store::LogStoreResult;
+
+    /// Yields a single [`StreamChunk`] and then never produces another item, simulating a
+    /// low-throughput stream where no more barriers arrive.
+    struct OneChunkThenIdleLogReader {
+        chunk: Option<StreamChunk>,
+    }
+
+    impl SinkLogReader for OneChunkThenIdleLogReader {
+        async fn next_item(&mut self) -> LogStoreResult<(u64, LogStoreReadItem)> {
+            match self.chunk.take() {
+                Some(chunk) => Ok((
+                    0,
+                    LogStoreReadItem::StreamChunk { chunk, chunk_id: 0 },
+                )),
+                None => std::future::pending().await,
+            }
+        }
+
+        async fn truncate(&mut self, _offset: TruncateOffset) -> LogStoreResult<()> {
+            Ok(())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct CountingWriter {
+        non_checkpoint_barriers: Arc<AtomicUsize>,
+    }
+
+    impl AsyncTruncateSinkWriter for CountingWriter {
+        async fn write_chunk<'a>(
+            &'a mut self,
+            _chunk: StreamChunk,
+            _add_future: DeliveryFutureManagerAddFuture<'a, Self::DeliveryFuture>,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn barrier(&mut self, is_checkpoint: bool) -> Result<()> {
+            if !is_checkpoint {
+                self.non_checkpoint_barriers.fetch_add(1, Ordering::SeqCst);
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_flush_interval_flushes_without_a_barrier() {
+        let chunk = StreamChunk::from_pretty(
+            " I
+            + 1",
+        );
+        let mut log_reader = OneChunkThenIdleLogReader { chunk: Some(chunk) };
+        let writer = CountingWriter::default();
+        let non_checkpoint_barriers = writer.non_checkpoint_barriers.clone();
+
+        let sinker = writer
+            .into_log_sinker(usize::MAX)
+            .with_flush_interval(Some(Duration::from_millis(10)));
+
+        // The sink loop never terminates on its own; give it enough time for a few flush ticks,
+        // then check that the writer's buffered record was flushed despite no barrier ever
+        // arriving from `log_reader`.
+        let _ = tokio::time::timeout(
+            Duration::from_millis(100),
+            sinker.consume_log_and_sink(&mut log_reader),
+        )
+        .await;
+
+        assert!(non_checkpoint_barriers.load(Ordering::SeqCst) > 0);
+    }
+}
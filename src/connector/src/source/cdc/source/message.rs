@@ -49,3 +49,51 @@ impl From<CdcMessage> for SourceMessage {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `CdcSplitReader` batches `CdcMessage`s coming off the JNI event stream as-is (see
+    // `CommonSplitReader::into_data_stream`), so transaction `BEGIN`/`END` markers are preserved
+    // as ordinary `SourceMessage`s with `is_transaction_meta` set, rather than being dropped or
+    // merged with data messages. Downstream, `PlainParser::parse_inner` uses that flag to route
+    // them to `ParseResult::TransactionControl` instead of treating them as row data.
+    #[test]
+    fn test_transaction_boundary_messages_are_surfaced() {
+        let mock_event_stream = vec![
+            CdcMessage {
+                payload: r#"{"status":"BEGIN","id":"3"}"#.to_owned(),
+                partition: "0".to_owned(),
+                offset: "0".to_owned(),
+                is_transaction_meta: true,
+                ..Default::default()
+            },
+            CdcMessage {
+                payload: r#"{"before":null,"after":{"id":1}}"#.to_owned(),
+                partition: "0".to_owned(),
+                offset: "1".to_owned(),
+                is_transaction_meta: false,
+                ..Default::default()
+            },
+            CdcMessage {
+                payload: r#"{"status":"END","id":"3"}"#.to_owned(),
+                partition: "0".to_owned(),
+                offset: "2".to_owned(),
+                is_transaction_meta: true,
+                ..Default::default()
+            },
+        ];
+
+        let messages: Vec<SourceMessage> =
+            mock_event_stream.into_iter().map(SourceMessage::from).collect();
+
+        let is_transaction_meta = |msg: &SourceMessage| match &msg.meta {
+            SourceMeta::DebeziumCdc(meta) => meta.is_transaction_meta,
+            _ => unreachable!(),
+        };
+        assert!(is_transaction_meta(&messages[0]));
+        assert!(!is_transaction_meta(&messages[1]));
+        assert!(is_transaction_meta(&messages[2]));
+    }
+}
@@ -13,11 +13,11 @@
 // limitations under the License.
 
 use std::str::FromStr;
+use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, Context};
 use async_trait::async_trait;
 use futures_async_stream::try_stream;
-use itertools::Itertools;
 use prost::Message;
 use risingwave_common::bail;
 use risingwave_common::metrics::GLOBAL_ERROR_METRICS;
@@ -93,8 +93,10 @@ impl<T: CdcSourceTypeTrait> SplitReader for CdcSplitReader<T> {
         }
 
         let source_id = split.split_id() as u64;
+        let source_id_str = source_id.to_string();
         let source_type = conn_props.get_source_type_pb();
         let (mut tx, mut rx) = mpsc::channel(DEFAULT_CHANNEL_SIZE);
+        let metrics = source_ctx.metrics.clone();
 
         let jvm = JVM.get_or_init()?;
 
@@ -137,14 +139,29 @@ impl<T: CdcSourceTypeTrait> SplitReader for CdcSplitReader<T> {
                     tracing::info!(?source_id, "end of jni call runJniDbzSourceThread");
                 }
                 Err(e) => {
+                    metrics
+                        .cdc_jni_call_error_count
+                        .with_label_values(&[&source_id_str])
+                        .inc();
                     tracing::error!(?source_id, error = %e.as_report(), "jni call error");
                 }
             }
         });
 
         // wait for the handshake message
+        let jni_startup_start_time = Instant::now();
         if let Some(res) = rx.recv().await {
-            let resp: GetEventStreamResponse = res?;
+            let resp: GetEventStreamResponse = match res {
+                Ok(resp) => resp,
+                Err(e) => {
+                    source_ctx
+                        .metrics
+                        .cdc_jni_call_error_count
+                        .with_label_values(&[&source_id_str])
+                        .inc();
+                    return Err(e.into());
+                }
+            };
             let inited = match resp.control {
                 Some(info) => info.handshake_ok,
                 None => {
@@ -152,7 +169,17 @@ impl<T: CdcSourceTypeTrait> SplitReader for CdcSplitReader<T> {
                     false
                 }
             };
+            source_ctx
+                .metrics
+                .cdc_jni_startup_latency
+                .with_label_values(&[&source_id_str])
+                .observe(jni_startup_start_time.elapsed().as_millis() as f64);
             if !inited {
+                source_ctx
+                    .metrics
+                    .cdc_jni_call_error_count
+                    .with_label_values(&[&source_id_str])
+                    .inc();
                 bail!("failed to start cdc connector");
             }
         }
@@ -194,6 +221,14 @@ impl<T: CdcSourceTypeTrait> SplitReader for CdcSplitReader<T> {
     }
 }
 
+/// Upper bound on the number of [`SourceMessage`]s coalesced from adjacent [`GetEventStreamResponse`]s
+/// into a single yielded batch, to amortize downstream chunk-building overhead under high event rates.
+const CDC_EVENT_BATCH_MAX_SIZE: usize = 1024;
+
+/// Max time a non-empty, not-yet-full batch is held before being flushed, so that a low event rate
+/// doesn't stall backpressure-sensitive downstream operators waiting for a batch to fill up.
+const CDC_EVENT_BATCH_FLUSH_INTERVAL: Duration = Duration::from_millis(100);
+
 impl<T: CdcSourceTypeTrait> CommonSplitReader for CdcSplitReader<T> {
     #[try_stream(ok = Vec<SourceMessage>, error = ConnectorError)]
     async fn into_data_stream(self) {
@@ -202,29 +237,50 @@ impl<T: CdcSourceTypeTrait> CommonSplitReader for CdcSplitReader<T> {
         let source_id = self.source_id.to_string();
         let metrics = self.source_ctx.metrics.clone();
 
-        while let Some(result) = rx.recv().await {
-            match result {
-                Ok(GetEventStreamResponse { events, .. }) => {
-                    tracing::trace!("receive {} cdc events ", events.len());
-                    metrics
-                        .connector_source_rows_received
-                        .with_label_values(&[source_type.as_str_name(), &source_id])
-                        .inc_by(events.len() as u64);
-                    let msgs = events.into_iter().map(SourceMessage::from).collect_vec();
-                    yield msgs;
+        let mut buffer: Vec<SourceMessage> = Vec::with_capacity(CDC_EVENT_BATCH_MAX_SIZE);
+        let mut flush_interval = tokio::time::interval(CDC_EVENT_BATCH_FLUSH_INTERVAL);
+        flush_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                biased;
+                result = rx.recv() => {
+                    let Some(result) = result else {
+                        if !buffer.is_empty() {
+                            yield std::mem::take(&mut buffer);
+                        }
+                        bail!("all senders are dropped");
+                    };
+                    match result {
+                        Ok(GetEventStreamResponse { events, .. }) => {
+                            tracing::trace!("receive {} cdc events ", events.len());
+                            metrics
+                                .connector_source_rows_received
+                                .with_label_values(&[source_type.as_str_name(), &source_id])
+                                .inc_by(events.len() as u64);
+                            buffer.extend(events.into_iter().map(SourceMessage::from));
+                            while buffer.len() >= CDC_EVENT_BATCH_MAX_SIZE {
+                                let rest = buffer.split_off(CDC_EVENT_BATCH_MAX_SIZE);
+                                yield std::mem::replace(&mut buffer, rest);
+                            }
+                        }
+                        Err(e) => {
+                            GLOBAL_ERROR_METRICS.user_source_error.report([
+                                "cdc_source".to_owned(),
+                                source_id.clone(),
+                                self.source_ctx.source_name.clone(),
+                                self.source_ctx.fragment_id.to_string(),
+                            ]);
+                            Err(e)?;
+                        }
+                    }
                 }
-                Err(e) => {
-                    GLOBAL_ERROR_METRICS.user_source_error.report([
-                        "cdc_source".to_owned(),
-                        source_id.clone(),
-                        self.source_ctx.source_name.clone(),
-                        self.source_ctx.fragment_id.to_string(),
-                    ]);
-                    Err(e)?;
+                _ = flush_interval.tick() => {
+                    if !buffer.is_empty() {
+                        yield std::mem::replace(&mut buffer, Vec::with_capacity(CDC_EVENT_BATCH_MAX_SIZE));
+                    }
                 }
             }
         }
-
-        bail!("all senders are dropped");
     }
 }
@@ -69,6 +69,13 @@ pub struct SourceMetrics {
     pub connector_source_rows_received: GenericCounterVec<AtomicU64>,
 
     pub direct_cdc_event_lag_latency: HistogramVec,
+
+    /// Time spent waiting for the CDC connector's JNI-backed source thread to start up, i.e.
+    /// from spawning the thread to receiving its handshake response.
+    pub cdc_jni_startup_latency: HistogramVec,
+    /// Number of CDC connector JNI calls (source thread startup or the source thread itself)
+    /// that returned an error.
+    pub cdc_jni_call_error_count: GenericCounterVec<AtomicU64>,
 }
 
 pub static GLOBAL_SOURCE_METRICS: LazyLock<SourceMetrics> =
@@ -126,6 +133,22 @@ impl SourceMetrics {
         let direct_cdc_event_lag_latency =
             register_histogram_vec_with_registry!(opts, &["table_name"], registry).unwrap();
 
+        let opts = histogram_opts!(
+            "source_cdc_jni_startup_duration_milliseconds",
+            "Time spent waiting for the CDC connector's JNI-backed source thread to start up",
+            exponential_buckets(1.0, 2.0, 21).unwrap(), // max 1048s
+        );
+        let cdc_jni_startup_latency =
+            register_histogram_vec_with_registry!(opts, &["source_id"], registry).unwrap();
+
+        let cdc_jni_call_error_count = register_int_counter_vec_with_registry!(
+            "source_cdc_jni_call_error_count",
+            "Number of CDC connector JNI calls that returned an error",
+            &["source_id"],
+            registry
+        )
+        .unwrap();
+
         let rdkafka_native_metric = Arc::new(RdKafkaStats::new(registry.clone()));
         SourceMetrics {
             partition_input_count,
@@ -134,6 +157,8 @@ impl SourceMetrics {
             rdkafka_native_metric,
             connector_source_rows_received,
             direct_cdc_event_lag_latency,
+            cdc_jni_startup_latency,
+            cdc_jni_call_error_count,
         }
     }
 }
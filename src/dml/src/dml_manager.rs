@@ -15,8 +15,12 @@
 use std::cmp::Ordering;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::num::NonZeroU32;
 use std::sync::{Arc, Weak};
 
+use governor::clock::MonotonicClock;
+use governor::state::{InMemoryState, NotKeyed};
+use governor::{Quota, RateLimiter};
 use parking_lot::RwLock;
 use risingwave_common::catalog::{ColumnDesc, TableId, TableVersionId};
 use risingwave_common::transaction::transaction_id::{TxnId, TxnIdGenerator};
@@ -27,6 +31,9 @@ use crate::{TableDmlHandle, TableDmlHandleRef};
 
 pub type DmlManagerRef = Arc<DmlManager>;
 
+/// Rate limiter for a table's batch DML data. See [`DmlManager::rate_limiter_for_table`].
+pub type DmlRateLimiter = RateLimiter<NotKeyed, InMemoryState, MonotonicClock>;
+
 #[derive(Debug)]
 pub struct TableReader {
     version_id: TableVersionId,
@@ -43,6 +50,12 @@ pub struct DmlManager {
     pub table_readers: RwLock<HashMap<TableId, TableReader>>,
     txn_id_generator: TxnIdGenerator,
     dml_channel_initial_permits: usize,
+    /// Per-table rate limiters for batch DML data, keyed by `table_id`. Kept here rather than on
+    /// `DmlExecutor` itself, because `DmlManager` (unlike the executor) is not rebuilt across a
+    /// recovery, so the token-bucket budget carries over instead of resetting and allowing a
+    /// burst right after the executor comes back up. Pruned alongside `table_readers` in
+    /// [`Self::register_reader`], so a table's limiter doesn't outlive every reader of it forever.
+    rate_limiters: RwLock<HashMap<TableId, Arc<DmlRateLimiter>>>,
 }
 
 impl DmlManager {
@@ -51,6 +64,7 @@ impl DmlManager {
             table_readers: RwLock::new(HashMap::new()),
             txn_id_generator: TxnIdGenerator::new(worker_node_id),
             dml_channel_initial_permits,
+            rate_limiters: RwLock::new(HashMap::new()),
         }
     }
 
@@ -118,6 +132,12 @@ impl DmlManager {
             }
         };
 
+        // Also drop the rate limiter of any table that no longer has a reader. `table_id` was
+        // just (re-)inserted into `table_readers` above, so this never prunes it.
+        self.rate_limiters
+            .write()
+            .retain(|table_id, _| table_readers.contains_key(table_id));
+
         Ok(handle)
     }
 
@@ -156,8 +176,28 @@ impl DmlManager {
         Ok(table_dml_handle)
     }
 
+    /// Returns the shared rate limiter for `table_id`'s batch DML data, creating one with the
+    /// given `rate_limit` if none exists yet. Since this limiter lives on `DmlManager` rather
+    /// than on the `DmlExecutor` that consumes it, a `DmlExecutor` rebuilt across a recovery
+    /// reuses the same limiter instead of starting with a freshly-refilled budget.
+    pub fn rate_limiter_for_table(
+        &self,
+        table_id: TableId,
+        rate_limit: NonZeroU32,
+    ) -> Arc<DmlRateLimiter> {
+        self.rate_limiters
+            .write()
+            .entry(table_id)
+            .or_insert_with(|| {
+                let quota = Quota::per_second(rate_limit);
+                Arc::new(RateLimiter::direct_with_clock(quota, &MonotonicClock))
+            })
+            .clone()
+    }
+
     pub fn clear(&self) {
-        self.table_readers.write().clear()
+        self.table_readers.write().clear();
+        self.rate_limiters.write().clear();
     }
 
     pub fn gen_txn_id(&self) -> TxnId {
@@ -307,4 +347,28 @@ mod tests {
             .register_reader(table_id, table_version_id, &other_column_descs)
             .unwrap();
     }
+
+    #[test]
+    fn test_rate_limiter_pruned_with_dropped_table() {
+        let dml_manager = DmlManager::for_test();
+        let table_id = TableId::new(1);
+        let table_version_id = INITIAL_TABLE_VERSION_ID;
+        let column_descs = vec![ColumnDesc::unnamed(100.into(), DataType::Float64)];
+
+        let h = dml_manager
+            .register_reader(table_id, table_version_id, &column_descs)
+            .unwrap();
+        let _ = dml_manager.rate_limiter_for_table(table_id, NonZeroU32::new(1).unwrap());
+        assert_eq!(dml_manager.rate_limiters.read().len(), 1);
+
+        // Drop the only reader, simulating the table being dropped, then register a reader for an
+        // unrelated table -- that's the only hook available to prune stale entries.
+        drop(h);
+        let other_table_id = TableId::new(2);
+        dml_manager
+            .register_reader(other_table_id, table_version_id, &column_descs)
+            .unwrap();
+
+        assert!(dml_manager.rate_limiters.read().is_empty());
+    }
 }
@@ -313,7 +313,7 @@ pub mod agg_kinds {
     #[macro_export]
     macro_rules! unimplemented_in_stream {
         () => {
-            AggKind::PercentileCont | AggKind::PercentileDisc | AggKind::Mode
+            AggKind::PercentileDisc
         };
     }
     pub use unimplemented_in_stream;
@@ -393,9 +393,14 @@ pub mod agg_kinds {
     #[macro_export]
     macro_rules! simply_cannot_two_phase {
         () => {
+            // FIXME: `StringAgg`/`JsonbAgg` could in principle support a two-phase plan when
+            // there's no `ORDER BY` too (each partial phase's result only needs concatenating, not
+            // re-ordering), unlike `ArrayAgg` just below -- but `partial_to_total_agg_call` only
+            // forwards the partial output as the total call's sole input, dropping `StringAgg`'s
+            // delimiter argument, which the total phase also needs. Move these out once
+            // `partial_to_total_agg_call` can carry extra arguments through to the total phase.
             AggKind::StringAgg
                 | AggKind::ApproxCountDistinct
-                | AggKind::ArrayAgg
                 | AggKind::JsonbAgg
                 | AggKind::JsonbObjectAgg
                 | AggKind::FirstValue
@@ -415,6 +420,13 @@ pub mod agg_kinds {
 
     /// [`AggKind`](crate::aggregate::AggKind)s that are implemented with a single value state (so-called
     /// stateless).
+    ///
+    /// `BitAnd`/`BitOr`/`BoolAnd`/`BoolOr` belong here even though they need retraction support:
+    /// unlike `Min`/`Max` (which need a `MaterializedInput` table to know the new extremum after
+    /// the current one is deleted), their updatable implementations retract by keeping a bounded
+    /// per-bit "number of unset bits" counter (see `BitAndUpdatable`/`BoolAndUpdatable` in
+    /// `risingwave_expr_impl::aggregate`), so a single encodable value is always enough state --
+    /// no per-input-row table is needed.
     #[macro_export]
     macro_rules! single_value_state {
         () => {
@@ -462,6 +474,11 @@ impl AggKind {
             | AggKind::Sum
             | AggKind::InternalLastSeenValue => Some(self),
             AggKind::Sum0 | AggKind::Count => Some(AggKind::Sum0),
+            // The total phase re-aggregates with the same `AggKind`, but resolves to the
+            // `array_agg(anyarray) -> anyarray` internal overload (see `array_agg_concat` in
+            // `risingwave_expr_impl::aggregate::array_agg`) since its input is now the partial
+            // phase's array output rather than a scalar -- this flattens instead of nesting.
+            AggKind::ArrayAgg => Some(self),
             agg_kinds::simply_cannot_two_phase!() => None,
             agg_kinds::rewritten!() => None,
         }
@@ -17,6 +17,7 @@ use std::fmt::Debug;
 use std::hash::{Hash, Hasher};
 use std::ops::Range;
 
+use itertools::Itertools;
 use risingwave_common::array::{Op, StreamChunk};
 use risingwave_common::bail;
 use risingwave_common::estimate_size::EstimateSize;
@@ -98,19 +99,44 @@ impl AggregateFunction for UpdatableApproxCountDistinct {
 
     fn encode_state(&self, state: &AggregateState) -> Result<Datum> {
         let state = state.downcast_ref::<UpdatableRegisters>();
-        // FIXME: store state of updatable registers properly
-        Ok(Some(ScalarImpl::Int64(state.calculate_result())))
+        // Persist every register's full per-count-value histogram, not just the harmonic-mean
+        // estimate derived from it, so a retraction after recovery still decrements the exact
+        // register it was originally counted into. Registers are sparse in practice (most buckets
+        // see at most a couple of distinct trailing-zero counts), so we only serialize non-empty
+        // `(register_idx, count_value, occurrences)` triples.
+        let mut triples = Vec::new();
+        for (register_idx, bucket) in state.registers.iter().enumerate() {
+            for (count_value, occurrences) in bucket.entries() {
+                triples.push(register_idx as i64);
+                triples.push(count_value as i64);
+                triples.push(occurrences as i64);
+            }
+        }
+        Ok(Some(ScalarImpl::List(ListValue::from_iter(triples))))
     }
 
     fn decode_state(&self, datum: Datum) -> Result<AggregateState> {
-        // FIXME: restore state of updatable registers properly
-        let Some(ScalarImpl::Int64(initial_count)) = datum else {
-            return Err(ExprError::InvalidState("expect int8".into()));
+        let Some(ScalarImpl::List(list)) = datum else {
+            return Err(ExprError::InvalidState("expect int8[]".into()));
         };
-        Ok(AggregateState::Any(Box::new(UpdatableRegisters {
-            initial_count,
-            ..UpdatableRegisters::default()
-        })))
+        let mut by_register: std::collections::HashMap<usize, Vec<(u8, u64)>> =
+            std::collections::HashMap::new();
+        let values = list.iter().flatten().map(|d| d.into_int64()).collect_vec();
+        for triple in values.chunks_exact(3) {
+            let [register_idx, count_value, occurrences] = *triple else {
+                unreachable!("chunks_exact(3) always yields slices of length 3")
+            };
+            by_register
+                .entry(register_idx as usize)
+                .or_default()
+                .push((count_value as u8, occurrences as u64));
+        }
+
+        let mut registers = UpdatableRegisters::default();
+        for (register_idx, entries) in by_register {
+            registers.registers[register_idx] = UpdatableBucket::from_entries(entries);
+        }
+        Ok(AggregateState::Any(Box::new(registers)))
     }
 }
 
@@ -215,8 +241,6 @@ impl AggregateFunction for AppendOnlyApproxCountDistinct {
 #[derive(Debug, Clone)]
 struct Registers<B: Bucket> {
     registers: Box<[B]>,
-    // FIXME: Currently we only store the count result (i64) as the state of updatable register.
-    // This is not correct, because the state should be the registers themselves.
     initial_count: i64,
 }
 
@@ -332,7 +356,7 @@ fn pos_in_serialized(bucket_idx: usize) -> (usize, usize, u32) {
 mod tests {
     use futures_util::FutureExt;
     use risingwave_common::array::{Array, DataChunk, I32Array, StreamChunk};
-    use risingwave_expr::aggregate::{build_append_only, AggCall};
+    use risingwave_expr::aggregate::{build_append_only, build_retractable, AggCall};
 
     #[test]
     fn test() {
@@ -366,4 +390,44 @@ mod tests {
             }
         }
     }
+
+    /// After a state round-trip (as happens on recovery), a retraction should still land on the
+    /// same register it was originally counted into, so the result matches what it would have
+    /// been without the round-trip.
+    #[test]
+    fn test_updatable_state_roundtrip_preserves_retraction() {
+        let approx_count_distinct =
+            build_retractable(&AggCall::from_pretty("(approx_count_distinct:int8 $0:int4)"))
+                .unwrap();
+
+        let col = I32Array::from_iter(0..1000).into_ref();
+        let input = StreamChunk::from(DataChunk::new(vec![col], 1000));
+        let mut state = approx_count_distinct.create_state();
+        approx_count_distinct
+            .update(&mut state, &input)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+
+        let encoded = approx_count_distinct.encode_state(&state).unwrap();
+        let mut state = approx_count_distinct.decode_state(encoded).unwrap();
+
+        let col = I32Array::from_iter(0..1000).into_ref();
+        let retraction =
+            StreamChunk::new(vec![risingwave_common::array::Op::Delete; 1000], vec![col]);
+        approx_count_distinct
+            .update(&mut state, &retraction)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+
+        let count = approx_count_distinct
+            .get_result(&state)
+            .now_or_never()
+            .unwrap()
+            .unwrap()
+            .unwrap()
+            .into_int64();
+        assert_eq!(count, 0);
+    }
 }
@@ -26,6 +26,13 @@ impl SparseCount {
         }
     }
 
+    /// The `(key, count)` pairs currently tracked, in ascending key order. Used by
+    /// [`UpdatableBucket::entries`] to persist the full per-register histogram, rather than just
+    /// the register's current max.
+    fn entries(&self) -> &[(u8, u64)] {
+        &self.inner
+    }
+
     fn get(&self, k: u8) -> u64 {
         for (key, count) in &self.inner {
             if *key == k {
@@ -105,6 +112,31 @@ impl<const DENSE_BITS: usize> UpdatableBucket<DENSE_BITS> {
             Ok(self.dense_counts[index as usize - 1])
         }
     }
+
+    /// All `(count_value, occurrences)` pairs with a non-zero `occurrences`, covering both the
+    /// dense and sparse halves of this register's histogram. Used to persist the full register
+    /// state (see [`Self::from_entries`]), rather than just [`Self::max`].
+    pub(super) fn entries(&self) -> impl Iterator<Item = (u8, u64)> + '_ {
+        self.dense_counts
+            .iter()
+            .enumerate()
+            .filter(|(_, &count)| count > 0)
+            .map(|(i, &count)| ((i + 1) as u8, count))
+            .chain(self.sparse_counts.entries().iter().copied())
+    }
+
+    /// Rebuilds a register from the `(count_value, occurrences)` pairs produced by [`Self::entries`].
+    pub(super) fn from_entries(entries: impl IntoIterator<Item = (u8, u64)>) -> Self {
+        let mut bucket = Self::default();
+        for (count_value, occurrences) in entries {
+            if count_value as usize <= DENSE_BITS {
+                bucket.dense_counts[count_value as usize - 1] = occurrences;
+            } else {
+                bucket.sparse_counts.inner.push((count_value, occurrences));
+            }
+        }
+        bucket
+    }
 }
 
 impl<const DENSE_BITS: usize> Default for UpdatableBucket<DENSE_BITS> {
@@ -27,6 +27,23 @@ fn array_agg(state: &mut ArrayAggState, value: Option<ScalarRefImpl<'_>>, ctx: &
         .append(value);
 }
 
+/// Concatenates per-shard `array_agg` results back into one array, used internally for the total
+/// phase of a two-phase `array_agg` (see `PlanAggCall::partial_to_total_agg_call`): unlike the
+/// user-facing overload above, the incoming value is itself already an array, so it's flattened
+/// element-by-element into the result instead of appended as a single nested element.
+#[aggregate("array_agg(anyarray) -> anyarray", internal)]
+fn array_agg_concat(state: &mut ArrayAggState, value: Option<ScalarRefImpl<'_>>, ctx: &Context) {
+    let Some(ScalarRefImpl::List(sublist)) = value else {
+        return;
+    };
+    let builder = state
+        .0
+        .get_or_insert_with(|| ctx.arg_types[0].as_list().create_array_builder(1));
+    for elem in sublist.iter() {
+        builder.append(elem);
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 struct ArrayAggState(Option<ArrayBuilderImpl>);
 
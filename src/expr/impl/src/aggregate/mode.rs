@@ -64,6 +64,12 @@ fn build(agg: &AggCall) -> Result<BoxedAggregateFunction> {
 /// select mode() within group (order by unnest) from unnest(array[]::int[]);
 /// ----
 /// NULL
+///
+/// -- Ties break toward the smallest value: 2 and 4 are both the most frequent (twice each).
+/// query I
+/// select mode() within group (order by unnest) from unnest(array[2,2,3,4,4]);
+/// ----
+/// 2
 /// ```
 struct Mode {
     return_type: DataType,
@@ -80,6 +86,14 @@ struct State {
 impl AggStateDyn for State {}
 
 impl State {
+    /// Folds in the next datum, assuming rows arrive in ascending order of the aggregated
+    /// argument (guaranteed by `mode`'s mandatory `WITHIN GROUP (ORDER BY ...)`, which the
+    /// planner enforces by sorting the input ahead of this aggregate).
+    ///
+    /// Ties are broken deterministically in favor of the smallest value: this relies on both the
+    /// ascending input order above and the strict `>` below (not `>=`) so that once a value
+    /// reaches the current mode's frequency, a later, larger value with the same frequency does
+    /// not overwrite it.
     fn add_datum(&mut self, datum_ref: DatumRef<'_>) {
         let datum = datum_ref.to_owned_datum();
         if datum.is_some() && self.cur_item == datum {
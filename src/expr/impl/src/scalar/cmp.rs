@@ -20,6 +20,13 @@ use risingwave_common::row::Row;
 use risingwave_common::types::{Scalar, ScalarRef, ScalarRefImpl};
 use risingwave_expr::function;
 
+// Note: `general_eq`/`general_ne`/`general_ge`/`general_gt`/`general_le`/`general_lt` below are
+// already registered for `date`, `time`, `timestamp` and `timestamptz` (see the `#[function(...)]`
+// attributes), so temporal comparisons already go through this vectorized framework using each
+// type's natural `Ord` impl. In particular `Timestamptz` stores a UTC microsecond instant
+// (see `risingwave_common::types::Timestamptz`), so its ordering is timezone-independent: two
+// literals with the same instant but different textual offsets compare equal.
+
 #[function("equal(boolean, boolean) -> boolean", batch_fn = "boolarray_eq")]
 #[function("equal(*int, *int) -> boolean")]
 #[function("equal(decimal, decimal) -> boolean")]
@@ -480,6 +487,35 @@ mod tests {
         Decimal::from_str(s).unwrap()
     }
 
+    #[test]
+    fn test_timestamptz_comparison_across_timezones() {
+        use risingwave_common::types::Timestamptz;
+
+        // Same instant, expressed in different timezones, must compare equal.
+        let utc = Timestamptz::from_str("2022-10-01 08:00:00+00:00").unwrap();
+        let plus8 = Timestamptz::from_str("2022-10-01 16:00:00+08:00").unwrap();
+        let minus5 = Timestamptz::from_str("2022-10-01 03:00:00-05:00").unwrap();
+        assert!(general_eq::<Timestamptz, Timestamptz, Timestamptz>(utc, plus8));
+        assert!(general_eq::<Timestamptz, Timestamptz, Timestamptz>(utc, minus5));
+        assert!(!general_ne::<Timestamptz, Timestamptz, Timestamptz>(
+            utc, plus8
+        ));
+
+        // An instant an hour later, even if its textual offset makes the clock time look
+        // earlier, must still compare greater.
+        let later = Timestamptz::from_str("2022-10-01 09:00:00+00:00").unwrap();
+        let later_plus8 = Timestamptz::from_str("2022-10-01 08:00:00+08:00").unwrap();
+        assert!(general_gt::<Timestamptz, Timestamptz, Timestamptz>(
+            later, plus8
+        ));
+        assert!(general_lt::<Timestamptz, Timestamptz, Timestamptz>(
+            plus8, later
+        ));
+        assert!(general_lt::<Timestamptz, Timestamptz, Timestamptz>(
+            later_plus8, later
+        ));
+    }
+
     #[tokio::test]
     async fn test_is_distinct_from() {
         let (input, target) = DataChunk::from_pretty(
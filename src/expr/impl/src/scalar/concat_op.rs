@@ -24,6 +24,13 @@ pub fn concat_op(left: &str, right: &str, writer: &mut impl Write) {
 
 #[cfg(test)]
 mod tests {
+    use risingwave_common::array::DataChunk;
+    use risingwave_common::row::Row;
+    use risingwave_common::test_prelude::DataChunkTestExt;
+    use risingwave_common::types::ToOwnedDatum;
+    use risingwave_common::util::iter_util::ZipEqDebug;
+    use risingwave_expr::expr::build_from_pretty;
+
     use super::*;
 
     #[test]
@@ -32,4 +39,38 @@ mod tests {
         concat_op("114", "514", &mut s);
         assert_eq!(s, "114514")
     }
+
+    #[test]
+    fn test_concat_op_empty_string() {
+        let mut s = String::new();
+        concat_op("", "", &mut s);
+        assert_eq!(s, "");
+
+        let mut s = String::new();
+        concat_op("abc", "", &mut s);
+        assert_eq!(s, "abc");
+    }
+
+    #[tokio::test]
+    async fn test_concat_op_null_propagation() {
+        // Unlike `concat`/`concat_ws`, which skip NULL arguments, `||` follows Postgres semantics:
+        // the result is NULL if either side is NULL.
+        let concat_op = build_from_pretty("(concat_op:varchar $0:varchar $1:varchar)");
+        let (input, expected) = DataChunk::from_pretty(
+            "T T  T
+             a b  ab
+             . b  .
+             a .  .
+             . .  .",
+        )
+        .split_column_at(2);
+
+        let output = concat_op.eval(&input).await.unwrap();
+        assert_eq!(&output, expected.column_at(0));
+
+        for (row, expected) in input.rows().zip_eq_debug(expected.rows()) {
+            let result = concat_op.eval_row(&row.to_owned_row()).await.unwrap();
+            assert_eq!(result, expected.datum_at(0).to_owned_datum());
+        }
+    }
 }
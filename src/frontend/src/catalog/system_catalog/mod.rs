@@ -358,8 +358,15 @@ impl SysCatalogReader for SysCatalogReaderImpl {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
+    use risingwave_pb::user::grant_privilege::{ActionWithGrantOption, PbAction};
+    use risingwave_pb::user::PbGrantPrivilege;
+
+    use super::*;
     use crate::catalog::system_catalog::SYS_CATALOGS;
     use crate::test_utils::LocalFrontend;
+    use crate::user::user_catalog::UserCatalog;
 
     #[tokio::test]
     async fn test_builtin_view_definition() {
@@ -372,4 +379,29 @@ mod tests {
             frontend.query_formatted_result(sql).await;
         }
     }
+
+    #[test]
+    fn test_get_acl_items_function_execute_grant() {
+        let function_id = 42;
+        let object = Object::FunctionId(function_id);
+        let user = UserCatalog::from(risingwave_pb::user::PbUserInfo {
+            id: 2,
+            name: "bob".to_owned(),
+            grant_privileges: vec![PbGrantPrivilege {
+                object: Some(object.clone()),
+                action_with_opts: vec![ActionWithGrantOption {
+                    action: PbAction::Execute as i32,
+                    with_grant_option: false,
+                    granted_by: 1,
+                }],
+            }],
+            ..Default::default()
+        });
+        let mut username_map = HashMap::new();
+        username_map.insert(1, "root".to_owned());
+        username_map.insert(2, "bob".to_owned());
+
+        let acl = get_acl_items(&object, false, &vec![user], &username_map);
+        assert_eq!(acl, "{bob=X/root}");
+    }
 }
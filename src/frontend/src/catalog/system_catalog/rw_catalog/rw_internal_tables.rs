@@ -32,6 +32,12 @@ struct RwInternalTable {
     created_at: Option<Timestamptz>,
     initialized_at_cluster_version: Option<String>,
     created_at_cluster_version: Option<String>,
+    /// Which kind of streaming aggregate state this table backs, e.g. `materialized_input`
+    /// (see `AggCallState::kind_name`). `NULL` for internal tables that aren't a per-call
+    /// aggregate state table, including an agg's own combined intermediate/result state table.
+    agg_call_state_kind: Option<String>,
+    /// See `generic::Agg::with_group_key_point_lookup`.
+    read_optimized_for_point_lookup: bool,
 }
 
 #[system_catalog(table, "rw_catalog.rw_internal_tables")]
@@ -60,6 +66,8 @@ fn read_rw_internal_tables(reader: &SysCatalogReaderImpl) -> Result<Vec<RwIntern
                 created_at: table.created_at_epoch.map(|e| e.as_timestamptz()),
                 initialized_at_cluster_version: table.initialized_at_cluster_version.clone(),
                 created_at_cluster_version: table.created_at_cluster_version.clone(),
+                agg_call_state_kind: table.agg_call_state_kind.clone(),
+                read_optimized_for_point_lookup: table.read_optimized_for_point_lookup,
             })
         })
         .collect())
@@ -161,6 +161,18 @@ pub struct TableCatalog {
     pub created_at_cluster_version: Option<String>,
 
     pub initialized_at_cluster_version: Option<String>,
+
+    /// For an internal table backing a single streaming aggregate call's state, names which
+    /// kind of state it holds (see `AggCallState::kind_name`). `None` for every other table,
+    /// including the agg's own combined intermediate/result state table. Surfaced to users via
+    /// `rw_catalog.rw_internal_tables` for capacity planning.
+    pub agg_call_state_kind: Option<String>,
+
+    /// Set by `generic::Agg::with_group_key_point_lookup` on an aggregate's result table: records
+    /// that a caller intentionally relies on point lookups by group key being a single prefix
+    /// read. Doesn't change the table's physical layout, which already guarantees this for every
+    /// agg result table regardless of the flag.
+    pub read_optimized_for_point_lookup: bool,
 }
 
 // How the stream job was created will determine
@@ -449,6 +461,8 @@ impl TableCatalog {
             created_at_cluster_version: self.created_at_cluster_version.clone(),
             initialized_at_cluster_version: self.initialized_at_cluster_version.clone(),
             retention_seconds: self.retention_seconds,
+            agg_call_state_kind: self.agg_call_state_kind.clone(),
+            read_optimized_for_point_lookup: self.read_optimized_for_point_lookup,
         }
     }
 
@@ -575,6 +589,8 @@ impl From<PbTable> for TableCatalog {
             created_at_cluster_version: tb.created_at_cluster_version.clone(),
             initialized_at_cluster_version: tb.initialized_at_cluster_version.clone(),
             retention_seconds: tb.retention_seconds,
+            agg_call_state_kind: tb.agg_call_state_kind.clone(),
+            read_optimized_for_point_lookup: tb.read_optimized_for_point_lookup,
             dependent_relations: tb
                 .dependent_relations
                 .into_iter()
@@ -672,6 +688,8 @@ mod tests {
             incoming_sinks: vec![],
             created_at_cluster_version: None,
             initialized_at_cluster_version: None,
+            agg_call_state_kind: None,
+            read_optimized_for_point_lookup: false,
         }
         .into();
 
@@ -732,6 +750,8 @@ mod tests {
                 created_at_cluster_version: None,
                 initialized_at_cluster_version: None,
                 dependent_relations: vec![],
+                agg_call_state_kind: None,
+                read_optimized_for_point_lookup: false,
             }
         );
         assert_eq!(table, TableCatalog::from(table.to_prost(0, 0)));
@@ -280,7 +280,11 @@ static CONVERT_DISTINCT_AGG_FOR_BATCH: LazyLock<OptimizationStage> = LazyLock::n
 static SIMPLIFY_AGG: LazyLock<OptimizationStage> = LazyLock::new(|| {
     OptimizationStage::new(
         "Simplify Aggregation",
-        vec![AggGroupBySimplifyRule::create(), AggCallMergeRule::create()],
+        vec![
+            AggGroupBySimplifyRule::create(),
+            AggCallMergeRule::create(),
+            SumToCountRule::create(),
+        ],
         ApplyOrder::TopDown,
     )
 });
@@ -307,6 +311,9 @@ static PROJECT_REMOVE: LazyLock<OptimizationStage> = LazyLock::new(|| {
             // eliminate and to values
             ProjectJoinMergeRule::create(),
             AggProjectMergeRule::create(),
+            // depends on `AggProjectMergeRule` having already pushed away a trivial
+            // pass-through projection that used to separate two aggs with the same group key
+            AggAggMergeRule::create(),
         ],
         ApplyOrder::BottomUp,
     )
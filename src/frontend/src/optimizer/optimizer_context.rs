@@ -15,6 +15,7 @@
 use core::convert::Into;
 use core::fmt::Formatter;
 use std::cell::{RefCell, RefMut};
+use std::collections::HashMap;
 use std::rc::Rc;
 use std::sync::Arc;
 
@@ -22,6 +23,7 @@ use risingwave_sqlparser::ast::{ExplainOptions, ExplainType};
 
 use crate::expr::{CorrelatedId, SessionTimezone};
 use crate::handler::HandlerArgs;
+use crate::optimizer::plan_node::generic::{AggCallState, AggStateInferenceKey};
 use crate::optimizer::plan_node::PlanNodeId;
 use crate::session::SessionImpl;
 use crate::utils::{OverwriteOptions, WithOptions};
@@ -55,6 +57,11 @@ pub struct OptimizerContext {
     /// Store the configs can be overwritten in with clause
     /// if not specified, use the value from session variable.
     overwrite_options: OverwriteOptions,
+    /// Caches [`generic::Agg::infer_stream_agg_state`](crate::optimizer::plan_node::generic::Agg::infer_stream_agg_state)
+    /// results keyed by the structural signature of the agg, so that re-inferring the state for
+    /// the same logical agg across alternative candidate plans reuses the previous result
+    /// instead of redoing the table-building work.
+    agg_state_inference_cache: RefCell<HashMap<AggStateInferenceKey, Vec<AggCallState>>>,
 }
 
 pub type OptimizerContextRef = Rc<OptimizerContext>;
@@ -86,6 +93,7 @@ impl OptimizerContext {
             next_expr_display_id: RefCell::new(RESERVED_ID_NUM.into()),
             total_rule_applied: RefCell::new(0),
             overwrite_options,
+            agg_state_inference_cache: RefCell::new(HashMap::new()),
         }
     }
 
@@ -107,6 +115,7 @@ impl OptimizerContext {
             next_expr_display_id: RefCell::new(0),
             total_rule_applied: RefCell::new(0),
             overwrite_options: OverwriteOptions::default(),
+            agg_state_inference_cache: RefCell::new(HashMap::new()),
         }
         .into()
     }
@@ -223,6 +232,25 @@ impl OptimizerContext {
     pub fn get_session_timezone(&self) -> String {
         self.session_timezone.borrow().timezone()
     }
+
+    /// Looks up a previously cached [`generic::Agg::infer_stream_agg_state`](crate::optimizer::plan_node::generic::Agg::infer_stream_agg_state)
+    /// result for an agg with the given structural signature, if one was cached by
+    /// [`Self::cache_agg_state_inference`] earlier in the optimization of this query.
+    pub fn get_cached_agg_state_inference(
+        &self,
+        key: &AggStateInferenceKey,
+    ) -> Option<Vec<AggCallState>> {
+        self.agg_state_inference_cache.borrow().get(key).cloned()
+    }
+
+    /// Caches an [`generic::Agg::infer_stream_agg_state`](crate::optimizer::plan_node::generic::Agg::infer_stream_agg_state)
+    /// result under the agg's structural signature, for [`Self::get_cached_agg_state_inference`]
+    /// to reuse if the optimizer infers state for a structurally identical agg again.
+    pub fn cache_agg_state_inference(&self, key: AggStateInferenceKey, state: Vec<AggCallState>) {
+        self.agg_state_inference_cache
+            .borrow_mut()
+            .insert(key, state);
+    }
 }
 
 impl std::fmt::Debug for OptimizerContext {
@@ -13,12 +13,14 @@
 // limitations under the License.
 
 use itertools::Itertools;
+use pretty_xmlish::{Pretty, XmlNode};
+use risingwave_common::util::iter_util::ZipEqFast;
 use risingwave_pb::batch_plan::plan_node::NodeBody;
 use risingwave_pb::batch_plan::HashAggNode;
 
 use super::batch::prelude::*;
 use super::generic::{self, GenericPlanRef, PlanAggCall};
-use super::utils::impl_distill_by_unit;
+use super::utils::{childless_record, Distill};
 use super::{
     ExprRewritable, PlanBase, PlanNodeType, PlanRef, PlanTreeNodeUnary, ToBatchPb,
     ToDistributedBatch,
@@ -34,6 +36,14 @@ use crate::utils::{ColIndexMappingRewriteExt, IndexSet};
 pub struct BatchHashAgg {
     pub base: PlanBase<Batch>,
     core: generic::Agg<PlanRef>,
+
+    /// Set by [`ToDistributedBatch::to_distributed`] when it chose a single-phase shuffle agg
+    /// specifically because the input was already distributed the way two-phase agg's own
+    /// shuffle phase would redistribute it to (see
+    /// [`generic::Agg::hash_agg_dist_satisfied_by_input_dist`]), rather than because two-phase
+    /// agg wasn't eligible/forced in the first place. Surfaced in `EXPLAIN` via [`Distill`] so
+    /// it's clear why a forced two-phase agg didn't show up in the plan.
+    two_phase_agg_skipped_due_to_dist: bool,
 }
 
 impl BatchHashAgg {
@@ -45,7 +55,17 @@ impl BatchHashAgg {
             .i2o_col_mapping()
             .rewrite_provided_distribution(input_dist);
         let base = PlanBase::new_batch_with_core(&core, dist, Order::any());
-        BatchHashAgg { base, core }
+        BatchHashAgg {
+            base,
+            core,
+            two_phase_agg_skipped_due_to_dist: false,
+        }
+    }
+
+    /// See [`Self::two_phase_agg_skipped_due_to_dist`].
+    fn with_two_phase_agg_skipped_due_to_dist(mut self) -> Self {
+        self.two_phase_agg_skipped_due_to_dist = true;
+        self
     }
 
     pub fn agg_calls(&self) -> &[PlanAggCall] {
@@ -69,13 +89,14 @@ impl BatchHashAgg {
         .enforce_if_not_satisfies(partial_agg, &Order::any())?;
 
         // insert total agg
+        let (_, partial_output_indices) = self.core.to_partial_agg();
         let total_agg_types = self
             .core
             .agg_calls
             .iter()
-            .enumerate()
-            .map(|(partial_output_idx, agg_call)| {
-                agg_call.partial_to_total_agg_call(partial_output_idx + self.group_key().len())
+            .zip_eq_fast(&partial_output_indices)
+            .map(|(agg_call, &partial_output_idx)| {
+                agg_call.partial_to_total_agg_call(partial_output_idx)
             })
             .collect();
         let total_agg_logical = generic::Agg::new(
@@ -97,7 +118,18 @@ impl BatchHashAgg {
     }
 }
 
-impl_distill_by_unit!(BatchHashAgg, core, "BatchHashAgg");
+impl Distill for BatchHashAgg {
+    fn distill<'a>(&self) -> XmlNode<'a> {
+        let mut vec = self.core.fields_pretty();
+        if self.two_phase_agg_skipped_due_to_dist {
+            vec.push((
+                "two_phase_agg",
+                Pretty::display(&"skipped: input already hash-distributed by group key"),
+            ));
+        }
+        childless_record("BatchHashAgg", vec)
+    }
+}
 
 impl PlanTreeNodeUnary for BatchHashAgg {
     fn input(&self) -> PlanRef {
@@ -117,14 +149,22 @@ impl ToDistributedBatch for BatchHashAgg {
         if self.core.must_try_two_phase_agg() {
             let input = self.input().to_distributed()?;
             let input_dist = input.distribution();
-            if !self.core.hash_agg_dist_satisfied_by_input_dist(input_dist)
-                && matches!(
-                    input_dist,
-                    Distribution::HashShard(_)
-                        | Distribution::UpstreamHashShard(_, _)
-                        | Distribution::SomeShard
-                )
-            {
+            if self.core.hash_agg_dist_satisfied_by_input_dist(input_dist) {
+                // The input is already distributed exactly the way two-phase agg's own shuffle
+                // phase would redistribute it to, so inserting a second phase would just add
+                // exchange and state-table overhead for no benefit: keep a single phase, but
+                // remember why so `EXPLAIN` can show it (see `Distill` above).
+                return Ok(self
+                    .clone_with_input(input)
+                    .with_two_phase_agg_skipped_due_to_dist()
+                    .into());
+            }
+            if matches!(
+                input_dist,
+                Distribution::HashShard(_)
+                    | Distribution::UpstreamHashShard(_, _)
+                    | Distribution::SomeShard
+            ) {
                 return self.to_two_phase_agg(input);
             }
         }
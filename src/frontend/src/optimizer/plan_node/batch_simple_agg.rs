@@ -19,6 +19,8 @@ use super::batch::prelude::*;
 use super::generic::{self, GenericPlanRef, PlanAggCall};
 use super::utils::impl_distill_by_unit;
 use super::{ExprRewritable, PlanBase, PlanRef, PlanTreeNodeUnary, ToBatchPb, ToDistributedBatch};
+use risingwave_common::util::iter_util::ZipEqFast;
+
 use crate::error::Result;
 use crate::expr::{ExprRewriter, ExprVisitor};
 use crate::optimizer::plan_node::expr_visitable::ExprVisitable;
@@ -87,12 +89,13 @@ impl ToDistributedBatch for BatchSimpleAgg {
                 BatchExchange::new(partial_agg, Order::any(), Distribution::Single).into();
 
             // insert total agg
+            let (_, partial_output_indices) = self.core.to_partial_agg();
             let total_agg_types = self
                 .core
                 .agg_calls
                 .iter()
-                .enumerate()
-                .map(|(partial_output_idx, agg_call)| {
+                .zip_eq_fast(&partial_output_indices)
+                .map(|(agg_call, &partial_output_idx)| {
                     agg_call.partial_to_total_agg_call(partial_output_idx)
                 })
                 .collect();
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet};
 use std::{fmt, vec};
 
 use fixedbitset::FixedBitSet;
@@ -54,6 +54,26 @@ pub struct Agg<PlanRef> {
     pub grouping_sets: Vec<IndexSet>,
     pub input: PlanRef,
     pub enable_two_phase: bool,
+
+    /// Opt-in hint, off by default, for queries that are known to point-query this agg's result
+    /// by its group key (e.g. a high-cardinality `GROUP BY` key later filtered with `WHERE key =
+    /// ?`). See [`Self::with_group_key_point_lookup`].
+    group_key_point_lookup: bool,
+}
+
+/// Ordered-set aggregates (`mode`, `percentile_cont`, `percentile_disc`, ...) and the other kinds
+/// in [`agg_kinds::must_have_order_by`] only make sense with a `WITHIN GROUP (ORDER BY ...)`
+/// clause. The SQL binder (`LogicalAggBuilder::try_rewrite_agg_call`) already rejects such calls
+/// coming from user queries, but optimizer rules build [`PlanAggCall`]s directly without going
+/// through the binder, so this re-asserts the invariant for every [`Agg`] the planner constructs.
+fn debug_assert_order_by_present(agg_calls: &[PlanAggCall]) {
+    for call in agg_calls {
+        debug_assert!(
+            !matches!(call.agg_kind, agg_kinds::must_have_order_by!()) || !call.order_by.is_empty(),
+            "agg call {} requires a non-empty ORDER BY, but got none",
+            call.agg_kind
+        );
+    }
 }
 
 impl<PlanRef: GenericPlanRef> Agg<PlanRef> {
@@ -73,6 +93,14 @@ impl<PlanRef: GenericPlanRef> Agg<PlanRef> {
         self.group_key.len() + self.agg_calls.len()
     }
 
+    /// Whether this is a global aggregate (no `GROUP BY`), which is guaranteed to produce exactly
+    /// one output row. In this case [`Self::stream_key`]'s empty pk is not "no primary key", but a
+    /// pk of arity zero over a single-row relation: the empty set of columns trivially and
+    /// uniquely identifies that one row.
+    pub fn is_singleton(&self) -> bool {
+        self.group_key.is_empty()
+    }
+
     /// get the Mapping of columnIndex from input column index to output column index,if a input
     /// column corresponds more than one out columns, mapping to any one
     pub fn o2i_col_mapping(&self) -> ColIndexMapping {
@@ -119,13 +147,60 @@ impl<PlanRef: GenericPlanRef> Agg<PlanRef> {
         self.two_phase_agg_forced() && self.can_two_phase_agg()
     }
 
+    /// Reports the reason each agg call is disqualifying two-phase agg, mirroring the checks in
+    /// [`Self::can_two_phase_agg`]. Returns an empty vec when two-phase agg is disabled globally,
+    /// there are no agg calls, or every call qualifies.
+    pub(crate) fn two_phase_agg_disable_reasons(&self) -> Vec<TwoPhaseAggDisableReason> {
+        if !self.two_phase_agg_enabled() || self.agg_calls.is_empty() {
+            return vec![];
+        }
+        self.agg_calls
+            .iter()
+            .enumerate()
+            .filter_map(|(call_index, call)| {
+                if matches!(call.agg_kind, agg_kinds::simply_cannot_two_phase!()) {
+                    return Some(TwoPhaseAggDisableReason {
+                        call_index,
+                        agg_kind: call.agg_kind,
+                        cause: TwoPhaseAggDisableCause::UnsupportedAggKind,
+                    });
+                }
+                let order_ok = matches!(call.agg_kind, agg_kinds::result_unaffected_by_order_by!())
+                    || call.order_by.is_empty();
+                if !order_ok {
+                    return Some(TwoPhaseAggDisableReason {
+                        call_index,
+                        agg_kind: call.agg_kind,
+                        cause: TwoPhaseAggDisableCause::OrderSensitive,
+                    });
+                }
+                let distinct_ok =
+                    matches!(call.agg_kind, agg_kinds::result_unaffected_by_distinct!())
+                        || !call.distinct;
+                if !distinct_ok {
+                    return Some(TwoPhaseAggDisableReason {
+                        call_index,
+                        agg_kind: call.agg_kind,
+                        cause: TwoPhaseAggDisableCause::DistinctSensitive,
+                    });
+                }
+                None
+            })
+            .collect()
+    }
+
+    /// The distribution the input should be shuffled to for a two-phase hash agg: hash-shard by
+    /// the group key. This is the one canonical place that computation is made, so the plan
+    /// builder and [`Self::hash_agg_dist_satisfied_by_input_dist`] can't disagree on it.
+    pub fn two_phase_shuffle_dist(&self) -> RequiredDist {
+        RequiredDist::shard_by_key(self.input.schema().len(), &self.group_key.to_vec())
+    }
+
     /// Generally used by two phase hash agg.
     /// If input dist already satisfies hash agg distribution,
     /// it will be more expensive to do two phase agg, should just do shuffle agg.
     pub(crate) fn hash_agg_dist_satisfied_by_input_dist(&self, input_dist: &Distribution) -> bool {
-        let required_dist =
-            RequiredDist::shard_by_key(self.input.schema().len(), &self.group_key.to_vec());
-        input_dist.satisfies(&required_dist)
+        input_dist.satisfies(&self.two_phase_shuffle_dist())
     }
 
     /// See if all stream aggregation calls have a stateless local agg counterpart.
@@ -143,7 +218,29 @@ impl<PlanRef: GenericPlanRef> Agg<PlanRef> {
             .collect()
     }
 
+    /// Whether every group key column is covered by `input_watermark_columns`, i.e. the input is
+    /// already "sorted" on the group key in the streaming sense: each group key column's value is
+    /// non-decreasing as the input is consumed. This is the streaming analogue of
+    /// [`Agg::input_provides_order_on_group_keys`](Agg::input_provides_order_on_group_keys), which
+    /// reads a batch plan's [`Order`](crate::optimizer::property::Order) — streaming plans carry
+    /// no such total order, only per-column watermark progress.
+    ///
+    /// A hypothetical sort-based streaming agg executor (lower memory than today's
+    /// hash-table-backed `StreamHashAgg`, analogous to `BatchSortAgg` on the batch side) could use
+    /// this to avoid retaining a full hash table of group states; no such executor exists yet in
+    /// this codebase, and nothing calls this today -- not even `explain_state_tables`, which
+    /// describes state *tables*, not watermark coverage. It's kept as a documented building block
+    /// for whichever of the two lands first.
+    pub(crate) fn input_watermark_covers_group_key(
+        &self,
+        input_watermark_columns: &FixedBitSet,
+    ) -> bool {
+        !self.group_key.is_empty()
+            && self.watermark_group_key(input_watermark_columns).len() == self.group_key.len()
+    }
+
     pub fn new(agg_calls: Vec<PlanAggCall>, group_key: IndexSet, input: PlanRef) -> Self {
+        debug_assert_order_by_present(&agg_calls);
         let enable_two_phase = input.ctx().session_ctx().config().enable_two_phase_agg();
         Self {
             agg_calls,
@@ -151,6 +248,7 @@ impl<PlanRef: GenericPlanRef> Agg<PlanRef> {
             input,
             grouping_sets: vec![],
             enable_two_phase,
+            group_key_point_lookup: false,
         }
     }
 
@@ -163,6 +261,36 @@ impl<PlanRef: GenericPlanRef> Agg<PlanRef> {
         self.enable_two_phase = enable_two_phase;
         self
     }
+
+    /// Marks this agg's result table as intentionally read-optimized for point lookups by group
+    /// key. The pk is already the group key and `value_indices` already packs every agg value
+    /// contiguously right after it (see the comment in
+    /// [`Self::infer_intermediate_state_table`]), so this doesn't change the table's physical
+    /// layout -- it records the caller's *intent* on the catalog (see
+    /// `TableCatalog::read_optimized_for_point_lookup`) so operators inspecting
+    /// `rw_internal_tables` can tell a deliberately point-lookup-shaped result table from one that
+    /// merely happens to have a single-column group key.
+    pub fn with_group_key_point_lookup(mut self, group_key_point_lookup: bool) -> Self {
+        self.group_key_point_lookup = group_key_point_lookup;
+        self
+    }
+
+    /// Ensures the group row count (`count(*)`) is present as an output column, appending it if
+    /// the caller didn't already request one explicitly.
+    ///
+    /// The streaming executors already materialize a `count(*)` agg call internally to detect
+    /// when a group becomes empty (see `find_or_append_row_count`); calling this lets that same
+    /// column be surfaced to the output instead of being appended and then projected away,
+    /// avoiding a duplicate `count(*)` state when the query also wants the group count. Used by
+    /// [`AggAggMergeRule`](crate::optimizer::rule::AggAggMergeRule) to fold an outer `count(*)`
+    /// into the row count an inner, directly-stacked agg already tracks.
+    pub fn with_implicit_count_col(mut self) -> Self {
+        let count_star = PlanAggCall::count_star();
+        if !self.agg_calls.iter().any(|call| call == &count_star) {
+            self.agg_calls.push(count_star);
+        }
+        self
+    }
 }
 
 impl<PlanRef: BatchPlanRef> Agg<PlanRef> {
@@ -177,6 +305,26 @@ impl<PlanRef: BatchPlanRef> Agg<PlanRef> {
         }
         self.group_key == input_order_prefix
     }
+
+    /// Splits `self` into its two-phase decomposition: the partial-phase `Agg` (run per shard,
+    /// before the exchange) and, for each of `self.agg_calls` in order, the column index within
+    /// the partial phase's `[group_key, agg_calls]` output schema that
+    /// [`PlanAggCall::partial_to_total_agg_call`] should read from when building the total-phase
+    /// calls. Centralizes what was duplicated between `BatchSimpleAgg::to_distributed` and
+    /// `BatchHashAgg::to_two_phase_agg`.
+    ///
+    /// The partial phase itself keeps every call as-is: by the time agg calls reach this planner
+    /// layer, aggregates that need decomposing into several state-carrying calls (`avg` into
+    /// `sum`/`count`, `stddev`/`variance` into `sum`/`sum of squares`/`count`, ...) have already
+    /// been rewritten by `LogicalAggBuilder::try_rewrite_agg_call` at the SQL binder layer, so
+    /// every call reaching here either has a well-defined `AggKind::partial_to_total` or is
+    /// excluded from two-phase execution entirely by `Agg::can_two_phase_agg`.
+    pub fn to_partial_agg(&self) -> (Agg<PlanRef>, Vec<usize>) {
+        let partial_output_indices = (0..self.agg_calls.len())
+            .map(|i| self.group_key.len() + i)
+            .collect();
+        (self.clone(), partial_output_indices)
+    }
 }
 
 impl<PlanRef: GenericPlanRef> GenericPlanNode for Agg<PlanRef> {
@@ -198,6 +346,8 @@ impl<PlanRef: GenericPlanRef> GenericPlanNode for Agg<PlanRef> {
     }
 
     fn stream_key(&self) -> Option<Vec<usize>> {
+        // For a global aggregate (empty group key, see `Self::is_singleton`), this is an empty
+        // pk: not "no primary key", but a pk of arity zero over the single output row.
         Some((0..self.group_key.len()).collect())
     }
 
@@ -221,12 +371,74 @@ impl<PlanRef: GenericPlanRef> GenericPlanNode for Agg<PlanRef> {
     }
 }
 
+/// Why a particular agg call keeps [`Agg::can_two_phase_agg`] from returning `true`, as reported
+/// by [`Agg::two_phase_agg_disable_reasons`].
+#[derive(Debug, Clone, Copy)]
+pub struct TwoPhaseAggDisableReason {
+    pub call_index: usize,
+    pub agg_kind: AggKind,
+    pub cause: TwoPhaseAggDisableCause,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TwoPhaseAggDisableCause {
+    /// The agg kind can never be split into a partial/final pair.
+    UnsupportedAggKind,
+    /// The call has an `ORDER BY` and its result depends on input order.
+    OrderSensitive,
+    /// The call is `DISTINCT` and its result depends on seeing each input once.
+    DistinctSensitive,
+}
+
+#[derive(Clone)]
 pub enum AggCallState {
     Value,
     MaterializedInput(Box<MaterializedInputState>),
 }
 
 impl AggCallState {
+    /// Builds a [`AggCallState::Value`] directly, without going through
+    /// [`Agg::infer_stream_agg_state`]. For tests that only care about the protobuf shape of a
+    /// given state kind.
+    #[cfg(test)]
+    pub fn result_value() -> Self {
+        AggCallState::Value
+    }
+
+    /// Builds a [`AggCallState::MaterializedInput`] directly, without going through
+    /// [`Agg::infer_stream_agg_state`]. For tests that only care about the protobuf shape of a
+    /// given state kind.
+    #[cfg(test)]
+    pub fn materialized_input(
+        table: TableCatalog,
+        included_upstream_indices: Vec<usize>,
+        table_value_indices: Vec<usize>,
+        order_columns: Vec<ColumnOrder>,
+    ) -> Self {
+        AggCallState::MaterializedInput(Box::new(MaterializedInputState {
+            table,
+            included_upstream_indices,
+            table_value_indices,
+            order_columns,
+        }))
+    }
+
+    /// A short, stable, user-facing name for this state kind, e.g. for surfacing
+    /// [`Self::infer_stream_agg_state`](Agg::infer_stream_agg_state)'s classification through
+    /// introspection (capacity planning wants to know, per materialized view, whether an
+    /// aggregate keeps a single row of state or a full per-key table).
+    ///
+    /// Note there is currently no third "table" kind despite occasional mentions of one: any
+    /// aggregate that cannot be tracked with a single running value, including ordered-set
+    /// aggregates like `mode` that fold over a materialized, sorted replay of their input, ends
+    /// up in [`AggCallState::MaterializedInput`].
+    pub fn kind_name(&self) -> &'static str {
+        match self {
+            AggCallState::Value => "value",
+            AggCallState::MaterializedInput(_) => "materialized_input",
+        }
+    }
+
     pub fn into_prost(self, state: &mut BuildFragmentGraphState) -> AggCallStatePb {
         AggCallStatePb {
             inner: Some(match self {
@@ -264,6 +476,7 @@ impl AggCallState {
     }
 }
 
+#[derive(Clone)]
 pub struct MaterializedInputState {
     pub table: TableCatalog,
     pub included_upstream_indices: Vec<usize>,
@@ -271,6 +484,55 @@ pub struct MaterializedInputState {
     pub order_columns: Vec<ColumnOrder>,
 }
 
+/// The structural signature [`Agg::infer_stream_agg_state`] is keyed on for reuse via
+/// [`OptimizerContext::get_cached_agg_state_inference`](crate::optimizer::optimizer_context::OptimizerContext::get_cached_agg_state_inference).
+/// Two aggs that agree on group key, agg calls (kind, arguments, `ORDER BY`, `DISTINCT`, filter),
+/// input schema, input stream key, input append-only-ness, input distribution, `vnode_col_idx`,
+/// and `window_col_idx` will always infer the same `AggCallState`s, so it's safe to reuse one's
+/// result for the other. `vnode_col_idx`/`window_col_idx` must be part of the key even though they're not
+/// fields of `Agg` itself: `window_col_idx` reorders the state table pk (see
+/// [`Agg::get_ordered_group_key`]) and `vnode_col_idx` sets the table's vnode column, so two
+/// structurally-identical `Agg`s called with different values for either (e.g. an EOWC candidate
+/// plan vs. a non-EOWC one for the same logical agg) must not collide on the same cache entry.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AggStateInferenceKey {
+    group_key: IndexSet,
+    agg_calls: Vec<PlanAggCall>,
+    input_schema: Schema,
+    input_stream_key: Option<Vec<usize>>,
+    input_append_only: bool,
+    input_dist: Distribution,
+    vnode_col_idx: Option<usize>,
+    window_col_idx: Option<usize>,
+}
+
+/// See [`Agg::explain_state_tables`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AggStateTableExplain {
+    /// What role this internal table plays for the agg, e.g. "intermediate state table".
+    pub kind: &'static str,
+    pub name: String,
+    pub pk_columns: Vec<String>,
+    pub value_indices: Vec<usize>,
+    pub read_prefix_len_hint: usize,
+}
+
+impl AggStateTableExplain {
+    fn from_table(kind: &'static str, table: &TableCatalog) -> Self {
+        Self {
+            kind,
+            name: table.name.clone(),
+            pk_columns: table
+                .pk
+                .iter()
+                .map(|order| table.columns[order.column_index].name().to_owned())
+                .collect(),
+            value_indices: table.value_indices.clone(),
+            read_prefix_len_hint: table.read_prefix_len_hint,
+        }
+    }
+}
+
 impl<PlanRef: stream::StreamPlanRef> Agg<PlanRef> {
     pub fn infer_tables(
         &self,
@@ -280,7 +542,7 @@ impl<PlanRef: stream::StreamPlanRef> Agg<PlanRef> {
     ) -> (
         TableCatalog,
         Vec<AggCallState>,
-        HashMap<usize, TableCatalog>,
+        BTreeMap<usize, TableCatalog>,
     ) {
         (
             self.infer_intermediate_state_table(&me, vnode_col_idx, window_col_idx),
@@ -289,6 +551,40 @@ impl<PlanRef: stream::StreamPlanRef> Agg<PlanRef> {
         )
     }
 
+    /// A structured summary of the pk columns, value indices, and read-prefix-len-hint that
+    /// [`Self::infer_tables`] decided on for each of this agg's internal state tables. Reuses the
+    /// same [`TableCatalog`]s the inference already builds, so this can't drift from what actually
+    /// gets materialized. Intended for `EXPLAIN`-style introspection when debugging state bloat.
+    pub fn explain_state_tables(
+        &self,
+        me: impl stream::StreamPlanRef,
+        vnode_col_idx: Option<usize>,
+        window_col_idx: Option<usize>,
+    ) -> Vec<AggStateTableExplain> {
+        let (intermediate_state_table, agg_states, distinct_dedup_tables) =
+            self.infer_tables(me, vnode_col_idx, window_col_idx);
+
+        let mut tables = vec![AggStateTableExplain::from_table(
+            "intermediate state table",
+            &intermediate_state_table,
+        )];
+        for state in agg_states {
+            if let AggCallState::MaterializedInput(state) = state {
+                tables.push(AggStateTableExplain::from_table(
+                    "materialized input state table",
+                    &state.table,
+                ));
+            }
+        }
+        for table in distinct_dedup_tables.values() {
+            tables.push(AggStateTableExplain::from_table(
+                "distinct dedup table",
+                table,
+            ));
+        }
+        tables
+    }
+
     fn get_ordered_group_key(&self, window_col_idx: Option<usize>) -> Vec<usize> {
         if let Some(window_col_idx) = window_col_idx {
             assert!(self.group_key.contains(window_col_idx));
@@ -343,12 +639,52 @@ impl<PlanRef: stream::StreamPlanRef> Agg<PlanRef> {
         (table_builder, included_upstream_indices, column_mapping)
     }
 
+    /// The structural signature used to cache [`Self::infer_stream_agg_state`] results. See
+    /// [`AggStateInferenceKey`].
+    fn agg_state_inference_key(
+        &self,
+        vnode_col_idx: Option<usize>,
+        window_col_idx: Option<usize>,
+    ) -> AggStateInferenceKey {
+        AggStateInferenceKey {
+            group_key: self.group_key.clone(),
+            agg_calls: self.agg_calls.clone(),
+            input_schema: self.input.schema().clone(),
+            input_stream_key: self.input.stream_key().map(|k| k.to_vec()),
+            input_append_only: self.input.append_only(),
+            input_dist: self.input.distribution().clone(),
+            vnode_col_idx,
+            window_col_idx,
+        }
+    }
+
     /// Infer `AggCallState`s for streaming agg.
+    ///
+    /// The optimizer may call this repeatedly for structurally identical aggs while comparing
+    /// candidate plans (see [`AggStateInferenceKey`]); the result is cached on the
+    /// [`OptimizerContextRef`] so repeated inference for the same logical agg reuses it instead of
+    /// rebuilding the same state tables.
     pub fn infer_stream_agg_state(
         &self,
         me: impl stream::StreamPlanRef,
         vnode_col_idx: Option<usize>,
         window_col_idx: Option<usize>,
+    ) -> Vec<AggCallState> {
+        let key = self.agg_state_inference_key(vnode_col_idx, window_col_idx);
+        let ctx = me.ctx();
+        if let Some(cached) = ctx.get_cached_agg_state_inference(&key) {
+            return cached;
+        }
+        let state = self.infer_stream_agg_state_uncached(me, vnode_col_idx, window_col_idx);
+        ctx.cache_agg_state_inference(key, state.clone());
+        state
+    }
+
+    fn infer_stream_agg_state_uncached(
+        &self,
+        me: impl stream::StreamPlanRef,
+        vnode_col_idx: Option<usize>,
+        window_col_idx: Option<usize>,
     ) -> Vec<AggCallState> {
         let in_fields = self.input.schema().fields().to_vec();
         let in_pks = self.input.stream_key().unwrap().to_vec();
@@ -399,6 +735,12 @@ impl<PlanRef: stream::StreamPlanRef> Agg<PlanRef> {
             // set value indices to reduce ser/de overhead
             let table_value_indices = table_value_indices.into_iter().collect_vec();
             table_builder.set_value_indices(table_value_indices.clone());
+            // The state table is a verbatim copy of rows from `self.input`, so it's append-only
+            // (and storage can skip tombstone/retraction bookkeeping) iff the input is.
+            table_builder.set_append_only(in_append_only);
+            // Kept in sync with `AggCallState::kind_name`'s `MaterializedInput` arm by
+            // `test_agg_call_state_kind_name_matches_table_tag` below.
+            table_builder.set_agg_call_state_kind("materialized_input");
 
             MaterializedInputState {
                 table: table_builder.build(tb_dist.unwrap_or_default(), read_prefix_len_hint),
@@ -422,7 +764,9 @@ impl<PlanRef: stream::StreamPlanRef> Agg<PlanRef> {
                 | AggKind::StringAgg
                 | AggKind::ArrayAgg
                 | AggKind::JsonbAgg
-                | AggKind::JsonbObjectAgg => {
+                | AggKind::JsonbObjectAgg
+                | AggKind::PercentileCont
+                | AggKind::Mode => {
                     // columns with order requirement in state table
                     let sort_keys = {
                         match agg_call.agg_kind {
@@ -463,6 +807,17 @@ impl<PlanRef: stream::StreamPlanRef> Agg<PlanRef> {
                                 .iter()
                                 .map(|o| (o.order_type, o.column_index))
                                 .collect(),
+                            AggKind::PercentileCont | AggKind::Mode => {
+                                // `WITHIN GROUP (ORDER BY ...)` is mandatory for these kinds, so
+                                // `order_by` always has exactly the one column to fold over, in
+                                // the order `get_result` (or, for `Mode`, `State::add_datum`)
+                                // expects it.
+                                agg_call
+                                    .order_by
+                                    .iter()
+                                    .map(|o| (o.order_type, o.column_index))
+                                    .collect()
+                            }
                             _ => unreachable!(),
                         }
                     };
@@ -484,9 +839,9 @@ impl<PlanRef: stream::StreamPlanRef> Agg<PlanRef> {
                         | AggKind::StringAgg
                         | AggKind::ArrayAgg
                         | AggKind::JsonbAgg
-                        | AggKind::JsonbObjectAgg => {
-                            agg_call.inputs.iter().map(|i| i.index).collect()
-                        }
+                        | AggKind::JsonbObjectAgg
+                        | AggKind::PercentileCont
+                        | AggKind::Mode => agg_call.inputs.iter().map(|i| i.index).collect(),
                         _ => vec![],
                     };
 
@@ -579,13 +934,29 @@ impl<PlanRef: stream::StreamPlanRef> Agg<PlanRef> {
 
         // the result_table is composed of group_key and all agg_call's values, so the value_indices
         // of this table should skip group_key.len().
+        //
+        // Note this already gives point lookups on the group key a read-optimized layout: the pk
+        // is the group key itself (see `create_table_builder`/`add_order_column` above), and
+        // `value_indices` packs every agg value contiguously right after it, so a lookup by key
+        // is a single prefix read with no secondary index to maintain. This holds unconditionally
+        // -- `Agg::with_group_key_point_lookup` doesn't change it, it only records on the catalog
+        // that a caller is relying on it, for `rw_internal_tables` to surface.
         table_builder.set_value_indices((n_group_key_cols..out_fields.len()).collect());
+        if self.group_key_point_lookup {
+            table_builder.set_read_optimized_for_point_lookup(true);
+        }
+        // For an append-only input, no group ever becomes empty, so the result table never
+        // receives deletes: a group's row is inserted when the group is first seen and afterwards
+        // only overwritten with its new state. Storage can use this hint to skip tombstone/retraction bookkeeping.
+        table_builder.set_append_only(in_append_only);
         table_builder.build(tb_dist, read_prefix_len_hint)
     }
 
     /// Infer dedup tables for distinct agg calls, partitioned by distinct columns.
     /// Since distinct agg calls only dedup on the first argument, the key of the result map is
-    /// `usize`, i.e. the distinct column index.
+    /// `usize`, i.e. the distinct column index. Returns a `BTreeMap` rather than a `HashMap` so
+    /// that the tables are emitted in a deterministic, distinct-column-index order, keeping
+    /// internal-table id assignment and `EXPLAIN` output stable across runs.
     ///
     /// Dedup table schema:
     /// group key | distinct key | count for AGG1(distinct x) | count for AGG2(distinct x) | ...
@@ -594,7 +965,7 @@ impl<PlanRef: stream::StreamPlanRef> Agg<PlanRef> {
         me: impl GenericPlanRef,
         vnode_col_idx: Option<usize>,
         window_col_idx: Option<usize>,
-    ) -> HashMap<usize, TableCatalog> {
+    ) -> BTreeMap<usize, TableCatalog> {
         let in_dist_key = self.input.distribution().dist_column_indices().to_vec();
         let in_fields = self.input.schema().fields();
 
@@ -865,3 +1236,671 @@ impl fmt::Debug for PlanAggCallDisplay<'_> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_agg_call_state_kind_name() {
+        assert_eq!(AggCallState::Value.kind_name(), "value");
+
+        // `min`/`max` classify as `MaterializedInput` on a retractable input and as `Value` on an
+        // append-only one (see the `single_value_state_iff_in_append_only!` arm of
+        // `Agg::infer_stream_agg_state_uncached`) -- this is exercised end-to-end by the streaming `min`/`max` planner tests in
+        // `agg.yaml`, which is why this unit test only checks the naming, not the classification
+        // decision itself.
+        let materialized_input = AggCallState::MaterializedInput(Box::new(MaterializedInputState {
+            table: TableCatalogBuilder::default().build(vec![], 0),
+            included_upstream_indices: vec![0],
+            table_value_indices: vec![0],
+            order_columns: vec![],
+        }));
+        assert_eq!(materialized_input.kind_name(), "materialized_input");
+    }
+
+    #[test]
+    fn test_agg_call_state_into_prost() {
+        let mut state = BuildFragmentGraphState::default();
+
+        let value_state = AggCallState::result_value().into_prost(&mut state);
+        assert!(matches!(
+            value_state.inner,
+            Some(agg_call_state::Inner::ValueState(_))
+        ));
+
+        let materialized_input_state = AggCallState::materialized_input(
+            TableCatalogBuilder::default().build(vec![], 0),
+            vec![0],
+            vec![0],
+            vec![],
+        )
+        .into_prost(&mut state);
+        match materialized_input_state.inner {
+            Some(agg_call_state::Inner::MaterializedInputState(s)) => {
+                assert_eq!(s.included_upstream_indices, vec![0]);
+                assert_eq!(s.table_value_indices, vec![0]);
+                assert!(s.order_columns.is_empty());
+            }
+            other => panic!("expect `MaterializedInputState`, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_two_phase_shuffle_dist_matches_group_key() {
+        let ctx = crate::optimizer::optimizer_context::OptimizerContext::mock().await;
+        let fields = vec![
+            Field::with_name(DataType::Int64, "k1"),
+            Field::with_name(DataType::Int64, "k2"),
+            Field::with_name(DataType::Int64, "v"),
+        ];
+        let values: crate::PlanRef =
+            crate::optimizer::plan_node::LogicalValues::new(vec![], Schema { fields }, ctx).into();
+
+        let agg = Agg::new(vec![], IndexSet::from(vec![0, 2]), values);
+        let dist = agg.two_phase_shuffle_dist();
+        assert_eq!(
+            dist,
+            RequiredDist::shard_by_key(agg.input.schema().len(), &[0, 2])
+        );
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "requires a non-empty ORDER BY")]
+    async fn test_percentile_cont_without_order_by_panics() {
+        let ctx = crate::optimizer::optimizer_context::OptimizerContext::mock().await;
+        let fields = vec![Field::with_name(DataType::Float64, "v")];
+        let values: crate::PlanRef =
+            crate::optimizer::plan_node::LogicalValues::new(vec![], Schema { fields }, ctx).into();
+
+        let percentile_cont = PlanAggCall {
+            agg_kind: AggKind::PercentileCont,
+            return_type: DataType::Float64,
+            inputs: vec![InputRef::new(0, DataType::Float64)],
+            distinct: false,
+            order_by: vec![],
+            filter: Condition::true_cond(),
+            direct_args: vec![],
+        };
+        Agg::new(vec![percentile_cont], IndexSet::empty(), values);
+    }
+
+    #[tokio::test]
+    async fn test_to_partial_agg_mixing_avg_and_sum() {
+        let ctx = crate::optimizer::optimizer_context::OptimizerContext::mock().await;
+        let fields = vec![
+            Field::with_name(DataType::Int64, "k"),
+            Field::with_name(DataType::Int64, "v"),
+        ];
+        let values: crate::PlanRef =
+            crate::optimizer::plan_node::LogicalValues::new(vec![], Schema { fields }, ctx).into();
+
+        // By the time `avg(v)` reaches this planner layer, `LogicalAggBuilder::try_rewrite_agg_call`
+        // has already decomposed it into `sum(v)` and `count(v)` (combined via a division
+        // expression above the `Agg`), so a `select k, avg(v), sum(v) ...` query's `Agg` core
+        // looks like this: a plain `sum`, `count` and `sum` side by side, no `AggKind::Avg` in
+        // sight.
+        let avg_sum_call = PlanAggCall {
+            agg_kind: AggKind::Sum,
+            return_type: DataType::Int64,
+            inputs: vec![InputRef::new(1, DataType::Int64)],
+            distinct: false,
+            order_by: vec![],
+            filter: Condition::true_cond(),
+            direct_args: vec![],
+        };
+        let avg_count_call = PlanAggCall {
+            agg_kind: AggKind::Count,
+            return_type: DataType::Int64,
+            inputs: vec![InputRef::new(1, DataType::Int64)],
+            distinct: false,
+            order_by: vec![],
+            filter: Condition::true_cond(),
+            direct_args: vec![],
+        };
+        let plain_sum_call = PlanAggCall {
+            agg_kind: AggKind::Sum,
+            return_type: DataType::Int64,
+            inputs: vec![InputRef::new(1, DataType::Int64)],
+            distinct: false,
+            order_by: vec![],
+            filter: Condition::true_cond(),
+            direct_args: vec![],
+        };
+
+        let agg = Agg::new(
+            vec![
+                avg_sum_call.clone(),
+                avg_count_call.clone(),
+                plain_sum_call.clone(),
+            ],
+            IndexSet::from(vec![0]),
+            values,
+        );
+        let (partial, partial_output_indices) = agg.to_partial_agg();
+
+        // The partial phase keeps every call as-is: there's no `AggKind::Avg` left to decompose.
+        assert_eq!(partial.agg_calls, agg.agg_calls);
+        // Group key occupies output column 0, so the three calls land at 1, 2 and 3.
+        assert_eq!(partial_output_indices, vec![1, 2, 3]);
+
+        let total_calls: Vec<_> = agg
+            .agg_calls
+            .iter()
+            .zip_eq_fast(&partial_output_indices)
+            .map(|(call, &idx)| call.partial_to_total_agg_call(idx))
+            .collect();
+        // `avg`'s `sum` half re-sums the partial sums ...
+        assert_eq!(total_calls[0].agg_kind, AggKind::Sum);
+        assert_eq!(total_calls[0].inputs[0].index(), 1);
+        // ... and its `count` half sums the partial counts via `sum0`, not `count` again.
+        assert_eq!(total_calls[1].agg_kind, AggKind::Sum0);
+        assert_eq!(total_calls[1].inputs[0].index(), 2);
+        assert_eq!(total_calls[2].agg_kind, AggKind::Sum);
+        assert_eq!(total_calls[2].inputs[0].index(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_array_agg_two_phase_gated_on_order_by() {
+        let ctx = crate::optimizer::optimizer_context::OptimizerContext::mock().await;
+        let fields = vec![
+            Field::with_name(DataType::Int64, "k"),
+            Field::with_name(DataType::Int64, "v"),
+        ];
+        let values: crate::PlanRef =
+            crate::optimizer::plan_node::LogicalValues::new(vec![], Schema { fields }, ctx).into();
+
+        let array_agg_call = PlanAggCall {
+            agg_kind: AggKind::ArrayAgg,
+            return_type: DataType::List(Box::new(DataType::Int64)),
+            inputs: vec![InputRef::new(1, DataType::Int64)],
+            distinct: false,
+            order_by: vec![],
+            filter: Condition::true_cond(),
+            direct_args: vec![],
+        };
+
+        // Without `ORDER BY`, concatenating each shard's partial array is a valid total-phase
+        // result, so two-phase agg is allowed ...
+        let agg = Agg::new(
+            vec![array_agg_call.clone()],
+            IndexSet::from(vec![0]),
+            values.clone(),
+        );
+        assert!(agg.can_two_phase_agg());
+        assert!(agg.two_phase_agg_disable_reasons().is_empty());
+
+        let (_, partial_output_indices) = agg.to_partial_agg();
+        let total_call = array_agg_call.partial_to_total_agg_call(partial_output_indices[0]);
+        // ... and the total phase re-applies `array_agg`, now over the partial arrays, which
+        // resolves to the flattening `array_agg(anyarray) -> anyarray` overload rather than
+        // nesting them.
+        assert_eq!(total_call.agg_kind, AggKind::ArrayAgg);
+        assert_eq!(total_call.return_type, array_agg_call.return_type);
+        assert_eq!(total_call.inputs[0].index(), partial_output_indices[0]);
+
+        // ... but with an `ORDER BY`, the shards can't agree on a global order, so it isn't.
+        let ordered_array_agg_call = PlanAggCall {
+            order_by: vec![ColumnOrder::new(1, OrderType::ascending())],
+            ..array_agg_call
+        };
+        let agg = Agg::new(vec![ordered_array_agg_call], IndexSet::from(vec![0]), values);
+        assert!(!agg.can_two_phase_agg());
+        assert_eq!(
+            agg.two_phase_agg_disable_reasons()[0].cause,
+            TwoPhaseAggDisableCause::OrderSensitive
+        );
+    }
+
+    #[tokio::test]
+    async fn test_two_phase_agg_disable_reasons_names_the_call() {
+        let ctx = crate::optimizer::optimizer_context::OptimizerContext::mock().await;
+        let fields = vec![Field::with_name(DataType::Varchar, "v")];
+        let values: crate::PlanRef =
+            crate::optimizer::plan_node::LogicalValues::new(vec![], Schema { fields }, ctx).into();
+
+        // `string_agg` can't be two-phased (its partial results can't be re-concatenated without
+        // also carrying the delimiter through to the total phase, see `simply_cannot_two_phase!`),
+        // so this is disabled for every plan that uses it, not just ones with an `ORDER BY`.
+        let string_agg_call = PlanAggCall {
+            agg_kind: AggKind::StringAgg,
+            return_type: DataType::Varchar,
+            inputs: vec![InputRef::new(0, DataType::Varchar)],
+            distinct: false,
+            order_by: vec![],
+            filter: Condition::true_cond(),
+            direct_args: vec![],
+        };
+        let agg = Agg::new(vec![string_agg_call], IndexSet::from(vec![]), values);
+
+        assert!(!agg.can_two_phase_agg());
+        let reasons = agg.two_phase_agg_disable_reasons();
+        assert_eq!(reasons.len(), 1);
+        assert_eq!(reasons[0].call_index, 0);
+        assert_eq!(reasons[0].agg_kind, AggKind::StringAgg);
+        // `EXPLAIN VERBOSE` renders `TwoPhaseAggDisableReason` with `Pretty::debug`, so the
+        // `Debug` output is what actually reaches users -- check it names `StringAgg` too.
+        assert!(format!("{:?}", reasons[0]).contains("StringAgg"));
+    }
+
+    #[tokio::test]
+    async fn test_explain_state_tables_sum_group_by_k() {
+        let ctx = crate::optimizer::optimizer_context::OptimizerContext::mock().await;
+        let in_fields = vec![
+            Field::with_name(DataType::Int64, "k"),
+            Field::with_name(DataType::Int64, "v"),
+        ];
+        let values: crate::PlanRef = crate::optimizer::plan_node::LogicalValues::new(
+            vec![],
+            Schema { fields: in_fields },
+            ctx.clone(),
+        )
+        .into();
+
+        let sum_call = PlanAggCall {
+            agg_kind: AggKind::Sum,
+            return_type: DataType::Int64,
+            inputs: vec![InputRef::new(1, DataType::Int64)],
+            distinct: false,
+            order_by: vec![],
+            filter: Condition::true_cond(),
+            direct_args: vec![],
+        };
+        let agg = Agg::new(vec![sum_call], IndexSet::from(vec![0]), values);
+
+        // Stand in for the `StreamHashAgg`/`StreamSimpleAgg` node that would normally own this
+        // `Agg` core: only its schema (group key column followed by the agg's output column) and
+        // `ctx` are used by table inference.
+        let out_fields = vec![
+            Field::with_name(DataType::Int64, "k"),
+            Field::with_name(DataType::Int64, "sum"),
+        ];
+        let me: crate::PlanRef = crate::optimizer::plan_node::LogicalValues::new(
+            vec![],
+            Schema { fields: out_fields },
+            ctx,
+        )
+        .into();
+
+        let tables = agg.explain_state_tables(me, None, None);
+
+        // `sum` over a non-append-only input is a single-value state (see
+        // `agg_kinds::single_value_state!`), materialized directly into the intermediate state
+        // table rather than a separate per-call table, so there's exactly one internal table here.
+        assert_eq!(tables.len(), 1);
+        let table = &tables[0];
+        assert_eq!(table.kind, "intermediate state table");
+        assert_eq!(table.pk_columns, vec!["k".to_owned()]);
+        assert_eq!(table.value_indices, vec![1]);
+    }
+
+    #[tokio::test]
+    async fn test_min_agg_reports_materialized_input_state() {
+        let ctx = crate::optimizer::optimizer_context::OptimizerContext::mock().await;
+        let in_fields = vec![
+            Field::with_name(DataType::Int64, "k"),
+            Field::with_name(DataType::Int64, "v"),
+        ];
+        // `LogicalValues` is not append-only, so `min` can't use a single retraction-unaware
+        // running value and must fall back to `MaterializedInput` (see
+        // `Agg::infer_stream_agg_state_uncached`'s unconditional `AggKind::Min` arm).
+        let values: crate::PlanRef = crate::optimizer::plan_node::LogicalValues::new(
+            vec![],
+            Schema { fields: in_fields },
+            ctx.clone(),
+        )
+        .into();
+
+        let min_call = PlanAggCall {
+            agg_kind: AggKind::Min,
+            return_type: DataType::Int64,
+            inputs: vec![InputRef::new(1, DataType::Int64)],
+            distinct: false,
+            order_by: vec![],
+            filter: Condition::true_cond(),
+            direct_args: vec![],
+        };
+        let agg = Agg::new(vec![min_call], IndexSet::from(vec![0]), values);
+
+        let out_fields = vec![
+            Field::with_name(DataType::Int64, "k"),
+            Field::with_name(DataType::Int64, "min"),
+        ];
+        let me: crate::PlanRef = crate::optimizer::plan_node::LogicalValues::new(
+            vec![],
+            Schema { fields: out_fields },
+            ctx,
+        )
+        .into();
+
+        let states = agg.infer_stream_agg_state(me, None, None);
+        assert_eq!(states.len(), 1);
+        let AggCallState::MaterializedInput(state) = &states[0] else {
+            panic!("expected min(x) on retractable input to report a materialized-input state");
+        };
+        // This is exactly what `rw_catalog.rw_internal_tables` surfaces to users, reusing
+        // `infer_stream_agg_state`'s own classification rather than a separate guess.
+        assert_eq!(
+            state.table.agg_call_state_kind.as_deref(),
+            Some("materialized_input")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_group_key_point_lookup_hint_is_opt_in() {
+        let ctx = crate::optimizer::optimizer_context::OptimizerContext::mock().await;
+        let in_fields = vec![
+            Field::with_name(DataType::Int64, "k"),
+            Field::with_name(DataType::Int64, "v"),
+        ];
+        let values: crate::PlanRef = crate::optimizer::plan_node::LogicalValues::new(
+            vec![],
+            Schema { fields: in_fields },
+            ctx.clone(),
+        )
+        .into();
+
+        let sum_call = PlanAggCall {
+            agg_kind: AggKind::Sum,
+            return_type: DataType::Int64,
+            inputs: vec![InputRef::new(1, DataType::Int64)],
+            distinct: false,
+            order_by: vec![],
+            filter: Condition::true_cond(),
+            direct_args: vec![],
+        };
+
+        let out_fields = vec![
+            Field::with_name(DataType::Int64, "k"),
+            Field::with_name(DataType::Int64, "sum"),
+        ];
+
+        // Default: the hint is off even though the physical layout (pk == group key, packed
+        // value_indices) is already point-lookup-friendly -- it's a caller-asserted intent, not a
+        // derived property.
+        let agg = Agg::new(vec![sum_call.clone()], IndexSet::from(vec![0]), values.clone());
+        let me: crate::PlanRef = crate::optimizer::plan_node::LogicalValues::new(
+            vec![],
+            Schema {
+                fields: out_fields.clone(),
+            },
+            ctx.clone(),
+        )
+        .into();
+        let table = agg.infer_intermediate_state_table(me, None, None);
+        assert!(!table.read_optimized_for_point_lookup);
+
+        // Opting in flips the catalog-visible hint without touching the table's actual layout.
+        let agg = Agg::new(vec![sum_call], IndexSet::from(vec![0]), values)
+            .with_group_key_point_lookup(true);
+        let me: crate::PlanRef =
+            crate::optimizer::plan_node::LogicalValues::new(vec![], Schema { fields: out_fields }, ctx)
+                .into();
+        let table = agg.infer_intermediate_state_table(me, None, None);
+        assert!(table.read_optimized_for_point_lookup);
+    }
+
+    #[test]
+    fn test_agg_call_state_kind_name_matches_table_tag() {
+        // `AggCallState::kind_name` and the literal `TableCatalogBuilder::set_agg_call_state_kind`
+        // call in `gen_materialized_input_state` (see `infer_stream_agg_state_uncached`) both name
+        // the `MaterializedInput` kind -- keep them in sync by construction rather than by
+        // convention, since nothing else in the type system forces them to agree.
+        let state = AggCallState::MaterializedInput(Box::new(MaterializedInputState {
+            table: TableCatalogBuilder::default().build(vec![], 0),
+            included_upstream_indices: vec![0],
+            table_value_indices: vec![0],
+            order_columns: vec![],
+        }));
+        assert_eq!(state.kind_name(), "materialized_input");
+    }
+
+    #[tokio::test]
+    async fn test_global_count_star_is_singleton_with_empty_stream_key() {
+        let ctx = crate::optimizer::optimizer_context::OptimizerContext::mock().await;
+        let in_fields = vec![Field::with_name(DataType::Int64, "v")];
+        let values: crate::PlanRef = crate::optimizer::plan_node::LogicalValues::new(
+            vec![],
+            Schema { fields: in_fields },
+            ctx,
+        )
+        .into();
+
+        let count_call = PlanAggCall {
+            agg_kind: AggKind::Count,
+            return_type: DataType::Int64,
+            inputs: vec![],
+            distinct: false,
+            order_by: vec![],
+            filter: Condition::true_cond(),
+            direct_args: vec![],
+        };
+        // No `GROUP BY`: a global aggregate.
+        let agg = Agg::new(vec![count_call], IndexSet::empty(), values);
+
+        assert!(agg.is_singleton());
+        // The pk is empty, but per `Agg::is_singleton`'s contract that's an arity-zero pk over the
+        // single output row, not the absence of a pk.
+        assert_eq!(agg.stream_key(), Some(vec![]));
+    }
+
+    #[tokio::test]
+    async fn test_input_watermark_covers_group_key() {
+        let ctx = crate::optimizer::optimizer_context::OptimizerContext::mock().await;
+        let in_fields = vec![
+            Field::with_name(DataType::Timestamp, "ts"),
+            Field::with_name(DataType::Int64, "v"),
+        ];
+        let values: crate::PlanRef = crate::optimizer::plan_node::LogicalValues::new(
+            vec![],
+            Schema { fields: in_fields },
+            ctx,
+        )
+        .into();
+
+        let count_call = PlanAggCall {
+            agg_kind: AggKind::Count,
+            return_type: DataType::Int64,
+            inputs: vec![],
+            distinct: false,
+            order_by: vec![],
+            filter: Condition::true_cond(),
+            direct_args: vec![],
+        };
+        let agg = Agg::new(vec![count_call], IndexSet::from(vec![0]), values);
+
+        let mut no_watermark = FixedBitSet::with_capacity(2);
+        assert!(!agg.input_watermark_covers_group_key(&no_watermark));
+
+        no_watermark.insert(0);
+        assert!(agg.input_watermark_covers_group_key(&no_watermark));
+    }
+
+    #[tokio::test]
+    async fn test_append_only_hint_on_intermediate_state_table() {
+        let ctx = crate::optimizer::optimizer_context::OptimizerContext::mock().await;
+        let in_fields = vec![Field::with_name(DataType::Int64, "v")];
+        // `StreamValues` is always append-only.
+        let append_only_input: crate::PlanRef = crate::optimizer::plan_node::StreamValues::new(
+            crate::optimizer::plan_node::LogicalValues::new(
+                vec![],
+                Schema {
+                    fields: in_fields,
+                },
+                ctx.clone(),
+            ),
+        )
+        .into();
+
+        let count_call = PlanAggCall {
+            agg_kind: AggKind::Count,
+            return_type: DataType::Int64,
+            inputs: vec![],
+            distinct: false,
+            order_by: vec![],
+            filter: Condition::true_cond(),
+            direct_args: vec![],
+        };
+        let out_fields = vec![Field::with_name(DataType::Int64, "count")];
+
+        let agg = Agg::new(
+            vec![count_call.clone()],
+            IndexSet::empty(),
+            append_only_input.clone(),
+        );
+        let me: crate::PlanRef = crate::optimizer::plan_node::LogicalValues::new(
+            vec![],
+            Schema {
+                fields: out_fields.clone(),
+            },
+            ctx.clone(),
+        )
+        .into();
+        let table = agg.infer_intermediate_state_table(me, None, None);
+        assert!(table.append_only);
+
+        // `StreamSimpleAgg` always flips `append_only` to `false`, since a group's count can be
+        // retracted and re-emitted as more rows for it arrive (see `StreamSimpleAgg::new`).
+        let simple_agg_core = Agg::new(vec![count_call.clone()], IndexSet::empty(), append_only_input);
+        let non_append_only_input: crate::PlanRef =
+            crate::optimizer::plan_node::StreamSimpleAgg::new(simple_agg_core, 0).into();
+
+        let agg = Agg::new(vec![count_call], IndexSet::empty(), non_append_only_input);
+        let me: crate::PlanRef = crate::optimizer::plan_node::LogicalValues::new(
+            vec![],
+            Schema { fields: out_fields },
+            ctx,
+        )
+        .into();
+        let table = agg.infer_intermediate_state_table(me, None, None);
+        assert!(!table.append_only);
+    }
+
+    #[tokio::test]
+    async fn test_infer_stream_agg_state_is_cached_by_signature() {
+        let ctx = crate::optimizer::optimizer_context::OptimizerContext::mock().await;
+        let in_fields = vec![Field::with_name(DataType::Int64, "v")];
+        let input: crate::PlanRef = crate::optimizer::plan_node::StreamValues::new(
+            crate::optimizer::plan_node::LogicalValues::new(
+                vec![],
+                Schema {
+                    fields: in_fields,
+                },
+                ctx.clone(),
+            ),
+        )
+        .into();
+        let count_call = PlanAggCall {
+            agg_kind: AggKind::Count,
+            return_type: DataType::Int64,
+            inputs: vec![],
+            distinct: false,
+            order_by: vec![],
+            filter: Condition::true_cond(),
+            direct_args: vec![],
+        };
+        let agg = Agg::new(vec![count_call], IndexSet::empty(), input);
+        let me: crate::PlanRef = crate::optimizer::plan_node::LogicalValues::new(
+            vec![],
+            Schema {
+                fields: vec![Field::with_name(DataType::Int64, "count")],
+            },
+            ctx.clone(),
+        )
+        .into();
+
+        // Nothing cached yet for this agg's signature.
+        let key = agg.agg_state_inference_key(None, None);
+        assert!(ctx.get_cached_agg_state_inference(&key).is_none());
+
+        let first = agg.infer_stream_agg_state(me.clone(), None, None);
+        // The first call should have populated the cache under the same signature.
+        assert!(ctx.get_cached_agg_state_inference(&key).is_some());
+
+        // A second inference for the structurally identical agg should hit the cache and return
+        // the same result, rather than recomputing it from scratch.
+        let second = agg.infer_stream_agg_state(me, None, None);
+        assert_eq!(
+            first.iter().map(AggCallState::kind_name).collect_vec(),
+            second.iter().map(AggCallState::kind_name).collect_vec(),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_agg_state_inference_key_distinguishes_vnode_and_window_col_idx() {
+        // Two structurally-identical `Agg`s (same group key, agg kinds, input distribution) that
+        // only differ in `vnode_col_idx`/`window_col_idx` must not collide on the same cache key,
+        // since both affect the inferred state table's pk ordering and vnode column.
+        let ctx = crate::optimizer::optimizer_context::OptimizerContext::mock().await;
+        let in_fields = vec![Field::with_name(DataType::Int64, "k")];
+        let input: crate::PlanRef = crate::optimizer::plan_node::LogicalValues::new(
+            vec![],
+            Schema { fields: in_fields },
+            ctx,
+        )
+        .into();
+        let agg = Agg::new(vec![], IndexSet::from(vec![0]), input);
+
+        let base_key = agg.agg_state_inference_key(None, None);
+        assert_ne!(base_key, agg.agg_state_inference_key(Some(0), None));
+        assert_ne!(base_key, agg.agg_state_inference_key(None, Some(0)));
+        assert_ne!(
+            agg.agg_state_inference_key(Some(0), None),
+            agg.agg_state_inference_key(None, Some(0))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_infer_distinct_dedup_tables_orders_by_distinct_col_index() {
+        let ctx = crate::optimizer::optimizer_context::OptimizerContext::mock().await;
+        let in_fields = vec![
+            Field::with_name(DataType::Int64, "k"),
+            Field::with_name(DataType::Int64, "d1"),
+            Field::with_name(DataType::Int64, "d2"),
+        ];
+        let values: crate::PlanRef = crate::optimizer::plan_node::LogicalValues::new(
+            vec![],
+            Schema {
+                fields: in_fields,
+            },
+            ctx.clone(),
+        )
+        .into();
+
+        let distinct_call = |input_idx: usize| PlanAggCall {
+            agg_kind: AggKind::Count,
+            return_type: DataType::Int64,
+            inputs: vec![InputRef::new(input_idx, DataType::Int64)],
+            distinct: true,
+            order_by: vec![],
+            filter: Condition::true_cond(),
+            direct_args: vec![],
+        };
+        // Declare the higher-indexed distinct column first: a `HashMap`'s iteration order (driven
+        // by hashing, not insertion order) wouldn't reliably come out as `[1, 2]` here, so this
+        // would be a flaky assertion if `infer_distinct_dedup_tables` still returned one.
+        let agg = Agg::new(
+            vec![distinct_call(2), distinct_call(1)],
+            IndexSet::from(vec![0]),
+            values,
+        );
+
+        let out_fields = vec![
+            Field::with_name(DataType::Int64, "k"),
+            Field::with_name(DataType::Int64, "count_d2"),
+            Field::with_name(DataType::Int64, "count_d1"),
+        ];
+        let me: crate::PlanRef = crate::optimizer::plan_node::LogicalValues::new(
+            vec![],
+            Schema {
+                fields: out_fields,
+            },
+            ctx,
+        )
+        .into();
+
+        let tables = agg.infer_distinct_dedup_tables(me, None, None);
+        let distinct_col_indices = tables.keys().copied().collect_vec();
+        assert_eq!(distinct_col_indices, vec![1, 2]);
+    }
+}
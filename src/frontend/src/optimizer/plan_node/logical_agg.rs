@@ -174,13 +174,20 @@ impl LogicalAgg {
         Ok(new_stream_simple_agg(core).into())
     }
 
-    fn gen_shuffle_plan(&self, stream_input: PlanRef) -> Result<PlanRef> {
-        let input =
-            RequiredDist::shard_by_key(stream_input.schema().len(), &self.group_key().to_vec())
-                .enforce_if_not_satisfies(stream_input, &Order::any())?;
+    fn gen_shuffle_plan(
+        &self,
+        stream_input: PlanRef,
+        two_phase_agg_skipped_due_to_dist: bool,
+    ) -> Result<PlanRef> {
+        let input = self
+            .core
+            .two_phase_shuffle_dist()
+            .enforce_if_not_satisfies(stream_input, &Order::any())?;
         let mut core = self.core.clone();
         core.input = input;
-        Ok(new_stream_hash_agg(core, None).into())
+        let agg = new_stream_hash_agg(core, None)
+            .with_two_phase_agg_skipped_due_to_dist(two_phase_agg_skipped_due_to_dist);
+        Ok(agg.into())
     }
 
     /// Generates distributed stream plan.
@@ -194,7 +201,7 @@ impl LogicalAgg {
         // If we have group key, and we won't try two phase agg optimization at all,
         // we will always choose shuffle agg over single agg.
         if !self.group_key().is_empty() && !self.core.must_try_two_phase_agg() {
-            return self.gen_shuffle_plan(stream_input);
+            return self.gen_shuffle_plan(stream_input, false);
         }
 
         // Standalone agg
@@ -240,6 +247,11 @@ impl LogicalAgg {
         // Vnode-based 2-phase agg
         // can be applied on agg calls not affected by order,
         // with input distributed by dist_key.
+        //
+        // If the only reason we didn't take this branch is that the input already satisfies the
+        // group-key hash distribution a second phase would redistribute it to anyway, remember
+        // that so the fallback shuffle agg below can report why two-phase agg was skipped.
+        let mut two_phase_agg_skipped_due_to_dist = false;
         match input_dist {
             Distribution::HashShard(dist_key) | Distribution::UpstreamHashShard(dist_key, _)
                 if (self.group_key().is_empty()
@@ -248,12 +260,17 @@ impl LogicalAgg {
                 let dist_key = dist_key.clone();
                 return self.gen_vnode_two_phase_streaming_agg_plan(stream_input, &dist_key);
             }
+            Distribution::HashShard(_) | Distribution::UpstreamHashShard(_, _)
+                if !self.group_key().is_empty() =>
+            {
+                two_phase_agg_skipped_due_to_dist = true;
+            }
             _ => {}
         }
 
         // Fallback to shuffle or single, if we can't generate any 2-phase plans.
         if !self.group_key().is_empty() {
-            self.gen_shuffle_plan(stream_input)
+            self.gen_shuffle_plan(stream_input, two_phase_agg_skipped_due_to_dist)
         } else {
             self.gen_single_plan(stream_input)
         }
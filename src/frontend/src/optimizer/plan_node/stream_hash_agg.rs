@@ -13,8 +13,7 @@
 // limitations under the License.
 
 use fixedbitset::FixedBitSet;
-use itertools::Itertools;
-use pretty_xmlish::XmlNode;
+use pretty_xmlish::{Pretty, XmlNode};
 use risingwave_pb::stream_plan::stream_node::PbNodeBody;
 
 use super::generic::{self, GenericPlanRef, PlanAggCall};
@@ -45,6 +44,14 @@ pub struct StreamHashAgg {
 
     /// The watermark column that Emit-On-Window-Close behavior is based on.
     window_col_idx: Option<usize>,
+
+    /// Set by [`LogicalAgg::gen_dist_stream_agg_plan`](super::LogicalAgg::gen_dist_stream_agg_plan)
+    /// when it chose a single-phase shuffle agg specifically because the input was already
+    /// distributed the way two-phase agg's own shuffle phase would redistribute it to (see
+    /// [`generic::Agg::hash_agg_dist_satisfied_by_input_dist`]), rather than because two-phase
+    /// agg wasn't eligible/forced in the first place. Surfaced in `EXPLAIN` via [`Distill`] so
+    /// it's clear why a forced two-phase agg didn't show up in the plan.
+    two_phase_agg_skipped_due_to_dist: bool,
 }
 
 impl StreamHashAgg {
@@ -56,6 +63,12 @@ impl StreamHashAgg {
         Self::new_with_eowc(core, vnode_col_idx, row_count_idx, false)
     }
 
+    /// See [`Self::two_phase_agg_skipped_due_to_dist`].
+    pub(crate) fn with_two_phase_agg_skipped_due_to_dist(mut self, skipped: bool) -> Self {
+        self.two_phase_agg_skipped_due_to_dist = skipped;
+        self
+    }
+
     pub fn new_with_eowc(
         core: generic::Agg<PlanRef>,
         vnode_col_idx: Option<usize>,
@@ -102,6 +115,7 @@ impl StreamHashAgg {
             row_count_idx,
             emit_on_window_close,
             window_col_idx,
+            two_phase_agg_skipped_due_to_dist: false,
         }
     }
 
@@ -113,6 +127,18 @@ impl StreamHashAgg {
         &self.core.group_key
     }
 
+    /// Whether this agg only emits a group's output once its window is closed by watermark,
+    /// rather than eagerly on every update. See [`Self::to_eowc_version`].
+    pub fn emit_on_window_close(&self) -> bool {
+        self.emit_on_window_close
+    }
+
+    /// The watermark column this agg's Emit-On-Window-Close behavior is based on, if
+    /// [`Self::emit_on_window_close`] is set.
+    pub fn window_col_idx(&self) -> Option<usize> {
+        self.window_col_idx
+    }
+
     pub(crate) fn i2o_col_mapping(&self) -> ColIndexMapping {
         self.core.i2o_col_mapping()
     }
@@ -145,9 +171,25 @@ impl StreamHashAgg {
 impl Distill for StreamHashAgg {
     fn distill<'a>(&self) -> XmlNode<'a> {
         let mut vec = self.core.fields_pretty();
+        if self.two_phase_agg_skipped_due_to_dist {
+            vec.push((
+                "two_phase_agg",
+                Pretty::display(&"skipped: input already hash-distributed by group key"),
+            ));
+        }
         if let Some(ow) = watermark_pretty(self.base.watermark_columns(), self.schema()) {
             vec.push(("output_watermarks", ow));
         }
+        if self.base.ctx().is_explain_verbose() {
+            let state_tables = self
+                .core
+                .explain_state_tables(&self.base, self.vnode_col_idx, self.window_col_idx);
+            vec.push(("state_tables", Pretty::debug(&state_tables)));
+            let disable_reasons = self.core.two_phase_agg_disable_reasons();
+            if !disable_reasons.is_empty() {
+                vec.push(("two_phase_agg_disable_reasons", Pretty::debug(&disable_reasons)));
+            }
+        }
         childless_record(
             plan_node_name!(
                 "StreamHashAgg",
@@ -206,7 +248,6 @@ impl StreamNode for StreamHashAgg {
             ),
             distinct_dedup_tables: distinct_dedup_tables
                 .into_iter()
-                .sorted_by_key(|(i, _)| *i)
                 .map(|(key_idx, table)| {
                     (
                         key_idx as u32,
@@ -246,3 +287,35 @@ impl ExprVisitable for StreamHashAgg {
         self.core.visit_exprs(v);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::optimizer::plan_node::{LogicalNow, StreamNow};
+
+    #[tokio::test]
+    async fn test_to_eowc_version_groups_by_watermark_column() {
+        let ctx = crate::optimizer::optimizer_context::OptimizerContext::mock().await;
+        // `StreamNow` produces a single watermark column, so grouping by it is the simplest way
+        // to get a `StreamHashAgg` whose group key is coverable by Emit-On-Window-Close.
+        let now: PlanRef = StreamNow::new(LogicalNow::new(ctx.clone()), ctx).into();
+
+        let core = generic::Agg::new(vec![PlanAggCall::count_star()], IndexSet::from(vec![0]), now);
+        let hash_agg = StreamHashAgg::new(core, None, 0);
+        assert!(!hash_agg.emit_on_window_close());
+        assert_eq!(hash_agg.window_col_idx(), None);
+
+        // Once converted, the agg only emits a group's row once its window (i.e. the group key's
+        // watermark) has closed, rather than eagerly on every input update.
+        let eowc = hash_agg
+            .to_eowc_version()
+            .unwrap()
+            .as_stream_hash_agg()
+            .unwrap()
+            .clone();
+        assert!(eowc.emit_on_window_close());
+        assert_eq!(eowc.window_col_idx(), Some(0));
+        // EOWC output is append-only: a group is only ever emitted once, when its window closes.
+        assert!(eowc.base.append_only());
+    }
+}
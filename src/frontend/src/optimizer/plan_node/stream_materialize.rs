@@ -260,6 +260,8 @@ impl StreamMaterialize {
             initialized_at_cluster_version: None,
             created_at_cluster_version: None,
             retention_seconds: retention_seconds.map(|i| i.into()),
+            agg_call_state_kind: None,
+            read_optimized_for_point_lookup: false,
         })
     }
 
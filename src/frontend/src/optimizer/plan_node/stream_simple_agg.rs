@@ -13,8 +13,7 @@
 // limitations under the License.
 
 use fixedbitset::FixedBitSet;
-use itertools::Itertools;
-use pretty_xmlish::XmlNode;
+use pretty_xmlish::{Pretty, XmlNode};
 use risingwave_pb::stream_plan::stream_node::PbNodeBody;
 
 use super::generic::{self, PlanAggCall};
@@ -68,7 +67,16 @@ impl Distill for StreamSimpleAgg {
         let name = plan_node_name!("StreamSimpleAgg",
             { "append_only", self.input().append_only() },
         );
-        childless_record(name, self.core.fields_pretty())
+        let mut vec = self.core.fields_pretty();
+        if self.base.ctx().is_explain_verbose() {
+            let state_tables = self.core.explain_state_tables(&self.base, None, None);
+            vec.push(("state_tables", Pretty::debug(&state_tables)));
+            let disable_reasons = self.core.two_phase_agg_disable_reasons();
+            if !disable_reasons.is_empty() {
+                vec.push(("two_phase_agg_disable_reasons", Pretty::debug(&disable_reasons)));
+            }
+        }
+        childless_record(name, vec)
     }
 }
 
@@ -118,7 +126,6 @@ impl StreamNode for StreamSimpleAgg {
             ),
             distinct_dedup_tables: distinct_dedup_tables
                 .into_iter()
-                .sorted_by_key(|(i, _)| *i)
                 .map(|(key_idx, table)| {
                     (
                         key_idx as u32,
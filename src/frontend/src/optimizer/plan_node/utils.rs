@@ -40,6 +40,9 @@ pub struct TableCatalogBuilder {
     read_prefix_len_hint: usize,
     watermark_columns: Option<FixedBitSet>,
     dist_key_in_pk: Option<Vec<usize>>,
+    append_only: bool,
+    agg_call_state_kind: Option<String>,
+    read_optimized_for_point_lookup: bool,
 }
 
 /// For DRY, mainly used for construct internal table catalog in stateful streaming executors.
@@ -110,6 +113,27 @@ impl TableCatalogBuilder {
         self.dist_key_in_pk = Some(dist_key_in_pk);
     }
 
+    /// Mark this table as append-only, i.e. it never receives deletes/updates. This is a perf
+    /// hint only: callers must only set it when the table's writes are truly append-only, e.g. an
+    /// aggregate's state table fed by an append-only input. Storage can use it to skip
+    /// tombstone/retraction bookkeeping.
+    pub fn set_append_only(&mut self, append_only: bool) {
+        self.append_only = append_only;
+    }
+
+    /// Tag this table as backing a single streaming aggregate call's state, e.g.
+    /// `"materialized_input"` (see `AggCallState::kind_name`). Surfaced via
+    /// `rw_catalog.rw_internal_tables` so operators can see, per materialized view, how much of
+    /// its state is a per-key materialized-input table rather than a single tracked value.
+    pub fn set_agg_call_state_kind(&mut self, agg_call_state_kind: impl Into<String>) {
+        self.agg_call_state_kind = Some(agg_call_state_kind.into());
+    }
+
+    /// See `generic::Agg::with_group_key_point_lookup`.
+    pub fn set_read_optimized_for_point_lookup(&mut self, read_optimized_for_point_lookup: bool) {
+        self.read_optimized_for_point_lookup = read_optimized_for_point_lookup;
+    }
+
     /// Check the column name whether exist before. if true, record occurrence and change the name
     /// to avoid duplicate.
     fn avoid_duplicate_col_name(&mut self, column_desc: &mut ColumnDesc) {
@@ -149,7 +173,7 @@ impl TableCatalogBuilder {
             // NOTE: This should be altered if `TableCatalogBuilder` is used to build something
             // other than internal tables.
             table_type: TableType::Internal,
-            append_only: false,
+            append_only: self.append_only,
             owner: risingwave_common::catalog::DEFAULT_SUPER_USER_ID,
             fragment_id: OBJECT_ID_PLACEHOLDER,
             dml_fragment_id: None,
@@ -176,6 +200,8 @@ impl TableCatalogBuilder {
             initialized_at_cluster_version: None,
             created_at_cluster_version: None,
             retention_seconds: None,
+            agg_call_state_kind: self.agg_call_state_kind,
+            read_optimized_for_point_lookup: self.read_optimized_for_point_lookup,
         }
     }
 
@@ -0,0 +1,295 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use itertools::Itertools;
+use risingwave_expr::aggregate::AggKind;
+
+use super::{BoxedRule, Rule};
+use crate::optimizer::plan_node::generic::Agg;
+use crate::optimizer::plan_node::{LogicalProject, PlanAggCall, PlanTreeNodeUnary};
+use crate::PlanRef;
+
+/// Merges two directly-stacked [`LogicalAgg`](crate::optimizer::plan_node::LogicalAgg)s with
+/// identical group keys into a single `LogicalAgg`, so the rows only get shuffled and grouped
+/// once. This pattern shows up after [`AggProjectMergeRule`](super::AggProjectMergeRule) has
+/// already pushed away a trivial pass-through projection that used to separate the two aggs --
+/// which is common after subquery unnesting -- so this rule should run after it in the same
+/// optimization stage.
+///
+/// Fires when every input the outer agg's calls read is one of the inner agg's group-key
+/// columns, none of them use a `filter` or `order_by`, and every outer call's kind is either
+/// `count(*)` or in [`is_duplicate_insensitive`]. The latter two conditions alone are not enough:
+/// merging makes the outer calls run over the inner agg's raw per-key rows instead of the single
+/// row the inner agg would have produced per group, which changes the result for any kind whose
+/// value depends on how many rows it sees. Restricting to kinds that only ever read a group-key
+/// column -- already guaranteed by the input check -- and whose value doesn't change when
+/// computed over duplicates of that same value keeps the merge exact for
+/// [`is_duplicate_insensitive`] kinds. `count(*)` would instead go from always `1` (one
+/// pre-aggregated row per group) to the raw per-key row count, so it can't be folded in the same
+/// way; it's handled separately by reusing the group row count the inner agg already tracks
+/// internally for emptiness detection (see
+/// [`Agg::with_implicit_count_col`](crate::optimizer::plan_node::generic::Agg::with_implicit_count_col))
+/// instead of letting it fall through to a second, redundant `count(*)` state.
+pub struct AggAggMergeRule {}
+
+/// Whether `kind`'s result is unchanged when computed over `N` copies of the same value instead
+/// of a single value, for any `N >= 1`. Used to guard [`AggAggMergeRule`], where the outer agg's
+/// calls only ever read an inner group-key column (so every row they see within one group carries
+/// the same value) but get recomputed over all of that group's raw rows instead of the single row
+/// the inner agg would have produced.
+fn is_duplicate_insensitive(kind: AggKind) -> bool {
+    matches!(
+        kind,
+        AggKind::Min
+            | AggKind::Max
+            | AggKind::FirstValue
+            | AggKind::LastValue
+            | AggKind::BitAnd
+            | AggKind::BitOr
+            | AggKind::BoolAnd
+            | AggKind::BoolOr
+    )
+}
+
+impl Rule for AggAggMergeRule {
+    fn apply(&self, plan: PlanRef) -> Option<PlanRef> {
+        let outer_agg = plan.as_logical_agg()?;
+        let inner_agg = outer_agg.input();
+        let inner_agg = inner_agg.as_logical_agg()?;
+
+        let inner_group_key_len = inner_agg.group_key().len();
+
+        // The outer group key must consist solely of the inner agg's group-key columns.
+        let outer_group_key = outer_agg.group_key().indices().collect_vec();
+        if outer_group_key.len() != inner_group_key_len
+            || !outer_group_key.iter().all(|i| *i < inner_group_key_len)
+        {
+            return None;
+        }
+
+        // Every input the outer agg's calls read must also be an inner group-key column, none may
+        // carry a `filter`/`order_by` (otherwise merging could silently change or discard the
+        // inner agg's aggregation semantics), and the call's kind must be `count(*)` or
+        // duplicate-insensitive (otherwise merging changes the result -- see the rule's doc
+        // comment).
+        let count_star = PlanAggCall::count_star();
+        if !outer_agg.agg_calls().iter().all(|call| {
+            call.filter.always_true()
+                && call.order_by.is_empty()
+                && (call == &count_star
+                    || (is_duplicate_insensitive(call.agg_kind)
+                        && call
+                            .inputs
+                            .iter()
+                            .all(|input| input.index() < inner_group_key_len)))
+        }) {
+            return None;
+        }
+
+        let needs_row_count = outer_agg.agg_calls().iter().any(|call| call == &count_star);
+        let inner_core = if needs_row_count {
+            inner_agg.core().clone().with_implicit_count_col()
+        } else {
+            inner_agg.core().clone()
+        };
+        let row_count_idx = needs_row_count.then(|| {
+            inner_core
+                .agg_calls
+                .iter()
+                .position(|call| call == &count_star)
+                .expect("with_implicit_count_col just ensured count(*) is present")
+        });
+
+        // Group key columns (in the outer's original order) followed by the outer agg call
+        // outputs, each either pointing at a freshly-appended call or, for `count(*)`, at the
+        // inner agg's (possibly shared) row count column. The outer calls' inputs index the inner
+        // agg's output, whose leading columns are its group key, so they are rebased onto the
+        // inner agg's input columns.
+        let inner_group_key = inner_agg.group_key().indices().collect_vec();
+        let mut new_calls = inner_core.agg_calls.clone();
+        let out_call_idx = outer_agg
+            .agg_calls()
+            .iter()
+            .map(|call| {
+                if call == &count_star {
+                    row_count_idx.unwrap()
+                } else {
+                    let mut call = call.clone();
+                    for input in &mut call.inputs {
+                        input.index = inner_group_key[input.index];
+                    }
+                    new_calls.push(call);
+                    new_calls.len() - 1
+                }
+            })
+            .collect_vec();
+
+        let new_agg = Agg::new(new_calls, inner_agg.group_key().clone(), inner_agg.input())
+            .with_enable_two_phase(inner_core.two_phase_agg_enabled())
+            .into();
+
+        let out_col_idx = outer_group_key.into_iter().chain(out_call_idx);
+        Some(LogicalProject::with_out_col_idx(new_agg, out_col_idx).into())
+    }
+}
+
+impl AggAggMergeRule {
+    pub fn create() -> BoxedRule {
+        Box::new(Self {})
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::FutureExt;
+    use risingwave_common::array::{StreamChunk, StreamChunkTestExt};
+    use risingwave_common::catalog::{Field, Schema};
+    use risingwave_common::types::DataType;
+    use risingwave_expr::aggregate::{build_append_only, AggCall};
+
+    use super::*;
+    use crate::expr::{assert_eq_input_ref, InputRef};
+    use crate::optimizer::optimizer_context::OptimizerContext;
+    use crate::optimizer::plan_node::{LogicalValues, PlanAggCall};
+    use crate::utils::{Condition, IndexSet};
+
+    async fn values_plan() -> PlanRef {
+        let ctx = OptimizerContext::mock().await;
+        let ty = DataType::Int64;
+        let fields = vec![
+            Field::with_name(ty.clone(), "k"),
+            Field::with_name(ty.clone(), "v"),
+        ];
+        LogicalValues::new(vec![], Schema { fields }, ctx).into()
+    }
+
+    async fn sum_v_group_by_k() -> PlanRef {
+        let ty = DataType::Int64;
+        let inner_calls = vec![PlanAggCall {
+            agg_kind: AggKind::Sum,
+            return_type: ty.clone(),
+            inputs: vec![InputRef::new(1, ty)],
+            distinct: false,
+            order_by: vec![],
+            filter: Condition::true_cond(),
+            direct_args: vec![],
+        }];
+        Agg::new(inner_calls, IndexSet::from(vec![0]), values_plan().await).into()
+    }
+
+    #[tokio::test]
+    async fn test_agg_agg_merge() {
+        // outer: select k, max(k) from (select k, sum(v) from values group by k) group by k
+        let ty = DataType::Int64;
+        let outer_calls = vec![PlanAggCall {
+            agg_kind: AggKind::Max,
+            return_type: ty.clone(),
+            inputs: vec![InputRef::new(0, ty)],
+            distinct: false,
+            order_by: vec![],
+            filter: Condition::true_cond(),
+            direct_args: vec![],
+        }];
+        let outer_agg: PlanRef =
+            Agg::new(outer_calls, IndexSet::from(vec![0]), sum_v_group_by_k().await).into();
+
+        let result = AggAggMergeRule::create().apply(outer_agg).unwrap();
+        let proj = result.as_logical_project().unwrap();
+        let merged_agg = proj.input();
+        let merged_agg = merged_agg.as_logical_agg().unwrap();
+        // sum(v) from the inner agg and max(k) from the outer agg both survive in one node.
+        assert_eq!(merged_agg.agg_calls().len(), 2);
+        assert_eq!(merged_agg.group_key().len(), 1);
+        assert_eq!(merged_agg.agg_calls()[0].agg_kind, AggKind::Sum);
+        assert_eq!(merged_agg.agg_calls()[1].agg_kind, AggKind::Max);
+    }
+
+    #[tokio::test]
+    async fn test_agg_agg_merge_folds_outer_count_star_into_inner_row_count() {
+        // outer: select k, count(*) from (select k, sum(v) from values group by k) group by k.
+        // count(*) over the single pre-aggregated row per group is always 1, not the group's row
+        // count, so it can't be merged as a plain duplicate-insensitive call like `max`/`min`
+        // above. Instead the rule appends the inner agg's own implicit row count (the same state
+        // already tracked for emptiness detection, see `Agg::with_implicit_count_col`) and points
+        // the outer `count(*)` at it, rather than declining to merge or adding a second, redundant
+        // `count(*)` state.
+        let outer_calls = vec![PlanAggCall::count_star()];
+        let outer_agg: PlanRef =
+            Agg::new(outer_calls, IndexSet::from(vec![0]), sum_v_group_by_k().await).into();
+
+        let result = AggAggMergeRule::create().apply(outer_agg).unwrap();
+        let proj = result.as_logical_project().unwrap();
+        let merged_agg = proj.input();
+        let merged_agg = merged_agg.as_logical_agg().unwrap();
+        // sum(v) from the inner agg plus one implicit count(*) appended for the outer call --
+        // not two separate count(*) states.
+        assert_eq!(merged_agg.agg_calls().len(), 2);
+        assert_eq!(merged_agg.agg_calls()[0].agg_kind, AggKind::Sum);
+        assert_eq!(merged_agg.agg_calls()[1], PlanAggCall::count_star());
+
+        // The projection's second output column (after the group key) points at the appended
+        // count(*), sharing it rather than adding a third call.
+        let out_col_idx = proj.exprs();
+        assert_eq!(out_col_idx.len(), 2);
+        assert_eq_input_ref!(&out_col_idx[1], 1);
+    }
+
+    #[tokio::test]
+    async fn test_agg_agg_merge_shares_existing_inner_count_star() {
+        // outer: select k, count(*) from (select k, count(*) from values group by k) group by k.
+        // the inner agg already has an explicit count(*), so the outer one must reuse it instead
+        // of appending a second copy.
+        let ctx = OptimizerContext::mock().await;
+        let ty = DataType::Int64;
+        let fields = vec![
+            Field::with_name(ty.clone(), "k"),
+            Field::with_name(ty.clone(), "v"),
+        ];
+        let values: PlanRef = LogicalValues::new(vec![], Schema { fields }, ctx).into();
+        let inner_agg: PlanRef =
+            Agg::new(vec![PlanAggCall::count_star()], IndexSet::from(vec![0]), values).into();
+        let outer_agg: PlanRef =
+            Agg::new(vec![PlanAggCall::count_star()], IndexSet::from(vec![0]), inner_agg).into();
+
+        let result = AggAggMergeRule::create().apply(outer_agg).unwrap();
+        let proj = result.as_logical_project().unwrap();
+        let merged_agg = proj.input();
+        let merged_agg = merged_agg.as_logical_agg().unwrap();
+        assert_eq!(merged_agg.agg_calls().len(), 1);
+        assert_eq!(merged_agg.agg_calls()[0], PlanAggCall::count_star());
+    }
+
+    /// Materializes both the two-stage (unmerged) and the rule's merged single-stage aggregation
+    /// of `max(k)` over a group with duplicate rows, and checks they produce the same concrete
+    /// value -- not just the same plan shape. Because the outer call only ever reads an inner
+    /// group-key column (enforced by the rule itself), every row of the group carries the same
+    /// `k`, so `max` computed once (unmerged, over the inner agg's single output row) or computed
+    /// over all the raw duplicates (merged) must agree.
+    #[test]
+    fn test_agg_agg_merge_preserves_values_for_allowed_kind() {
+        // Unmerged: the inner agg has already collapsed the group down to its single output row.
+        let inner_output_row = StreamChunk::from_pretty(" I\n + 1");
+        // Merged: the rewritten outer call instead runs over the still-duplicated raw rows.
+        let raw_rows = StreamChunk::from_pretty(" I\n + 1\n + 1\n + 1");
+
+        let max_of = |chunk: &StreamChunk| {
+            let max = build_append_only(&AggCall::from_pretty("(max:int8 $0:int8)")).unwrap();
+            let mut state = max.create_state();
+            max.update(&mut state, chunk).now_or_never().unwrap().unwrap();
+            max.get_result(&state).now_or_never().unwrap().unwrap()
+        };
+
+        assert_eq!(max_of(&inner_output_row), max_of(&raw_rows));
+    }
+}
@@ -154,6 +154,10 @@ mod apply_hop_window_transpose_rule;
 pub use apply_hop_window_transpose_rule::*;
 mod agg_call_merge_rule;
 pub use agg_call_merge_rule::*;
+mod sum_to_count_rule;
+pub use sum_to_count_rule::*;
+mod agg_agg_merge_rule;
+pub use agg_agg_merge_rule::*;
 mod pull_up_correlated_predicate_agg_rule;
 mod values_extract_project_rule;
 pub use batch::batch_push_limit_to_scan_rule::*;
@@ -227,6 +231,8 @@ macro_rules! for_all_rules {
             , { AggGroupBySimplifyRule }
             , { ApplyHopWindowTransposeRule }
             , { AggCallMergeRule }
+            , { SumToCountRule }
+            , { AggAggMergeRule }
             , { ValuesExtractProjectRule }
             , { BatchPushLimitToScanRule }
             , { PullUpCorrelatedPredicateAggRule }
@@ -0,0 +1,189 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::types::ScalarImpl;
+use risingwave_expr::aggregate::AggKind;
+
+use super::{BoxedRule, Rule};
+use crate::optimizer::plan_node::generic::{Agg, GenericPlanRef, PlanAggCall};
+use crate::optimizer::plan_node::{LogicalAgg, PlanTreeNodeUnary};
+use crate::PlanRef;
+
+/// `sum(1)` (or `sum` of any constant `1` input) is semantically equivalent to `count(*)`, but
+/// uses the heavier `Sum` state and misses optimizations (e.g. two-phase aggregation, state table
+/// simplifications) that only recognize `AggKind::Count`. This rule rewrites such calls to
+/// `count(*)` once the input has been constant-folded down to a `Literal` of `1`.
+///
+/// Only fires when the agg has a non-empty group key. A grouped `sum(1)` always has at least one
+/// input row per group, so it never actually returns `NULL`, making the rewrite to `count(*)`
+/// (which returns `0` on zero rows) exact. An ungrouped agg, however, can run over zero input rows
+/// -- where `sum(1)` must return `NULL` but `count(*)` returns `0` -- so rewriting it would
+/// silently change the result.
+pub struct SumToCountRule {}
+
+impl Rule for SumToCountRule {
+    fn apply(&self, plan: PlanRef) -> Option<PlanRef> {
+        let agg: &LogicalAgg = plan.as_logical_agg()?;
+        if agg.group_key().is_empty() {
+            return None;
+        }
+        let input = agg.input();
+        let project = input.as_logical_project()?;
+
+        let mut changed = false;
+        let new_calls = agg
+            .agg_calls()
+            .iter()
+            .map(|call| {
+                if call.agg_kind == AggKind::Sum
+                    && !call.distinct
+                    && let [input_ref] = call.inputs.as_slice()
+                    && is_literal_one(&project.exprs()[input_ref.index])
+                {
+                    changed = true;
+                    PlanAggCall {
+                        agg_kind: AggKind::Count,
+                        inputs: vec![],
+                        ..call.clone()
+                    }
+                } else {
+                    call.clone()
+                }
+            })
+            .collect();
+
+        if !changed {
+            return None;
+        }
+        Some(Agg::new(new_calls, agg.group_key().clone(), agg.input()).into())
+    }
+}
+
+fn is_literal_one(expr: &crate::expr::ExprImpl) -> bool {
+    let Some(literal) = expr.as_literal() else {
+        return false;
+    };
+    match literal.get_data() {
+        Some(ScalarImpl::Int16(v)) => *v == 1,
+        Some(ScalarImpl::Int32(v)) => *v == 1,
+        Some(ScalarImpl::Int64(v)) => *v == 1,
+        Some(ScalarImpl::Float32(v)) => v.into_inner() == 1.0,
+        Some(ScalarImpl::Float64(v)) => v.into_inner() == 1.0,
+        _ => false,
+    }
+}
+
+impl SumToCountRule {
+    pub fn create() -> BoxedRule {
+        Box::new(SumToCountRule {})
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::{Field, Schema};
+    use risingwave_common::types::{DataType, ScalarImpl};
+
+    use super::*;
+    use crate::expr::{ExprImpl, InputRef, Literal};
+    use crate::optimizer::optimizer_context::OptimizerContext;
+    use crate::optimizer::plan_node::{LogicalProject, LogicalValues};
+    use crate::utils::{Condition, IndexSet};
+
+    #[tokio::test]
+    async fn test_sum_one_to_count() {
+        let ctx = OptimizerContext::mock().await;
+        let fields = vec![Field::with_name(DataType::Int64, "k")];
+        let values: PlanRef = LogicalValues::new(vec![], Schema { fields }, ctx).into();
+
+        // select k, 1 as one from values
+        let project: PlanRef = LogicalProject::new(
+            values,
+            vec![
+                InputRef::new(0, DataType::Int64).into(),
+                Literal::new(Some(ScalarImpl::Int32(1)), DataType::Int32).into(),
+            ],
+        )
+        .into();
+
+        // select k, sum(one) from project group by k
+        let sum_call = PlanAggCall {
+            agg_kind: AggKind::Sum,
+            return_type: DataType::Int64,
+            inputs: vec![InputRef::new(1, DataType::Int32)],
+            distinct: false,
+            order_by: vec![],
+            filter: Condition::true_cond(),
+            direct_args: vec![],
+        };
+        let agg: PlanRef = Agg::new(vec![sum_call], IndexSet::from(vec![0]), project).into();
+
+        let result = SumToCountRule::create().apply(agg).unwrap();
+        let agg = result.as_logical_agg().unwrap();
+        assert_eq!(agg.agg_calls().len(), 1);
+        assert_eq!(agg.agg_calls()[0].agg_kind, AggKind::Count);
+        assert!(agg.agg_calls()[0].inputs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sum_one_ungrouped_not_rewritten() {
+        let ctx = OptimizerContext::mock().await;
+        let fields = vec![Field::with_name(DataType::Int64, "k")];
+        let values: PlanRef = LogicalValues::new(vec![], Schema { fields }, ctx).into();
+
+        // select 1 as one from values
+        let project: PlanRef = LogicalProject::new(
+            values,
+            vec![Literal::new(Some(ScalarImpl::Int32(1)), DataType::Int32).into()],
+        )
+        .into();
+
+        // select sum(one) from project -- no GROUP BY, so an empty `values` would make this
+        // `sum(1)` run over zero rows and return `NULL`; `count(*)` would wrongly return `0`.
+        let sum_call = PlanAggCall {
+            agg_kind: AggKind::Sum,
+            return_type: DataType::Int64,
+            inputs: vec![InputRef::new(0, DataType::Int32)],
+            distinct: false,
+            order_by: vec![],
+            filter: Condition::true_cond(),
+            direct_args: vec![],
+        };
+        let agg: PlanRef = Agg::new(vec![sum_call], IndexSet::empty(), project).into();
+
+        assert!(SumToCountRule::create().apply(agg).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sum_non_one_not_rewritten() {
+        let ctx = OptimizerContext::mock().await;
+        let fields = vec![Field::with_name(DataType::Int64, "v")];
+        let values: PlanRef = LogicalValues::new(vec![], Schema { fields }, ctx).into();
+
+        // select v from values (not wrapped in a project at all)
+        let sum_call = PlanAggCall {
+            agg_kind: AggKind::Sum,
+            return_type: DataType::Int64,
+            inputs: vec![InputRef::new(0, DataType::Int64)],
+            distinct: false,
+            order_by: vec![],
+            filter: Condition::true_cond(),
+            direct_args: vec![],
+        };
+        let agg: PlanRef = Agg::new(vec![sum_call], IndexSet::empty(), values).into();
+
+        // no `LogicalProject` input to inspect for a constant, so the rule can't fire.
+        assert!(SumToCountRule::create().apply(agg).is_none());
+    }
+}
@@ -592,6 +592,8 @@ pub(crate) mod tests {
             incoming_sinks: vec![],
             initialized_at_cluster_version: None,
             created_at_cluster_version: None,
+            agg_call_state_kind: None,
+            read_optimized_for_point_lookup: false,
         };
         let batch_plan_node: PlanRef = LogicalScan::create(
             "".to_string(),
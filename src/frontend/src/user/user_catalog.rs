@@ -84,9 +84,7 @@ impl UserCatalog {
             Object::SourceId(id) => self.object_acls.entry(id),
             Object::SinkId(id) => self.object_acls.entry(id),
             Object::ViewId(id) => self.object_acls.entry(id),
-            Object::FunctionId(_) => {
-                unreachable!("grant privilege on function is not supported yet.")
-            }
+            Object::FunctionId(id) => self.object_acls.entry(id),
             _ => unreachable!(""),
         }
     }
@@ -99,9 +97,7 @@ impl UserCatalog {
             Object::SourceId(id) => self.object_acls.get(id),
             Object::SinkId(id) => self.object_acls.get(id),
             Object::ViewId(id) => self.object_acls.get(id),
-            Object::FunctionId(_) => {
-                unreachable!("grant privilege on function is not supported yet.")
-            }
+            Object::FunctionId(id) => self.object_acls.get(id),
             _ => unreachable!("unexpected object type."),
         }
     }
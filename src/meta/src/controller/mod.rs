@@ -138,6 +138,10 @@ impl From<ObjectModel<table::Model>> for PbTable {
             initialized_at_cluster_version: value.1.initialized_at_cluster_version,
             created_at_cluster_version: value.1.created_at_cluster_version,
             retention_seconds: value.0.retention_seconds.map(|id| id as u32),
+            // Not persisted: it's re-derived by the frontend planner every time a table catalog
+            // is built, not stored as durable table metadata.
+            agg_call_state_kind: None,
+            read_optimized_for_point_lookup: false,
         }
     }
 }
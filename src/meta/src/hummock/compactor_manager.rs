@@ -26,6 +26,7 @@ use risingwave_pb::hummock::{
     SubscribeCompactionEventResponse,
 };
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use tokio::sync::Notify;
 
 use crate::manager::MetaSrvEnv;
 use crate::model::{MetadataModel, MetadataModelError};
@@ -37,6 +38,10 @@ pub const TASK_RUN_TOO_LONG: &str = "running too long";
 pub const TASK_NOT_FOUND: &str = "task not found";
 pub const TASK_NORMAL: &str = "task is normal, please wait some time";
 
+/// How long [`CompactorManager::wait_for_compactor`] waits for a compactor to become available
+/// before giving up, if [`CompactorManager::add_compactor`] doesn't wake it sooner.
+const NO_AVAILABLE_COMPACTOR_STALL: Duration = Duration::from_secs(5);
+
 /// Wraps the stream between meta node and compactor node.
 /// Compactor node will re-establish the stream when the previous one fails.
 #[derive(Debug)]
@@ -387,6 +392,15 @@ impl CompactorManagerInner {
 
 pub struct CompactorManager {
     inner: Arc<RwLock<CompactorManagerInner>>,
+    /// Notified whenever [`Self::add_compactor`] registers a new compactor, so
+    /// [`Self::wait_for_compactor`] can wake up as soon as one becomes available instead of
+    /// always waiting out `stall_duration`.
+    compactor_available: Notify,
+    /// How long [`Self::wait_for_compactor`] waits for a compactor to become available before
+    /// giving up, if [`Self::add_compactor`] doesn't wake it sooner. [`NO_AVAILABLE_COMPACTOR_STALL`]
+    /// in production; overridden to a much shorter duration in [`Self::for_test`] so tests that
+    /// exercise the no-compactor-available path don't burn real wall-clock time.
+    stall_duration: Duration,
 }
 
 impl CompactorManager {
@@ -395,6 +409,8 @@ impl CompactorManager {
 
         Ok(Self {
             inner: Arc::new(RwLock::new(inner)),
+            compactor_available: Notify::new(),
+            stall_duration: NO_AVAILABLE_COMPACTOR_STALL,
         })
     }
 
@@ -403,6 +419,8 @@ impl CompactorManager {
         let inner = CompactorManagerInner::for_test();
         Self {
             inner: Arc::new(RwLock::new(inner)),
+            compactor_available: Notify::new(),
+            stall_duration: Duration::from_millis(10),
         }
     }
 
@@ -410,11 +428,28 @@ impl CompactorManager {
         self.inner.read().next_compactor()
     }
 
+    /// Like [`Self::next_compactor`], but if no compactor is available yet, waits up to
+    /// `stall_duration` for one to register via [`Self::add_compactor`] instead of giving up
+    /// immediately.
+    pub async fn wait_for_compactor(&self) -> Option<Arc<Compactor>> {
+        let notified = self.compactor_available.notified();
+        if let Some(compactor) = self.next_compactor() {
+            return Some(compactor);
+        }
+        tokio::select! {
+            _ = notified => {}
+            _ = tokio::time::sleep(self.stall_duration) => {}
+        }
+        self.next_compactor()
+    }
+
     pub fn add_compactor(
         &self,
         context_id: HummockContextId,
     ) -> UnboundedReceiver<MetaResult<SubscribeCompactionEventResponse>> {
-        self.inner.write().add_compactor(context_id)
+        let rx = self.inner.write().add_compactor(context_id);
+        self.compactor_available.notify_waiters();
+        rx
     }
 
     pub fn abort_all_compactors(&self) {
@@ -558,4 +593,26 @@ mod tests {
         assert_eq!(compactor_manager.compactor_num(), 0);
         assert!(compactor_manager.get_compactor(context_id).is_none());
     }
+
+    #[tokio::test]
+    async fn test_wait_for_compactor_wakes_up_on_add() {
+        let compactor_manager = Arc::new(CompactorManager::for_test());
+        assert!(compactor_manager.next_compactor().is_none());
+
+        let waiter = {
+            let compactor_manager = compactor_manager.clone();
+            tokio::spawn(async move { compactor_manager.wait_for_compactor().await })
+        };
+
+        // Give the waiter a chance to start waiting before a compactor registers, so this
+        // actually exercises the wake-up path instead of `wait_for_compactor`'s fast path.
+        tokio::task::yield_now().await;
+        compactor_manager.add_compactor(1);
+
+        let compactor = tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("wait_for_compactor should wake up immediately, not stall")
+            .unwrap();
+        assert_eq!(compactor.unwrap().context_id(), 1);
+    }
 }
@@ -12,12 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
 use function_name::named;
 use itertools::Itertools;
-use risingwave_hummock_sdk::{CompactionGroupId, HummockCompactionTaskId};
+use risingwave_hummock_sdk::{CompactionGroupId, HummockCompactionTaskId, HummockContextId};
 use risingwave_pb::hummock::{CompactStatus as PbCompactStatus, CompactTaskAssignment};
 
 use crate::hummock::compaction::selector::level_selector::PickerInfo;
@@ -45,6 +45,23 @@ impl HummockManager {
             .len() as u64
     }
 
+    /// Returns the number of compaction tasks currently assigned to each compactor, keyed by
+    /// `context_id`. Unlike [`Self::get_assigned_compact_task_num`], which only reports the
+    /// aggregate count, this gives a per-compactor breakdown for assertions and metrics when
+    /// multiple compactors are active.
+    #[named]
+    pub async fn list_assigned_tasks_number(&self) -> HashMap<HummockContextId, u64> {
+        let mut ret: HashMap<HummockContextId, u64> = HashMap::new();
+        for assignment in read_lock!(self, compaction)
+            .await
+            .compact_task_assignment
+            .values()
+        {
+            *ret.entry(assignment.context_id).or_default() += 1;
+        }
+        ret
+    }
+
     #[named]
     pub async fn list_all_tasks_ids(&self) -> Vec<HummockCompactionTaskId> {
         let compaction = read_lock!(self, compaction).await;
@@ -715,10 +715,15 @@ impl HummockManager {
         // Don't trigger compactions if we enable deterministic compaction
         if !self.env.opts.compaction_deterministic_test {
             // commit_epoch may contains SSTs from any compaction group
-            self.try_send_compaction_request(parent_group_id, compact_task::TaskType::SpaceReclaim);
+            self.try_send_compaction_request(
+                parent_group_id,
+                compact_task::TaskType::SpaceReclaim,
+                None,
+            );
             self.try_send_compaction_request(
                 target_compaction_group_id,
                 compact_task::TaskType::SpaceReclaim,
+                None,
             );
         }
 
@@ -14,6 +14,7 @@
 
 use std::collections::{HashMap, HashSet};
 use std::ops::DerefMut;
+use std::time::Duration;
 
 use fail::fail_point;
 use function_name::named;
@@ -83,6 +84,7 @@ impl HummockManager {
 
     /// Checks whether `context_id` is valid.
     pub async fn check_context(&self, context_id: HummockContextId) -> Result<bool> {
+        fail_point!("fp_check_context_transient_invalid", |_| Ok(false));
         Ok(self
             .metadata_manager()
             .get_worker_by_id(context_id)
@@ -91,6 +93,19 @@ impl HummockManager {
             .is_some())
     }
 
+    /// Like [`Self::check_context`], but re-checks once after a short delay before giving up, in
+    /// case the context only appeared invalid transiently (e.g. a compactor mid-reconnect that
+    /// hasn't re-registered its worker yet). Used by [`Self::release_invalid_contexts`] so such a
+    /// compactor doesn't get its pins released just because it lost a race with worker
+    /// registration.
+    async fn check_context_with_retry(&self, context_id: HummockContextId) -> Result<bool> {
+        if self.check_context(context_id).await? {
+            return Ok(true);
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        self.check_context(context_id).await
+    }
+
     /// Release invalid contexts, aka worker node ids which are no longer valid in `ClusterManager`.
     #[named]
     pub(super) async fn release_invalid_contexts(&self) -> Result<Vec<HummockContextId>> {
@@ -112,7 +127,7 @@ impl HummockManager {
 
         let mut invalid_context_ids = vec![];
         for active_context_id in &active_context_ids {
-            if !self.check_context(*active_context_id).await? {
+            if !self.check_context_with_retry(*active_context_id).await? {
                 invalid_context_ids.push(*active_context_id);
             }
         }
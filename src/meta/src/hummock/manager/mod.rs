@@ -1543,6 +1543,7 @@ impl HummockManager {
             self.try_send_compaction_request(
                 compact_task.compaction_group_id,
                 compact_task::TaskType::Dynamic,
+                None,
             );
         }
 
@@ -1792,7 +1793,7 @@ impl HummockManager {
         if !self.env.opts.compaction_deterministic_test {
             // commit_epoch may contains SSTs from any compaction group
             for id in &modified_compaction_groups {
-                self.try_send_compaction_request(*id, compact_task::TaskType::Dynamic);
+                self.try_send_compaction_request(*id, compact_task::TaskType::Dynamic, None);
             }
             if !table_stats_change.is_empty() {
                 table_stats_change.retain(|table_id, _| {
@@ -2039,20 +2040,23 @@ impl HummockManager {
             return Ok(());
         }
         for compaction_group in compaction_groups {
-            self.try_send_compaction_request(compaction_group, compact_task::TaskType::Dynamic);
+            self.try_send_compaction_request(compaction_group, compact_task::TaskType::Dynamic, None);
         }
         Ok(())
     }
 
-    /// Sends a compaction request.
+    /// Sends a compaction request. `priority`, if given, lets the caller jump the group ahead of
+    /// others in [`Self::auto_pick_compaction_group_and_type`]; see
+    /// [`CompactionState::try_sched_compaction`].
     pub fn try_send_compaction_request(
         &self,
         compaction_group: CompactionGroupId,
         task_type: compact_task::TaskType,
+        priority: Option<u32>,
     ) -> bool {
         match self
             .compaction_state
-            .try_sched_compaction(compaction_group, task_type)
+            .try_sched_compaction(compaction_group, task_type, priority)
         {
             Ok(_) => true,
             Err(e) => {
@@ -2073,8 +2077,9 @@ impl HummockManager {
     ) -> Result<()> {
         let start_time = Instant::now();
 
-        // 1. Get idle compactor.
-        let compactor = match self.compactor_manager.next_compactor() {
+        // 1. Get idle compactor, waiting briefly in case one is about to register rather than
+        // failing immediately.
+        let compactor = match self.compactor_manager.wait_for_compactor().await {
             Some(compactor) => compactor,
             None => {
                 tracing::warn!("trigger_manual_compaction No compactor is available.");
@@ -2200,6 +2205,17 @@ impl HummockManager {
             .notify_frontend_without_version(Operation::Update, Info::HummockStats(stats.clone()));
     }
 
+    /// Starts the loop that periodically fires compaction-group triggers (dynamic, space-reclaim,
+    /// TTL, tombstone, group-split, ...) and dispatches them to `on_handle_trigger_multi_group`
+    /// and friends.
+    ///
+    /// Note: this crate has no `CompactionScheduler` type and no `pick_and_assign` method --
+    /// compaction-group dispatch lives entirely in this function and its trigger handlers below,
+    /// there's no separate scheduler component with its own shutdown arm to drain. On shutdown,
+    /// the `Either::Right` arm further down only breaks this loop once no trigger handler is in
+    /// flight: `futures::future::select` races `event_stream.next()` against the shutdown signal,
+    /// not the handler call itself, so a handler that already won the race always runs to
+    /// completion before the next iteration re-checks shutdown.
     #[named]
     pub fn hummock_timer_task(hummock_manager: Arc<Self>) -> (JoinHandle<()>, Sender<()>) {
         use futures::{FutureExt, StreamExt};
@@ -2891,8 +2907,26 @@ impl HummockManager {
     }
 
     async fn on_handle_trigger_multi_group(&self, task_type: compact_task::TaskType) {
-        for cg_id in self.compaction_group_ids().await {
-            if let Err(e) = self.compaction_state.try_sched_compaction(cg_id, task_type) {
+        let excluded_groups = &self.env.opts.periodic_compaction_schedule_excluded_groups;
+        let compaction_group_ids = self.compaction_group_ids().await;
+        let write_rates = self.group_write_rates(&compaction_group_ids).await;
+        for cg_id in compaction_group_ids {
+            if excluded_groups.contains(&cg_id) {
+                continue;
+            }
+            // Derive a priority from the group's recent write rate, so hot groups are scheduled
+            // ahead of cold ones by `auto_pick_compaction_group_and_type` instead of every
+            // periodically-triggered group competing on equal footing.
+            let priority = write_rates
+                .get(&cg_id)
+                .copied()
+                .unwrap_or(0)
+                .try_into()
+                .unwrap_or(u32::MAX);
+            if let Err(e) =
+                self.compaction_state
+                    .try_sched_compaction(cg_id, task_type, Some(priority))
+            {
                 tracing::warn!(
                     error = %e.as_report(),
                     "Failed to schedule {:?} compaction for compaction group {}",
@@ -2903,13 +2937,74 @@ impl HummockManager {
         }
     }
 
+    /// The [`SchedulingStrategy`] used by [`Self::auto_pick_compaction_group_and_type`].
+    fn compaction_scheduling_strategy(&self) -> SchedulingStrategy {
+        SchedulingStrategy::WriteRatePriority
+    }
+
+    /// Recent write rate (sum of sampled per-checkpoint write bytes, across each group's member
+    /// tables) for each of `group_ids`, keyed by group id. Used by
+    /// [`SchedulingStrategy::WriteRatePriority`] to prioritize hot groups; groups with no member
+    /// tables or no recorded throughput history score `0`.
+    #[named]
+    async fn group_write_rates(
+        &self,
+        group_ids: &[CompactionGroupId],
+    ) -> HashMap<CompactionGroupId, u64> {
+        let member_table_ids: HashMap<CompactionGroupId, Vec<u32>> = {
+            let versioning_guard = read_lock!(self, versioning).await;
+            group_ids
+                .iter()
+                .map(|group_id| {
+                    let member_table_ids = versioning_guard
+                        .current_version
+                        .levels
+                        .get(group_id)
+                        .map(|group| group.member_table_ids.clone())
+                        .unwrap_or_default();
+                    (*group_id, member_table_ids)
+                })
+                .collect()
+        };
+        let table_write_throughput = self.history_table_throughput.read();
+        member_table_ids
+            .into_iter()
+            .map(|(group_id, table_ids)| {
+                let write_rate = table_ids
+                    .iter()
+                    .filter_map(|table_id| table_write_throughput.get(table_id))
+                    .map(|history| history.iter().sum::<u64>())
+                    .sum();
+                (group_id, write_rate)
+            })
+            .collect()
+    }
+
     pub async fn auto_pick_compaction_group_and_type(
         &self,
     ) -> Option<(CompactionGroupId, compact_task::TaskType)> {
-        use rand::prelude::SliceRandom;
-        use rand::thread_rng;
         let mut compaction_group_ids = self.compaction_group_ids().await;
-        compaction_group_ids.shuffle(&mut thread_rng());
+
+        match self.compaction_scheduling_strategy() {
+            SchedulingStrategy::Random => {
+                use rand::prelude::SliceRandom;
+                use rand::thread_rng;
+                compaction_group_ids.shuffle(&mut thread_rng());
+            }
+            SchedulingStrategy::WriteRatePriority => {
+                let write_rates = self.group_write_rates(&compaction_group_ids).await;
+                compaction_group_ids.sort_by_key(|group_id| {
+                    std::cmp::Reverse(write_rates.get(group_id).copied().unwrap_or(0))
+                });
+            }
+        }
+
+        // An explicit priority given to `try_sched_compaction` overrides the strategy above;
+        // groups without one keep their strategy-determined relative order, since `sort_by_key`
+        // is stable.
+        compaction_group_ids.sort_by_key(|group_id| {
+            std::cmp::Reverse(self.compaction_state.max_priority(*group_id))
+        });
 
         for cg_id in compaction_group_ids {
             if let Some(pick_type) = self.compaction_state.auto_pick_type(cg_id) {
@@ -3188,12 +3283,24 @@ impl HummockManager {
                                                         compactor.context_id(),
                                                     );
 
+                                                    hummock_manager.metrics
+                                                        .compaction_schedule_status
+                                                        .with_label_values(&["send_failure", &group.to_string()])
+                                                        .inc();
                                                     hummock_manager.compactor_manager.remove_compactor(context_id);
                                                     break;
                                                 }
+                                                hummock_manager.metrics
+                                                    .compaction_schedule_status
+                                                    .with_label_values(&["ok", &group.to_string()])
+                                                    .inc();
                                             }
                                             Ok(None) => {
                                                 // no compact_task to be picked
+                                                hummock_manager.metrics
+                                                    .compaction_schedule_status
+                                                    .with_label_values(&["no_task", &group.to_string()])
+                                                    .inc();
                                                 hummock_manager
                                                     .compaction_state
                                                     .unschedule(group, task_type);
@@ -3201,6 +3308,10 @@ impl HummockManager {
                                             }
                                             Err(err) => {
                                                 tracing::warn!(error = %err.as_report(), "Failed to get compaction task");
+                                                hummock_manager.metrics
+                                                    .compaction_schedule_status
+                                                    .with_label_values(&["pick_failure", &group.to_string()])
+                                                    .inc();
                                                 break;
                                             }
                                         };
@@ -3248,6 +3359,18 @@ impl HummockManager {
     }
 }
 
+/// Strategy [`HummockManager::auto_pick_compaction_group_and_type`] uses to choose which
+/// compaction group to schedule next when multiple groups have pending compaction work.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchedulingStrategy {
+    /// Pick uniformly at random among groups with pending work (the historical behavior).
+    Random,
+    /// Prefer the group with the highest recent write rate. Groups under heavy writes accumulate
+    /// compaction debt the fastest, so compacting them first keeps read amplification down on the
+    /// hottest data.
+    WriteRatePriority,
+}
+
 // This structure describes how hummock handles sst switching in a compaction group. A better sst cut will result in better data alignment, which in turn will improve the efficiency of the compaction.
 // By adopting certain rules, a better sst cut will lead to better data alignment and thus improve the efficiency of the compaction.
 pub enum TableAlignRule {
@@ -3444,23 +3567,39 @@ use crate::hummock::sequence::next_sstable_object_id;
 #[derive(Debug, Default)]
 pub struct CompactionState {
     scheduled: Mutex<HashSet<(CompactionGroupId, compact_task::TaskType)>>,
+    /// Explicit priority hints passed to [`Self::try_sched_compaction`], keyed by the same
+    /// `(group, task_type)` the dedup set uses. Consulted by
+    /// [`HummockManager::auto_pick_compaction_group_and_type`] so a caller with better
+    /// information than [`SchedulingStrategy`] (e.g. a caller that knows a group's pending ratio)
+    /// can still jump the queue. Absent entries default to priority `0`.
+    priorities: Mutex<HashMap<(CompactionGroupId, compact_task::TaskType), u32>>,
 }
 
 impl CompactionState {
     pub fn new() -> Self {
         Self {
             scheduled: Default::default(),
+            priorities: Default::default(),
         }
     }
 
-    /// Enqueues only if the target is not yet in queue.
+    /// Enqueues only if the target is not yet in queue. `priority`, if given, is recorded (taking
+    /// the max with any previously recorded priority for this `(group, task_type)`) regardless of
+    /// whether the target was already queued, so a higher-priority request can promote an
+    /// already-pending one.
     pub fn try_sched_compaction(
         &self,
         compaction_group: CompactionGroupId,
         task_type: TaskType,
+        priority: Option<u32>,
     ) -> std::result::Result<bool, SendError<CompactionRequestChannelItem>> {
-        let mut guard = self.scheduled.lock();
         let key = (compaction_group, task_type);
+        if let Some(priority) = priority {
+            let mut priorities = self.priorities.lock();
+            let entry = priorities.entry(key).or_insert(0);
+            *entry = (*entry).max(priority);
+        }
+        let mut guard = self.scheduled.lock();
         if guard.contains(&key) {
             return Ok(false);
         }
@@ -3473,7 +3612,21 @@ impl CompactionState {
         compaction_group: CompactionGroupId,
         task_type: compact_task::TaskType,
     ) {
-        self.scheduled.lock().remove(&(compaction_group, task_type));
+        let key = (compaction_group, task_type);
+        self.scheduled.lock().remove(&key);
+        self.priorities.lock().remove(&key);
+    }
+
+    /// The highest priority explicitly given to `group` across its pending task types via
+    /// [`Self::try_sched_compaction`], or `0` if none was given.
+    fn max_priority(&self, group: CompactionGroupId) -> u32 {
+        self.priorities
+            .lock()
+            .iter()
+            .filter(|((g, _), _)| *g == group)
+            .map(|(_, priority)| *priority)
+            .max()
+            .unwrap_or(0)
     }
 
     pub fn auto_pick_type(&self, group: CompactionGroupId) -> Option<TaskType> {
@@ -483,6 +483,39 @@ async fn test_context_id_validation() {
     hummock_manager.pin_version(context_id).await.unwrap();
 }
 
+#[tokio::test]
+#[cfg(feature = "failpoints")]
+async fn test_release_invalid_contexts_retries_transient_invalid() {
+    use crate::hummock::manager::read_lock;
+
+    let (_env, hummock_manager, _cluster_manager, worker_node) = setup_compute_env(80).await;
+    let context_id = worker_node.id;
+    hummock_manager.pin_version(context_id).await.unwrap();
+
+    // `context_id` is actually still valid, but `check_context` reports it as transiently
+    // invalid on its first call.
+    fail::cfg("fp_check_context_transient_invalid", "return").unwrap();
+    tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        fail::remove("fp_check_context_transient_invalid");
+    });
+
+    let invalid_context_ids = hummock_manager.release_invalid_contexts().await.unwrap();
+
+    // The retry inside `release_invalid_contexts` re-checks after the failpoint clears, so the
+    // still-valid context is retained rather than released.
+    assert!(invalid_context_ids.is_empty());
+
+    #[function_name::named]
+    async fn is_pinned(hummock_manager: &HummockManagerRef, context_id: HummockContextId) -> bool {
+        read_lock!(hummock_manager, versioning)
+            .await
+            .pinned_versions
+            .contains_key(&context_id)
+    }
+    assert!(is_pinned(&hummock_manager, context_id).await);
+}
+
 #[tokio::test]
 async fn test_hummock_manager_basic() {
     let (_env, hummock_manager, cluster_manager, worker_node) = setup_compute_env(1).await;
@@ -2188,3 +2221,194 @@ async fn test_gc_stats() {
     );
     assert_eq_gc_stats(6, 3, 0, 0, 2, 4);
 }
+
+#[tokio::test]
+async fn test_list_assigned_tasks_number() {
+    use risingwave_pb::hummock::CompactTaskAssignment;
+
+    use crate::hummock::manager::write_lock;
+
+    let (_env, hummock_manager, _cluster_manager, _worker_node) = setup_compute_env(80).await;
+    assert!(hummock_manager
+        .list_assigned_tasks_number()
+        .await
+        .is_empty());
+
+    #[function_name::named]
+    async fn insert_fake_assignment(
+        hummock_manager: &HummockManagerRef,
+        task_id: u64,
+        context_id: HummockContextId,
+    ) {
+        write_lock!(hummock_manager, compaction)
+            .await
+            .compact_task_assignment
+            .insert(
+                task_id,
+                CompactTaskAssignment {
+                    compact_task: None,
+                    context_id,
+                },
+            );
+    }
+
+    insert_fake_assignment(&hummock_manager, 1, 100).await;
+    insert_fake_assignment(&hummock_manager, 2, 100).await;
+    insert_fake_assignment(&hummock_manager, 3, 200).await;
+
+    let assigned_tasks_number = hummock_manager.list_assigned_tasks_number().await;
+    assert_eq!(assigned_tasks_number.len(), 2);
+    assert_eq!(assigned_tasks_number[&100], 2);
+    assert_eq!(assigned_tasks_number[&200], 1);
+}
+
+#[tokio::test]
+async fn test_on_handle_trigger_multi_group_respects_excluded_groups() {
+    use std::time::Duration;
+
+    use risingwave_pb::hummock::compact_task::TaskType;
+
+    use crate::hummock::CompactorManager;
+    use crate::manager::{ClusterManager, FragmentManager, MetaOpts};
+
+    let excluded_group: CompactionGroupId = StaticCompactionGroupId::MaterializedView.into();
+    let included_group: CompactionGroupId = StaticCompactionGroupId::StateDefault.into();
+
+    let mut opts = MetaOpts::test(false);
+    opts.periodic_compaction_schedule_excluded_groups = vec![excluded_group];
+    let env = MetaSrvEnv::for_test_opts(Arc::new(opts)).await;
+    let cluster_manager = Arc::new(
+        ClusterManager::new(env.clone(), Duration::from_secs(1))
+            .await
+            .unwrap(),
+    );
+    let fragment_manager = Arc::new(FragmentManager::new(env.clone()).await.unwrap());
+    let compactor_manager = Arc::new(CompactorManager::for_test());
+    let (compactor_streams_change_tx, _compactor_streams_change_rx) =
+        tokio::sync::mpsc::unbounded_channel();
+    let hummock_manager = HummockManager::with_config(
+        env.clone(),
+        cluster_manager,
+        fragment_manager,
+        Arc::new(MetaMetrics::default()),
+        compactor_manager,
+        CompactionConfigBuilder::new().build(),
+        compactor_streams_change_tx,
+    )
+    .await;
+
+    hummock_manager
+        .on_handle_trigger_multi_group(TaskType::Dynamic)
+        .await;
+
+    assert_eq!(
+        hummock_manager
+            .compaction_state
+            .auto_pick_type(included_group),
+        Some(TaskType::Dynamic)
+    );
+    assert_eq!(
+        hummock_manager
+            .compaction_state
+            .auto_pick_type(excluded_group),
+        None
+    );
+
+    // Excluded groups can still be scheduled on demand.
+    hummock_manager
+        .compaction_state
+        .try_sched_compaction(excluded_group, TaskType::Dynamic, None)
+        .unwrap();
+    assert_eq!(
+        hummock_manager
+            .compaction_state
+            .auto_pick_type(excluded_group),
+        Some(TaskType::Dynamic)
+    );
+}
+
+#[tokio::test]
+async fn test_auto_pick_compaction_group_prefers_higher_write_rate() {
+    use std::collections::VecDeque;
+
+    use risingwave_pb::hummock::compact_task::TaskType;
+
+    use crate::hummock::manager::write_lock;
+
+    let (_env, hummock_manager, _cluster_manager, _worker_node) = setup_compute_env(80).await;
+
+    let cold_group: CompactionGroupId = StaticCompactionGroupId::StateDefault.into();
+    let hot_group: CompactionGroupId = StaticCompactionGroupId::MaterializedView.into();
+    let cold_table_id: u32 = 1;
+    let hot_table_id: u32 = 2;
+
+    #[function_name::named]
+    async fn add_member_table(
+        hummock_manager: &HummockManagerRef,
+        group_id: CompactionGroupId,
+        table_id: u32,
+    ) {
+        write_lock!(hummock_manager, versioning)
+            .await
+            .current_version
+            .levels
+            .get_mut(&group_id)
+            .unwrap()
+            .member_table_ids
+            .push(table_id);
+    }
+
+    add_member_table(&hummock_manager, cold_group, cold_table_id).await;
+    add_member_table(&hummock_manager, hot_group, hot_table_id).await;
+
+    hummock_manager
+        .history_table_throughput
+        .write()
+        .insert(cold_table_id, VecDeque::from([10]));
+    hummock_manager
+        .history_table_throughput
+        .write()
+        .insert(hot_table_id, VecDeque::from([1000]));
+
+    hummock_manager
+        .compaction_state
+        .try_sched_compaction(cold_group, TaskType::Dynamic, None)
+        .unwrap();
+    hummock_manager
+        .compaction_state
+        .try_sched_compaction(hot_group, TaskType::Dynamic, None)
+        .unwrap();
+
+    let (picked_group, _) = hummock_manager
+        .auto_pick_compaction_group_and_type()
+        .await
+        .unwrap();
+    assert_eq!(picked_group, hot_group);
+}
+
+#[tokio::test]
+async fn test_auto_pick_compaction_group_prefers_explicit_priority() {
+    use risingwave_pb::hummock::compact_task::TaskType;
+
+    let (_env, hummock_manager, _cluster_manager, _worker_node) = setup_compute_env(80).await;
+
+    let low_priority_group: CompactionGroupId = StaticCompactionGroupId::StateDefault.into();
+    let high_priority_group: CompactionGroupId = StaticCompactionGroupId::MaterializedView.into();
+
+    // Neither group has any recorded write throughput, so without an explicit priority they'd
+    // tie under `SchedulingStrategy::WriteRatePriority` and fall back to iteration order.
+    hummock_manager
+        .compaction_state
+        .try_sched_compaction(low_priority_group, TaskType::Dynamic, Some(1))
+        .unwrap();
+    hummock_manager
+        .compaction_state
+        .try_sched_compaction(high_priority_group, TaskType::Dynamic, Some(10))
+        .unwrap();
+
+    let (picked_group, _) = hummock_manager
+        .auto_pick_compaction_group_and_type()
+        .await
+        .unwrap();
+    assert_eq!(picked_group, high_priority_group);
+}
@@ -134,6 +134,9 @@ pub struct MetaOpts {
     pub enable_committed_sst_sanity_check: bool,
     /// Schedule compaction for all compaction groups with this interval.
     pub periodic_compaction_interval_sec: u64,
+    /// Compaction groups excluded from the periodic compaction triggers. They can still be
+    /// scheduled on demand (e.g. via `try_sched_compaction`).
+    pub periodic_compaction_schedule_excluded_groups: Vec<u64>,
     /// Interval of reporting the number of nodes in the cluster.
     pub node_num_monitor_interval_sec: u64,
 
@@ -244,6 +247,7 @@ impl MetaOpts {
             collect_gc_watermark_spin_interval_sec: 5,
             enable_committed_sst_sanity_check: false,
             periodic_compaction_interval_sec: 60,
+            periodic_compaction_schedule_excluded_groups: vec![],
             node_num_monitor_interval_sec: 10,
             prometheus_endpoint: None,
             prometheus_selector: None,
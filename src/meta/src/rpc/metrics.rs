@@ -156,6 +156,9 @@ pub struct MetaMetrics {
 
     pub compaction_event_consumed_latency: Histogram,
     pub compaction_event_loop_iteration_latency: Histogram,
+    /// Outcome of each attempt to pick and dispatch a compaction task to a compactor, labeled by
+    /// outcome and compaction group, so operators can see assign/send failure rates over time.
+    pub compaction_schedule_status: IntCounterVec,
 
     /// ********************************** Object Store ************************************
     // Object store related metrics (for backup/restore and version checkpoint)
@@ -619,6 +622,14 @@ impl MetaMetrics {
         let compaction_event_loop_iteration_latency =
             register_histogram_with_registry!(opts, registry).unwrap();
 
+        let compaction_schedule_status = register_int_counter_vec_with_registry!(
+            "storage_compaction_schedule_status",
+            "Outcome of each attempt to pick and dispatch a compaction task, by outcome and compaction group",
+            &["status", "group"],
+            registry
+        )
+        .unwrap();
+
         Self {
             grpc_latency,
             barrier_latency,
@@ -682,6 +693,7 @@ impl MetaMetrics {
             branched_sst_count,
             compaction_event_consumed_latency,
             compaction_event_loop_iteration_latency,
+            compaction_schedule_status,
         }
     }
 
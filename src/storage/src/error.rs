@@ -55,6 +55,17 @@ pub enum ErrorKind {
         #[from]
         Box<MemTableError>,
     ),
+
+    #[error(
+        "scan stalled: no row received within {timeout:?} after {partial_row_count} row(s) collected"
+    )]
+    ScanStalled {
+        timeout: std::time::Duration,
+        partial_row_count: usize,
+    },
+
+    #[error("row does not match target schema: {0}")]
+    SchemaMismatch(String),
 }
 
 pub type StorageResult<T> = std::result::Result<T, StorageError>;
@@ -327,6 +327,7 @@ mod tests {
     use crate::hummock::iterator::test_utils::{
         default_builder_opt_for_test, gen_iterator_test_sstable_base,
         gen_iterator_test_sstable_from_kv_pair, gen_iterator_test_sstable_with_incr_epoch,
+        gen_iterator_test_sstable_with_key_epochs_overlap,
         gen_iterator_test_sstable_with_range_tombstones, iterator_test_bytes_key_of,
         iterator_test_bytes_key_of_epoch, iterator_test_bytes_user_key_of, iterator_test_value_of,
         mock_sstable_store, TEST_KEYS_COUNT,
@@ -508,6 +509,40 @@ mod tests {
         assert!(!ui.is_valid());
     }
 
+    #[tokio::test]
+    async fn test_multi_epoch_overlap() {
+        let sstable_store = mock_sstable_store();
+
+        // a single key versioned at several epochs within one SST
+        let table = gen_iterator_test_sstable_with_key_epochs_overlap(
+            0,
+            1,
+            vec![100, 200, 300],
+            sstable_store.clone(),
+        )
+        .await;
+
+        let read_options = Arc::new(SstableIteratorReadOptions::default());
+        let iters = vec![SstableIterator::create(
+            table,
+            sstable_store.clone(),
+            read_options,
+        )];
+
+        let mi = MergeIterator::new(iters);
+        let mut ui = UserIterator::for_test(mi, (Unbounded, Unbounded));
+        ui.rewind().await.unwrap();
+
+        // newer-epoch value shadows older ones on read
+        let k = ui.key();
+        let v = ui.value();
+        assert_eq!(k, iterator_test_bytes_key_of_epoch(1, 300).to_ref());
+        assert_eq!(v, &Bytes::from(iterator_test_value_of(300)));
+
+        ui.next().await.unwrap();
+        assert!(!ui.is_valid());
+    }
+
     async fn generate_test_data(
         sstable_store: SstableStoreRef,
         range_tombstones: Vec<(usize, usize, u64)>,
@@ -122,6 +122,19 @@ pub fn iterator_test_bytes_key_of_epoch(idx: usize, epoch: HummockEpoch) -> Full
     iterator_test_key_of_epoch(idx, test_epoch(epoch)).into_bytes()
 }
 
+/// Generates the same logical key at every epoch in `epochs`, like
+/// `iterator_test_key_of_epoch` but for multiple versions at once. Useful for compaction
+/// correctness tests that need to exercise version ordering and GC of a single key.
+pub fn iterator_test_key_of_epochs(
+    idx: usize,
+    epochs: impl IntoIterator<Item = HummockEpoch>,
+) -> Vec<FullKey<Vec<u8>>> {
+    epochs
+        .into_iter()
+        .map(|epoch| iterator_test_key_of_epoch(idx, epoch))
+        .collect_vec()
+}
+
 /// The value of an index, like `value_test_00002` without value meta
 pub fn iterator_test_value_of(idx: usize) -> Vec<u8> {
     format!("value_test_{:05}", idx).as_bytes().to_vec()
@@ -201,6 +214,31 @@ pub async fn gen_iterator_test_sstable_from_kv_pair(
     .await
 }
 
+/// Generates an SST containing a single logical key (`idx`) versioned at every epoch in
+/// `epochs`, with the value at each epoch equal to `iterator_test_value_of(epoch as usize)`.
+/// This lets tests verify that reads observe the value written at the newest epoch and that
+/// compaction correctly dedups older versions.
+pub async fn gen_iterator_test_sstable_with_key_epochs_overlap(
+    object_id: HummockSstableObjectId,
+    idx: usize,
+    epochs: Vec<HummockEpoch>,
+    sstable_store: SstableStoreRef,
+) -> TableHolder {
+    let mut epochs = epochs;
+    // SST entries must be sorted by key, with greater epochs coming first.
+    epochs.sort_by(|a, b| b.cmp(a));
+    let kv_pairs = epochs
+        .into_iter()
+        .map(|epoch| {
+            (
+                iterator_test_key_of_epoch(idx, test_epoch(epoch)),
+                HummockValue::put(iterator_test_value_of(epoch as usize)),
+            )
+        })
+        .collect_vec();
+    gen_test_sstable(default_builder_opt_for_test(), object_id, kv_pairs, sstable_store).await
+}
+
 // key=[idx, epoch], value
 pub async fn gen_iterator_test_sstable_with_range_tombstones(
     object_id: HummockSstableObjectId,
@@ -516,16 +516,16 @@ impl<S: StateStore, SD: ValueRowSerde> StorageTableInner<S, SD> {
         Ok(iter)
     }
 
-    /// Iterates on the table with the given prefix of the pk in `pk_prefix` and the range bounds.
-    async fn iter_with_pk_bounds(
+    /// Encodes `pk_prefix` and `range_bounds` into a `(prefix_hint, encoded_key_range,
+    /// vnode_hint)` triple, as consumed by [`Self::iter_with_encoded_key_range`]. Factored out of
+    /// [`Self::iter_with_pk_bounds`] so [`Self::count_range`] can reuse the same pk-bound encoding
+    /// without also paying for row construction.
+    // TODO: directly use `prefixed_range`.
+    fn encode_pk_bounds(
         &self,
-        epoch: HummockReadEpoch,
         pk_prefix: impl Row,
         range_bounds: impl RangeBounds<OwnedRow>,
-        ordered: bool,
-        prefetch_options: PrefetchOptions,
-    ) -> StorageResult<StorageTableInnerIter<S, SD>> {
-        // TODO: directly use `prefixed_range`.
+    ) -> StorageResult<(Option<Bytes>, (Bound<Bytes>, Bound<Bytes>), Option<VirtualNode>)> {
         fn serialize_pk_bound(
             pk_serializer: &OrderedRowSerde,
             pk_prefix: impl Row,
@@ -625,17 +625,88 @@ impl<S: StateStore, SD: ValueRowSerde> StorageTableInner<S, SD> {
             pk_prefix_indices
         );
 
+        let vnode_hint = self.distribution.try_compute_vnode_by_pk_prefix(pk_prefix);
+        Ok((prefix_hint, (start_key, end_key), vnode_hint))
+    }
+
+    /// Iterates on the table with the given prefix of the pk in `pk_prefix` and the range bounds.
+    async fn iter_with_pk_bounds(
+        &self,
+        epoch: HummockReadEpoch,
+        pk_prefix: impl Row,
+        range_bounds: impl RangeBounds<OwnedRow>,
+        ordered: bool,
+        prefetch_options: PrefetchOptions,
+    ) -> StorageResult<StorageTableInnerIter<S, SD>> {
+        let (prefix_hint, encoded_key_range, vnode_hint) =
+            self.encode_pk_bounds(pk_prefix, range_bounds)?;
+
         self.iter_with_encoded_key_range(
             prefix_hint,
-            (start_key, end_key),
+            encoded_key_range,
             epoch,
-            self.distribution.try_compute_vnode_by_pk_prefix(pk_prefix),
+            vnode_hint,
             ordered,
             prefetch_options,
         )
         .await
     }
 
+    /// Counts the number of rows in the given pk range, based on a snapshot corresponding to the
+    /// given `epoch`, without deserializing any row's value. `SELECT count(*)` (or a count over a
+    /// range) would otherwise pay for building and immediately discarding every row via
+    /// [`Self::batch_iter_with_pk_bounds`]; this short-circuits that by counting raw keys
+    /// directly off [`StateStoreRead::iter`](crate::store::StateStoreRead::iter).
+    pub async fn count_range(
+        &self,
+        epoch: HummockReadEpoch,
+        range_bounds: impl RangeBounds<OwnedRow>,
+    ) -> StorageResult<u64> {
+        let (prefix_hint, encoded_key_range, vnode_hint) =
+            self.encode_pk_bounds(row::empty(), range_bounds)?;
+
+        let cache_policy = match (
+            encoded_key_range.start_bound(),
+            encoded_key_range.end_bound(),
+        ) {
+            (Unbounded, _) | (_, Unbounded) => CachePolicy::Fill(CacheContext::LruPriorityLow),
+            _ => CachePolicy::Fill(CacheContext::Default),
+        };
+        let vnodes = match vnode_hint {
+            Some(vnode) => Either::Left(std::iter::once(vnode)),
+            None => Either::Right(self.distribution.vnodes().iter_vnodes()),
+        };
+        let read_backup = matches!(epoch, HummockReadEpoch::Backup(_));
+
+        self.store.try_wait_epoch(epoch).await?;
+        let raw_epoch = epoch.get_epoch();
+
+        let mut count = 0u64;
+        for vnode in vnodes {
+            let table_key_range = prefixed_range_with_vnode(encoded_key_range.clone(), vnode);
+            let read_options = ReadOptions {
+                prefix_hint: prefix_hint.clone(),
+                retention_seconds: self.table_option.retention_seconds,
+                table_id: self.table_id,
+                read_version_from_backup: read_backup,
+                cache_policy,
+                ..Default::default()
+            };
+            let mut iter = self
+                .store
+                .iter(table_key_range, raw_epoch, read_options)
+                .await?;
+            while iter.try_next().await?.is_some() {
+                count += 1;
+            }
+        }
+
+        // See the comment on the same call in `StorageTableInnerIterInner::new`.
+        self.store.validate_read_epoch(epoch)?;
+
+        Ok(count)
+    }
+
     /// Construct a [`StorageTableInnerIter`] for batch executors.
     /// Differs from the streaming one, this iterator will wait for the epoch before iteration
     pub async fn batch_iter_with_pk_bounds(
@@ -660,6 +731,28 @@ impl<S: StateStore, SD: ValueRowSerde> StorageTableInner<S, SD> {
         self.batch_iter_with_pk_bounds(epoch, row::empty(), .., ordered, prefetch_options)
             .await
     }
+
+    /// Iterates on all rows of a single `vnode`, yielding [`KeyedRow`]s directly (unlike
+    /// [`TableIter::next_row`](super::TableIter::next_row), which discards the key). This lets a
+    /// repartitioner read [`KeyedRow::vnode`] off each row to route it without recomputing the
+    /// vnode hash.
+    pub async fn batch_iter_vnode(
+        &self,
+        epoch: HummockReadEpoch,
+        vnode: VirtualNode,
+        ordered: bool,
+        prefetch_options: PrefetchOptions,
+    ) -> StorageResult<StorageTableInnerIter<S, SD>> {
+        self.iter_with_encoded_key_range(
+            None,
+            (Unbounded, Unbounded),
+            epoch,
+            Some(vnode),
+            ordered,
+            prefetch_options,
+        )
+        .await
+    }
 }
 
 /// [`StorageTableInnerIterInner`] iterates on the storage table.
@@ -787,3 +880,60 @@ impl<S: StateStore, SD: ValueRowSerde> StorageTableInnerIterInner<S, SD> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::hash::VirtualNode;
+    use risingwave_common::types::DataType;
+    use risingwave_common::util::epoch::test_epoch;
+
+    use super::*;
+    use crate::memory::MemoryStateStore;
+    use crate::store::{StateStoreWrite, WriteOptions};
+    use crate::storage_value::StorageValue;
+
+    #[tokio::test]
+    async fn test_count_range_does_not_deserialize_values() {
+        let store = MemoryStateStore::new();
+        let table_id = TableId::new(233);
+        let column_descs = vec![
+            ColumnDesc::unnamed(ColumnId::from(0), DataType::Int32),
+            ColumnDesc::unnamed(ColumnId::from(1), DataType::Int32),
+        ];
+        let order_types = vec![OrderType::ascending()];
+        let pk_indices = vec![0];
+        let value_indices = vec![1];
+        let table = StorageTable::for_test(
+            store.clone(),
+            table_id,
+            column_descs,
+            order_types,
+            pk_indices,
+            value_indices,
+        );
+
+        let epoch = test_epoch(1);
+        // Not a valid row encoding: if `count_range` ever deserialized these, it would surface
+        // the decode error instead of a count.
+        let garbage_value = Bytes::from_static(b"not a valid encoded row");
+        let batch = (0..3)
+            .map(|i| {
+                let key = serialize_pk_with_vnode(
+                    OwnedRow::new(vec![Some((i as i32).into())]),
+                    table.pk_serializer(),
+                    VirtualNode::ZERO,
+                );
+                (key, StorageValue::new_put(garbage_value.clone()))
+            })
+            .collect();
+        store
+            .ingest_batch(batch, vec![], WriteOptions { epoch, table_id })
+            .unwrap();
+
+        let count = table
+            .count_range(HummockReadEpoch::Committed(epoch), ..)
+            .await
+            .unwrap();
+        assert_eq!(count, 3);
+    }
+}
@@ -17,7 +17,7 @@ use std::collections::BinaryHeap;
 use std::error::Error;
 
 use futures::{Stream, StreamExt};
-use futures_async_stream::try_stream;
+use futures_async_stream::{for_await, try_stream};
 
 use super::KeyedRow;
 
@@ -76,9 +76,119 @@ where
     }
 }
 
+struct TaggedNode<Id, K: AsRef<[u8]>, S> {
+    shard_id: Id,
+    stream: S,
+
+    /// See [`Node::peeked`].
+    peeked: KeyedRow<K>,
+}
+
+impl<Id, K: AsRef<[u8]>, S> PartialEq for TaggedNode<Id, K, S> {
+    fn eq(&self, other: &Self) -> bool {
+        match self.peeked.key() == other.peeked.key() {
+            true => unreachable!("primary key from different iters should be unique"),
+            false => false,
+        }
+    }
+}
+impl<Id, K: AsRef<[u8]>, S> Eq for TaggedNode<Id, K, S> {}
+
+impl<Id, K: AsRef<[u8]>, S> PartialOrd for TaggedNode<Id, K, S> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<Id, K: AsRef<[u8]>, S> Ord for TaggedNode<Id, K, S> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // The heap is a max heap, so we need to reverse the order.
+        self.peeked.key().cmp(other.peeked.key()).reverse()
+    }
+}
+
+/// Like [`merge_sort`], but tags each yielded row with the id of the shard (the caller-chosen
+/// value paired with its stream in `streams`) it came from. Useful for diagnostics/validation
+/// code that needs to verify repartitioning correctness, e.g. after a scale operation. Additive
+/// and zero-cost when the tag is ignored: the merge logic and ordering are unchanged from
+/// [`merge_sort`], `shard_id` is simply carried alongside each row.
+#[try_stream(ok = (Id, KeyedRow<K>), error = E)]
+pub async fn merge_sort_tagged<'a, Id, K, E, R>(streams: Vec<(Id, R)>)
+where
+    Id: Copy + 'a,
+    K: AsRef<[u8]> + 'a,
+    E: Error + 'a,
+    R: Stream<Item = Result<KeyedRow<K>, E>> + 'a + Unpin,
+{
+    let mut heap = BinaryHeap::new();
+    for (shard_id, mut stream) in streams {
+        if let Some(peeked) = stream.next().await.transpose()? {
+            heap.push(TaggedNode {
+                shard_id,
+                stream,
+                peeked,
+            });
+        }
+    }
+    while let Some(mut node) = heap.peek_mut() {
+        yield match node.stream.next().await.transpose()? {
+            // There still remains data in the stream, take and update the peeked value.
+            Some(new_peeked) => {
+                let shard_id = node.shard_id;
+                (shard_id, std::mem::replace(&mut node.peeked, new_peeked))
+            }
+            // This stream is exhausted, remove it from the heap.
+            None => {
+                let TaggedNode {
+                    shard_id, peeked, ..
+                } = PeekMut::pop(node);
+                (shard_id, peeked)
+            }
+        };
+    }
+}
+
+/// Merges already-sorted `streams` according to `comparator` and yields only the smallest `n`
+/// rows overall, ordered globally across all shards. It stops pulling from every stream as soon as
+/// `n` rows have been yielded, so a small `LIMIT` over a large table reads only a few rows per
+/// stream.
+///
+/// `comparator` is applied directly to rows rather than assuming the table key's byte order, so
+/// callers can sort by a derived key (e.g. a secondary `ORDER BY` column) while still merging
+/// streams that only guarantee the table's own key order internally.
+#[try_stream(ok = KeyedRow<K>, error = E)]
+pub async fn merge_sort_top_n<'a, K, E, R, C>(mut streams: Vec<R>, n: usize, comparator: C)
+where
+    K: AsRef<[u8]> + 'a,
+    E: Error + 'a,
+    R: Stream<Item = Result<KeyedRow<K>, E>> + 'a + Unpin,
+    C: Fn(&KeyedRow<K>, &KeyedRow<K>) -> std::cmp::Ordering,
+{
+    let mut peeked = Vec::with_capacity(streams.len());
+    for stream in &mut streams {
+        peeked.push(stream.next().await.transpose()?);
+    }
+
+    for _ in 0..n {
+        let Some(min_idx) = peeked
+            .iter()
+            .enumerate()
+            .filter_map(|(i, row)| row.as_ref().map(|row| (i, row)))
+            .min_by(|(_, a), (_, b)| comparator(a, b))
+            .map(|(i, _)| i)
+        else {
+            // Every stream is exhausted; fewer than `n` rows exist in total.
+            break;
+        };
+        yield peeked[min_idx].take().unwrap();
+        peeked[min_idx] = streams[min_idx].next().await.transpose()?;
+    }
+    // Once `n` rows have been yielded, the remaining streams are simply dropped without being
+    // pulled from any further.
+}
+
 #[cfg(test)]
 mod tests {
-    use futures_async_stream::for_await;
     use risingwave_common::hash::VirtualNode;
     use risingwave_common::row::OwnedRow;
     use risingwave_common::types::ScalarImpl;
@@ -88,7 +198,11 @@ mod tests {
     use crate::error::StorageResult;
 
     fn gen_pk_and_row(i: u8) -> StorageResult<KeyedRow<Vec<u8>>> {
-        let mut key = VirtualNode::ZERO.to_be_bytes().to_vec();
+        gen_pk_and_row_with_vnode(VirtualNode::ZERO, i)
+    }
+
+    fn gen_pk_and_row_with_vnode(vnode: VirtualNode, i: u8) -> StorageResult<KeyedRow<Vec<u8>>> {
+        let mut key = vnode.to_be_bytes().to_vec();
         key.extend(vec![i]);
         Ok(KeyedRow::new(
             TableKey(key),
@@ -129,4 +243,102 @@ mod tests {
             assert_eq!(actual.into_owned_row(), expected.into_owned_row());
         }
     }
+
+    #[tokio::test]
+    async fn test_merge_sort_tagged() {
+        let streams = vec![
+            (
+                0u32,
+                futures::stream::iter(vec![
+                    gen_pk_and_row(0),
+                    gen_pk_and_row(3),
+                    gen_pk_and_row(6),
+                    gen_pk_and_row(9),
+                ]),
+            ),
+            (
+                1u32,
+                futures::stream::iter(vec![
+                    gen_pk_and_row(1),
+                    gen_pk_and_row(4),
+                    gen_pk_and_row(7),
+                    gen_pk_and_row(10),
+                ]),
+            ),
+            (
+                2u32,
+                futures::stream::iter(vec![
+                    gen_pk_and_row(2),
+                    gen_pk_and_row(5),
+                    gen_pk_and_row(8),
+                ]),
+            ),
+        ];
+
+        let merge_sorted = merge_sort_tagged(streams);
+
+        #[for_await]
+        for (i, result) in merge_sorted.enumerate() {
+            let expected = gen_pk_and_row(i as u8).unwrap();
+            let (shard_id, actual) = result.unwrap();
+            assert_eq!(shard_id, i as u32 % 3);
+            assert_eq!(actual.key(), expected.key());
+            assert_eq!(actual.into_owned_row(), expected.into_owned_row());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_merge_sort_top_n() {
+        let streams = vec![
+            futures::stream::iter(vec![
+                gen_pk_and_row(0),
+                gen_pk_and_row(3),
+                gen_pk_and_row(6),
+                gen_pk_and_row(9),
+            ]),
+            futures::stream::iter(vec![
+                gen_pk_and_row(1),
+                gen_pk_and_row(4),
+                gen_pk_and_row(7),
+                gen_pk_and_row(10),
+            ]),
+            futures::stream::iter(vec![
+                gen_pk_and_row(2),
+                gen_pk_and_row(5),
+                gen_pk_and_row(8),
+            ]),
+        ];
+
+        let top_5 = merge_sort_top_n(streams, 5, |a, b| a.key().cmp(b.key()));
+
+        let mut rows = vec![];
+        #[for_await]
+        for result in top_5 {
+            rows.push(result.unwrap());
+        }
+        // Only the smallest 5 keys across all three streams, in order.
+        assert_eq!(rows.len(), 5);
+        for (i, row) in rows.iter().enumerate() {
+            let expected = gen_pk_and_row(i as u8).unwrap();
+            assert_eq!(row.key(), expected.key());
+            assert_eq!(row.row(), expected.row());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_merge_sort_top_n_fewer_rows_than_n() {
+        let streams = vec![
+            futures::stream::iter(vec![gen_pk_and_row(0), gen_pk_and_row(2)]),
+            futures::stream::iter(vec![gen_pk_and_row(1)]),
+        ];
+
+        let top_n = merge_sort_top_n(streams, 10, |a, b| a.key().cmp(b.key()));
+
+        let mut rows = vec![];
+        #[for_await]
+        for result in top_n {
+            rows.push(result.unwrap());
+        }
+        assert_eq!(rows.len(), 3);
+    }
 }
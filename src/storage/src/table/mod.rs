@@ -16,19 +16,22 @@ pub mod batch_table;
 pub mod merge_sort;
 
 use std::ops::Deref;
+use std::time::Duration;
 
 use bytes::Bytes;
 use futures::{Stream, StreamExt};
+use futures_async_stream::try_stream;
 use risingwave_common::array::DataChunk;
 use risingwave_common::catalog::Schema;
 pub use risingwave_common::hash::table_distribution::*;
 use risingwave_common::hash::VirtualNode;
-use risingwave_common::row::{OwnedRow, Row};
+use risingwave_common::row::{OwnedRow, Row, RowExt};
+use risingwave_common::types::{literal_type_match, DatumRef};
 use risingwave_common::util::chunk_coalesce::DataChunkBuilder;
 use risingwave_common::util::iter_util::ZipEqFast;
 use risingwave_hummock_sdk::key::TableKey;
 
-use crate::error::StorageResult;
+use crate::error::{StorageError, StorageResult};
 
 // TODO: GAT-ify this trait or remove this trait
 #[async_trait::async_trait]
@@ -36,6 +39,48 @@ pub trait TableIter: Send {
     async fn next_row(&mut self) -> StorageResult<Option<OwnedRow>>;
 }
 
+/// Wraps a [`TableIter`] into a `Stream` of [`DataChunk`]s, using the same chunk-building logic
+/// as [`collect_data_chunk`], so batch executors that only have a `TableIter` (e.g. row-based
+/// in-memory sources) can be consumed the same way as executors reading a `KeyedRow` stream.
+/// Respects `chunk_size` like `collect_data_chunk` does, and simply stops (yielding nothing more)
+/// once `iter` is exhausted.
+#[try_stream(ok = DataChunk, error = StorageError)]
+pub async fn table_iter_to_chunk_stream<T: TableIter>(
+    mut iter: T,
+    schema: Schema,
+    chunk_size: Option<usize>,
+) {
+    loop {
+        let mut builders = schema.create_array_builders(chunk_size.unwrap_or(0));
+        let mut row_count = 0;
+        for _ in 0..chunk_size.unwrap_or(usize::MAX) {
+            match iter.next_row().await? {
+                Some(row) => {
+                    for (datum, builder) in row.iter().zip_eq_fast(builders.iter_mut()) {
+                        builder.append(datum);
+                    }
+                    row_count += 1;
+                }
+                None => break,
+            }
+        }
+
+        if row_count == 0 {
+            break;
+        }
+
+        let columns: Vec<_> = builders
+            .into_iter()
+            .map(|builder| builder.finish().into())
+            .collect();
+        yield DataChunk::new(columns, row_count);
+
+        if row_count < chunk_size.unwrap_or(usize::MAX) {
+            break;
+        }
+    }
+}
+
 pub async fn collect_data_chunk<E, S>(
     stream: &mut S,
     schema: &Schema,
@@ -43,11 +88,36 @@ pub async fn collect_data_chunk<E, S>(
 ) -> Result<Option<DataChunk>, E>
 where
     S: Stream<Item = Result<KeyedRow<Bytes>, E>> + Unpin,
+    E: From<StorageError>,
+{
+    collect_data_chunk_with_poll_timeout(stream, schema, chunk_size, None).await
+}
+
+/// Like [`collect_data_chunk`], but if `poll_timeout` is set, fails with a
+/// [`StorageError::scan_stalled`] instead of waiting forever when a single poll of `stream`
+/// exceeds it. This turns a stalled scan (e.g. a remote storage hiccup) into a clean query
+/// failure with a diagnostic, rather than an indefinite hang.
+pub async fn collect_data_chunk_with_poll_timeout<E, S>(
+    stream: &mut S,
+    schema: &Schema,
+    chunk_size: Option<usize>,
+    poll_timeout: Option<Duration>,
+) -> Result<Option<DataChunk>, E>
+where
+    S: Stream<Item = Result<KeyedRow<Bytes>, E>> + Unpin,
+    E: From<StorageError>,
 {
     let mut builders = schema.create_array_builders(chunk_size.unwrap_or(0));
     let mut row_count = 0;
     for _ in 0..chunk_size.unwrap_or(usize::MAX) {
-        match stream.next().await.transpose()? {
+        let next = match poll_timeout {
+            Some(poll_timeout) => match tokio::time::timeout(poll_timeout, stream.next()).await {
+                Ok(next) => next,
+                Err(_) => return Err(StorageError::scan_stalled(poll_timeout, row_count).into()),
+            },
+            None => stream.next().await,
+        };
+        match next.transpose()? {
             Some(row) => {
                 for (datum, builder) in row.iter().zip_eq_fast(builders.iter_mut()) {
                     builder.append(datum);
@@ -74,7 +144,72 @@ where
     }
 }
 
+/// Like [`collect_data_chunk`], but additionally deduplicates rows that share the same
+/// [`KeyedRow::key`], keeping only the latest one seen. This assumes the input `stream` is
+/// key-ordered (e.g. a merge-sorted scan across shards, see [`merge_sort`]) so duplicate keys are
+/// always adjacent; it does not do a full, unordered dedup. [`merge_sort`] itself passes rows with
+/// equal keys through unchanged; this function drops such duplicates at the chunk-building layer.
+pub async fn collect_data_chunk_dedup<E, S>(
+    stream: &mut S,
+    schema: &Schema,
+    chunk_size: Option<usize>,
+) -> Result<Option<DataChunk>, E>
+where
+    S: Stream<Item = Result<KeyedRow<Bytes>, E>> + Unpin,
+{
+    let mut builders = schema.create_array_builders(chunk_size.unwrap_or(0));
+    let mut row_count = 0;
+    // The last row we've seen but not yet appended to `builders`, so that if the next row turns
+    // out to share its key, we can overwrite it with the newer one before it's ever appended.
+    let mut pending: Option<KeyedRow<Bytes>> = None;
+
+    for _ in 0..chunk_size.unwrap_or(usize::MAX) {
+        match stream.next().await.transpose()? {
+            Some(row) => {
+                if let Some(prev) = &pending
+                    && prev.key() != row.key()
+                {
+                    let prev = pending.take().unwrap();
+                    for (datum, builder) in prev.iter().zip_eq_fast(builders.iter_mut()) {
+                        builder.append(datum);
+                    }
+                    row_count += 1;
+                }
+                pending = Some(row);
+            }
+            None => break,
+        }
+    }
+    if let Some(prev) = pending {
+        for (datum, builder) in prev.iter().zip_eq_fast(builders.iter_mut()) {
+            builder.append(datum);
+        }
+        row_count += 1;
+    }
+
+    let chunk = {
+        let columns: Vec<_> = builders
+            .into_iter()
+            .map(|builder| builder.finish().into())
+            .collect();
+        DataChunk::new(columns, row_count)
+    };
+
+    if chunk.cardinality() == 0 {
+        Ok(None)
+    } else {
+        Ok(Some(chunk))
+    }
+}
+
 /// Collects data chunks from stream of rows.
+///
+/// Note: this crate has no `deserialize_log_stream`/`ChangeLogValue` (there's no changelog-row
+/// abstraction here at all — CDC-style sources are parsed directly into `StreamChunk`s upstream
+/// in `risingwave_connector`). This function is the closest existing building block for batching
+/// a row-at-a-time stream into chunks: it already accumulates rows into a caller-supplied
+/// `DataChunkBuilder` of a fixed size and flushes a final partial chunk via `consume_all` once the
+/// stream ends.
 pub async fn collect_data_chunk_with_builder<E, S, R>(
     stream: &mut S,
     builder: &mut DataChunkBuilder,
@@ -130,9 +265,52 @@ impl<T: AsRef<[u8]>> KeyedRow<T> {
         &self.row
     }
 
+    /// Looks up `name` in `schema` and returns the datum at the resolved column index, or `None`
+    /// if `schema` has no column with that name. For callers that know the schema and want
+    /// by-name access for clarity instead of tracking positional indices themselves.
+    pub fn datum_by_name(&self, schema: &Schema, name: &str) -> Option<DatumRef<'_>> {
+        let index = schema.fields().iter().position(|f| f.name == name)?;
+        Some(self.row.datum_at(index))
+    }
+
     pub fn into_parts(self) -> (TableKey<T>, OwnedRow) {
         (self.vnode_prefixed_key, self.row)
     }
+
+    /// Borrows a subset of this row's columns by `indices`, without cloning any cell. For hot
+    /// scan paths that only need to peek at a few columns (e.g. key columns) instead of
+    /// materializing the whole row via [`Self::into_owned_row`]. See [`RowExt::project`].
+    ///
+    /// # Panics
+    /// Panics if `indices` contains an out-of-bounds index.
+    pub fn project_borrowed(&self, indices: &[usize]) -> impl Row + '_ {
+        (&self.row).project(indices)
+    }
+
+    /// Like [`Self::into_owned_row`], but validates every column's value against `target_schema`
+    /// first and returns an error on the first mismatch, instead of silently handing back a row
+    /// that may no longer conform to it. Useful when reading a table whose on-disk schema may lag
+    /// behind a newer frontend schema, where trusting the stored row's shape could otherwise cause
+    /// a panic much later, deep inside an operator that assumes `target_schema`.
+    pub fn into_typed_row(self, target_schema: &Schema) -> StorageResult<OwnedRow> {
+        if self.row.len() != target_schema.len() {
+            return Err(StorageError::schema_mismatch(format!(
+                "row has {} columns, but target schema expects {}",
+                self.row.len(),
+                target_schema.len()
+            )));
+        }
+        for (i, (datum, field)) in self.row.iter().zip_eq_fast(target_schema.fields()).enumerate() {
+            if !literal_type_match(&field.data_type, datum.map(|d| d.into_scalar_impl()).as_ref())
+            {
+                return Err(StorageError::schema_mismatch(format!(
+                    "column {} ({}) expects type {:?}, but stored value doesn't match",
+                    i, field.name, field.data_type
+                )));
+            }
+        }
+        Ok(self.row)
+    }
 }
 
 impl<T: AsRef<[u8]>> Deref for KeyedRow<T> {
@@ -142,3 +320,143 @@ impl<T: AsRef<[u8]>> Deref for KeyedRow<T> {
         &self.row
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::hash::VirtualNode;
+    use risingwave_common::types::{DataType, ScalarImpl};
+    use risingwave_hummock_sdk::key::TableKey;
+
+    use super::*;
+
+    fn gen_keyed_row(i: u8, value: i64) -> StorageResult<KeyedRow<Bytes>> {
+        let mut key = VirtualNode::ZERO.to_be_bytes().to_vec();
+        key.push(i);
+        Ok(KeyedRow::new(
+            TableKey(Bytes::from(key)),
+            OwnedRow::new(vec![Some(ScalarImpl::Int64(value))]),
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_collect_data_chunk_dedup_adjacent_keys() {
+        let schema = Schema::new(vec![risingwave_common::catalog::Field::unnamed(
+            DataType::Int64,
+        )]);
+        let mut stream = futures::stream::iter(vec![
+            gen_keyed_row(0, 1),
+            gen_keyed_row(0, 2), // duplicate key of the row above, should win
+            gen_keyed_row(1, 3),
+            gen_keyed_row(2, 4),
+            gen_keyed_row(2, 5), // duplicate key of the row above, should win
+        ]);
+
+        let chunk = collect_data_chunk_dedup(&mut stream, &schema, None)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(chunk.cardinality(), 3);
+        let values: Vec<_> = chunk
+            .rows()
+            .map(|row| row.datum_at(0).unwrap().into_int64())
+            .collect();
+        assert_eq!(values, vec![2, 3, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_collect_data_chunk_with_poll_timeout_fires_on_stall() {
+        let schema = Schema::new(vec![risingwave_common::catalog::Field::unnamed(
+            DataType::Int64,
+        )]);
+        // One real row, then the stream stalls forever (never becomes ready again).
+        let mut stream =
+            futures::stream::iter(vec![gen_keyed_row(0, 1)]).chain(futures::stream::pending());
+
+        let err = collect_data_chunk_with_poll_timeout(
+            &mut stream,
+            &schema,
+            None,
+            Some(Duration::from_millis(10)),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("scan stalled"));
+    }
+
+    #[test]
+    fn test_keyed_row_datum_by_name() {
+        let schema = Schema::new(vec![
+            risingwave_common::catalog::Field::with_name(DataType::Int64, "id"),
+            risingwave_common::catalog::Field::with_name(DataType::Int64, "value"),
+        ]);
+        let row = KeyedRow::new(
+            TableKey(Bytes::from(vec![0u8])),
+            OwnedRow::new(vec![Some(ScalarImpl::Int64(1)), Some(ScalarImpl::Int64(42))]),
+        );
+
+        assert_eq!(
+            row.datum_by_name(&schema, "value")
+                .unwrap()
+                .unwrap()
+                .into_int64(),
+            42
+        );
+        assert!(row.datum_by_name(&schema, "nonexistent").is_none());
+    }
+
+    #[test]
+    fn test_keyed_row_into_typed_row_matching_schema() {
+        let schema = Schema::new(vec![
+            risingwave_common::catalog::Field::with_name(DataType::Int64, "id"),
+            risingwave_common::catalog::Field::with_name(DataType::Varchar, "name"),
+        ]);
+        let row = KeyedRow::new(
+            TableKey(Bytes::from(vec![0u8])),
+            OwnedRow::new(vec![
+                Some(ScalarImpl::Int64(1)),
+                Some(ScalarImpl::Utf8("a".into())),
+            ]),
+        );
+
+        let typed_row = row.into_typed_row(&schema).unwrap();
+        assert_eq!(typed_row.datum_at(0).unwrap().into_int64(), 1);
+    }
+
+    #[test]
+    fn test_keyed_row_into_typed_row_mismatching_schema() {
+        let schema = Schema::new(vec![
+            risingwave_common::catalog::Field::with_name(DataType::Int64, "id"),
+            // Stored row has a varchar here, not an int32.
+            risingwave_common::catalog::Field::with_name(DataType::Int32, "name"),
+        ]);
+        let row = KeyedRow::new(
+            TableKey(Bytes::from(vec![0u8])),
+            OwnedRow::new(vec![
+                Some(ScalarImpl::Int64(1)),
+                Some(ScalarImpl::Utf8("a".into())),
+            ]),
+        );
+
+        let err = row.into_typed_row(&schema).unwrap_err();
+        assert!(err.to_string().contains("does not match target schema"));
+    }
+
+    #[test]
+    fn test_keyed_row_project_borrowed() {
+        let row = KeyedRow::new(
+            TableKey(Bytes::from(vec![0u8])),
+            OwnedRow::new(vec![
+                Some(ScalarImpl::Int64(1)),
+                Some(ScalarImpl::Utf8("a".into())),
+                Some(ScalarImpl::Int64(2)),
+            ]),
+        );
+
+        let projected = row.project_borrowed(&[2, 0]);
+        assert_eq!(projected.datum_at(0).unwrap().into_int64(), 2);
+        assert_eq!(projected.datum_at(1).unwrap().into_int64(), 1);
+        // The original row is still usable afterwards: `project_borrowed` didn't consume it.
+        assert_eq!(row.row().datum_at(1).unwrap().into_utf8(), "a");
+    }
+}
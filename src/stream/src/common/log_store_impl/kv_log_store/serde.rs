@@ -362,10 +362,40 @@ impl LogStoreRowSerde {
         end_seq_id: SeqIdType,
         expected_epoch: u64,
         metrics: &KvLogStoreReadMetrics,
+    ) -> LogStoreResult<StreamChunk> {
+        self.deserialize_stream_chunk_with_projection(
+            iters,
+            start_seq_id,
+            end_seq_id,
+            expected_epoch,
+            metrics,
+            None,
+        )
+        .await
+    }
+
+    /// Like [`Self::deserialize_stream_chunk`], but when `payload_indices` is `Some`, only the
+    /// selected payload columns are kept in the decoded rows, similar to how [`ValueRowSerde`]'s
+    /// `value_indices` narrows down the set of columns materialized out of a serialized row.
+    /// This avoids allocating for payload columns that the caller doesn't need.
+    pub(crate) async fn deserialize_stream_chunk_with_projection<I: StateStoreReadIter>(
+        &self,
+        iters: impl IntoIterator<Item = I>,
+        start_seq_id: SeqIdType,
+        end_seq_id: SeqIdType,
+        expected_epoch: u64,
+        metrics: &KvLogStoreReadMetrics,
+        payload_indices: Option<&[usize]>,
     ) -> LogStoreResult<StreamChunk> {
         let size_bound = (end_seq_id - start_seq_id + 1) as usize;
-        let mut data_chunk_builder =
-            DataChunkBuilder::new(self.payload_schema.clone(), size_bound + 1);
+        let payload_schema = match payload_indices {
+            Some(indices) => indices
+                .iter()
+                .map(|&i| self.payload_schema[i].clone())
+                .collect(),
+            None => self.payload_schema.clone(),
+        };
+        let mut data_chunk_builder = DataChunkBuilder::new(payload_schema, size_bound + 1);
         let mut ops = Vec::with_capacity(size_bound);
         let mut read_info = ReadInfo::new();
         let stream = select_all(iters.into_iter().map(|iter| {
@@ -396,6 +426,10 @@ impl LogStoreRowSerde {
                             size_bound
                         ));
                     }
+                    let row = match payload_indices {
+                        Some(indices) => row.project(indices).into_owned_row(),
+                        None => row,
+                    };
                     assert!(data_chunk_builder.append_one_row(row).is_none());
                 }
                 (_, LogStoreRowOp::Barrier { .. }) => {
@@ -992,6 +1026,48 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_deserialize_stream_chunk_with_projection() {
+        let pk_info = &KV_LOG_STORE_V2_INFO;
+        let table = gen_test_log_store_table(pk_info);
+        let serde = LogStoreRowSerde::new(
+            &table,
+            Some(Arc::new(Bitmap::ones(VirtualNode::COUNT))),
+            pk_info,
+        );
+        let (ops, rows) = gen_test_data(0);
+
+        let mut seq_id = 1;
+        let start_seq_id = seq_id;
+
+        let (stream, tx) = gen_row_stream(
+            serde.clone(),
+            ops.clone(),
+            rows.clone(),
+            EPOCH1,
+            &mut seq_id,
+        );
+        let end_seq_id = seq_id - 1;
+        tx.send(()).unwrap();
+        // only keep the first payload column
+        let chunk = serde
+            .deserialize_stream_chunk_with_projection(
+                once(FromStreamStateStoreIter::new(stream.boxed())),
+                start_seq_id,
+                end_seq_id,
+                EPOCH1,
+                &KvLogStoreReadMetrics::for_test(),
+                Some(&[0]),
+            )
+            .await
+            .unwrap();
+        for (i, (op, row)) in chunk.rows().enumerate() {
+            assert_eq!(ops[i], op);
+            assert_eq!(row.len(), 1);
+            assert_eq!(rows[i].datum_at(0), row.datum_at(0));
+        }
+    }
+
     fn gen_row_stream(
         serde: LogStoreRowSerde,
         ops: Vec<Op>,
@@ -134,7 +134,9 @@ impl MaterializedInputState {
             AggKind::StringAgg
             | AggKind::ArrayAgg
             | AggKind::JsonbAgg
-            | AggKind::JsonbObjectAgg => Box::new(GenericAggStateCache::new(
+            | AggKind::JsonbObjectAgg
+            | AggKind::PercentileCont
+            | AggKind::Mode => Box::new(GenericAggStateCache::new(
                 OrderedStateCache::new(),
                 agg_call.args.arg_types(),
             )),
@@ -1147,4 +1149,106 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_mode_agg_state() -> StreamExecutorResult<()> {
+        // Assumption of input schema:
+        // (a: int32, _row_id: int64)
+        // where `a` is the column to aggregate, with `mode() within group (order by a)`
+
+        let input_schema = Schema::new(vec![
+            Field::unnamed(DataType::Int32),
+            Field::unnamed(DataType::Int64),
+        ]);
+
+        let agg_call = AggCall::from_pretty("(mode:int4 $0:int4 orderby $0:asc)");
+        let agg = build_append_only(&agg_call).unwrap();
+        let group_key = None;
+
+        let (mut table, mapping) = create_mem_state_table(
+            &input_schema,
+            vec![0, 1],
+            vec![
+                OrderType::ascending(), // a ASC
+                OrderType::ascending(), // _row_id ASC
+            ],
+        )
+        .await;
+
+        let order_columns = vec![
+            ColumnOrder::new(0, OrderType::ascending()), // a ASC
+            ColumnOrder::new(1, OrderType::ascending()), // _row_id ASC
+        ];
+        let mut state = MaterializedInputState::new(
+            PbAggNodeVersion::Max,
+            &agg_call,
+            &PkIndices::new(), // unused
+            &order_columns,
+            &mapping,
+            usize::MAX,
+            &input_schema,
+        )
+        .unwrap();
+
+        let mut epoch = EpochPair::new_test_epoch(test_epoch(1));
+        table.init_epoch(epoch);
+
+        {
+            // values: 2, 2, 3 -> mode is 2
+            let chunk = create_chunk(
+                " i I
+                + 2 123
+                + 2 128
+                + 3 130",
+                &mut table,
+                &mapping,
+            );
+            state.apply_chunk(&chunk)?;
+
+            epoch.inc_for_test();
+            table.commit(epoch).await.unwrap();
+
+            let res = state.get_output(&table, group_key.as_ref(), &agg).await?;
+            assert_eq!(res, Some(2i32.into()));
+        }
+
+        {
+            // retract the `3` and add two `4`s: values become 2, 2, 4, 4 -> tied at freq 2,
+            // smallest wins, so mode stays 2
+            let chunk = create_chunk(
+                " i I
+                - 3 130
+                + 4 134
+                + 4 137",
+                &mut table,
+                &mapping,
+            );
+            state.apply_chunk(&chunk)?;
+
+            epoch.inc_for_test();
+            table.commit(epoch).await.unwrap();
+
+            let res = state.get_output(&table, group_key.as_ref(), &agg).await?;
+            assert_eq!(res, Some(2i32.into()));
+        }
+
+        {
+            // one more `4` breaks the tie: values are 2, 2, 4, 4, 4 -> mode is 4
+            let chunk = create_chunk(
+                " i I
+                + 4 140",
+                &mut table,
+                &mapping,
+            );
+            state.apply_chunk(&chunk)?;
+
+            epoch.inc_for_test();
+            table.commit(epoch).await.unwrap();
+
+            let res = state.get_output(&table, group_key.as_ref(), &agg).await?;
+            assert_eq!(res, Some(4i32.into()));
+        }
+
+        Ok(())
+    }
 }
@@ -43,6 +43,10 @@ pub struct SnapshotReadArgs {
     pub current_pos: Option<OwnedRow>,
     pub ordered: bool,
     pub chunk_size: usize,
+    /// When set, snapshot rows are emitted as `UpdateInsert` instead of `Insert`, so that
+    /// downstream sinks can apply them with upsert semantics instead of treating a snapshot row
+    /// that races with a later binlog update as a duplicate insert.
+    pub upsert: bool,
 }
 
 impl SnapshotReadArgs {
@@ -52,6 +56,7 @@ impl SnapshotReadArgs {
             current_pos,
             ordered: false,
             chunk_size,
+            upsert: false,
         }
     }
 }
@@ -101,7 +106,7 @@ impl UpstreamTableRead for UpstreamTableReader<ExternalStorageTable> {
         pin_mut!(row_stream);
 
         let mut builder = DataChunkBuilder::new(self.inner.schema().data_types(), args.chunk_size);
-        let chunk_stream = iter_chunks(row_stream, &mut builder);
+        let chunk_stream = iter_chunks(row_stream, &mut builder, args.upsert);
         #[for_await]
         for chunk in chunk_stream {
             yield Some(chunk?);
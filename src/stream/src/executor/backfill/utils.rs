@@ -676,18 +676,25 @@ where
 }
 
 #[try_stream(ok = StreamChunk, error = StreamExecutorError)]
-pub(crate) async fn iter_chunks<'a, S, E, R>(mut iter: S, builder: &'a mut DataChunkBuilder)
-where
+pub(crate) async fn iter_chunks<'a, S, E, R>(
+    mut iter: S,
+    builder: &'a mut DataChunkBuilder,
+    upsert: bool,
+) where
     StreamExecutorError: From<E>,
     R: Row,
     S: Stream<Item = Result<R, E>> + Unpin + 'a,
 {
+    // CDC snapshot rows are logically inserts, but some downstream sinks (e.g. a CDC table that
+    // is also written to by the binlog stream) require upsert semantics so that a snapshot row
+    // racing with a later binlog update doesn't get treated as a duplicate insert.
+    let op = if upsert { Op::UpdateInsert } else { Op::Insert };
     while let Some(data_chunk) = collect_data_chunk_with_builder(&mut iter, builder)
         .instrument_await("backfill_snapshot_read")
         .await?
     {
         debug_assert!(data_chunk.cardinality() > 0);
-        let ops = vec![Op::Insert; data_chunk.capacity()];
+        let ops = vec![op; data_chunk.capacity()];
         let stream_chunk = StreamChunk::from_parts(ops, data_chunk);
         yield stream_chunk;
     }
@@ -14,18 +14,22 @@
 
 use std::collections::BTreeMap;
 use std::mem;
+use std::num::NonZeroU32;
+use std::sync::Arc;
 
 use either::Either;
-use futures::{StreamExt, TryStreamExt};
+use futures::{Stream, StreamExt, TryStreamExt};
 use futures_async_stream::try_stream;
 use risingwave_common::array::StreamChunk;
 use risingwave_common::catalog::{ColumnDesc, TableId, TableVersionId};
+use risingwave_common::estimate_size::EstimateSize;
 use risingwave_common::transaction::transaction_id::TxnId;
 use risingwave_common::transaction::transaction_message::TxnMsg;
-use risingwave_dml::dml_manager::DmlManagerRef;
+use risingwave_dml::dml_manager::{DmlManagerRef, DmlRateLimiter};
 
 use super::error::StreamExecutorError;
 use super::{expect_first_barrier, BoxedMessageStream, Execute, Executor, Message, Mutation};
+use crate::common::metrics::MetricsInfo;
 use crate::common::StreamChunkBuilder;
 use crate::executor::stream_reader::StreamReaderWithPause;
 
@@ -47,8 +51,34 @@ pub struct DmlExecutor {
     column_descs: Vec<ColumnDesc>,
 
     chunk_size: usize,
+
+    /// Invoked with a batch chunk and a reason string whenever a row is rejected instead of
+    /// being forwarded downstream, e.g. because its arity doesn't match `column_descs`.
+    deadletter_callback: Option<DmlDeadLetterCallback>,
+
+    /// Maximum number of concurrently active transactions allowed in `active_txn_map`. `None`
+    /// means unbounded. Guards against a buggy client opening many transactions without ending
+    /// them and growing the map without limit.
+    max_active_txn_count: Option<usize>,
+
+    /// Maximum estimated byte size of `batch_group` before it's flushed, independent of
+    /// `chunk_size` (a row count) and of barriers. `None` means unbounded. Guards against wide
+    /// rows making the batch group consume significant memory while row count alone stays small.
+    batch_group_max_bytes: Option<usize>,
+
+    /// Rate limit, in rows per second, applied to batch data (DML from users) before it's merged
+    /// with the upstream stream. `None` means unbounded. The token-bucket budget backing this
+    /// limit lives on `dml_manager` rather than on `self`, so it survives a rebuild of this
+    /// executor across recovery instead of resetting and allowing a burst.
+    rate_limit: Option<u32>,
+
+    metrics_info: MetricsInfo,
 }
 
+/// Callback invoked for a batch [`StreamChunk`] that `DmlExecutor` rejects rather than
+/// forwarding downstream, together with the reason it was rejected.
+pub type DmlDeadLetterCallback = Arc<dyn Fn(&StreamChunk, &str) + Send + Sync>;
+
 /// If a transaction's data is less than `MAX_CHUNK_FOR_ATOMICITY` * `CHUNK_SIZE`, we can provide
 /// atomicity. Otherwise, it is possible that part of transaction's data is sent to the downstream
 /// without barrier boundaries. There are some cases that could cause non-atomicity for large
@@ -63,6 +93,9 @@ struct TxnBuffer {
     vec: Vec<StreamChunk>,
     // When vec size exceeds `MAX_CHUNK_FOR_ATOMICITY`, set true to `overflow`.
     overflow: bool,
+    // Total number of chunks forwarded downstream without atomicity once `overflow` is set,
+    // kept around for diagnostics (e.g. logged on rollback of an already-overflowed transaction).
+    sent_chunk_count: usize,
 }
 
 impl DmlExecutor {
@@ -73,6 +106,7 @@ impl DmlExecutor {
         table_version_id: TableVersionId,
         column_descs: Vec<ColumnDesc>,
         chunk_size: usize,
+        metrics_info: MetricsInfo,
     ) -> Self {
         Self {
             upstream,
@@ -81,6 +115,65 @@ impl DmlExecutor {
             table_version_id,
             column_descs,
             chunk_size,
+            deadletter_callback: None,
+            max_active_txn_count: None,
+            batch_group_max_bytes: None,
+            rate_limit: None,
+            metrics_info,
+        }
+    }
+
+    pub fn with_deadletter_callback(mut self, callback: DmlDeadLetterCallback) -> Self {
+        self.deadletter_callback = Some(callback);
+        self
+    }
+
+    pub fn with_rate_limit(mut self, rate_limit: u32) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    pub fn with_max_active_txn_count(mut self, max_active_txn_count: usize) -> Self {
+        self.max_active_txn_count = Some(max_active_txn_count);
+        self
+    }
+
+    pub fn with_batch_group_max_bytes(mut self, batch_group_max_bytes: usize) -> Self {
+        self.batch_group_max_bytes = Some(batch_group_max_bytes);
+        self
+    }
+
+    /// Whether `batch_group`'s estimated byte size has reached `self.batch_group_max_bytes` (if
+    /// configured). Checked independently of `chunk_size` (a row count) so wide rows can't make
+    /// the batch group grow unboundedly in memory while staying under the row-count threshold.
+    fn batch_group_over_byte_cap(&self, batch_group: &[StreamChunk]) -> bool {
+        match self.batch_group_max_bytes {
+            Some(max_bytes) => {
+                let bytes: usize = batch_group.iter().map(|c| c.estimated_size()).sum();
+                bytes >= max_bytes
+            }
+            None => false,
+        }
+    }
+
+    /// Returns `true` and forwards the chunk to the deadletter callback (if any) when the
+    /// chunk's arity doesn't match `self.column_descs`, so that the caller can drop the row
+    /// instead of forwarding it downstream.
+    fn reject_if_malformed(&self, chunk: &StreamChunk) -> bool {
+        if chunk.columns().len() != self.column_descs.len() {
+            let reason = format!(
+                "chunk arity {} doesn't match table column count {}",
+                chunk.columns().len(),
+                self.column_descs.len()
+            );
+            if let Some(callback) = &self.deadletter_callback {
+                callback(chunk, &reason);
+            } else {
+                tracing::warn!(table_id = %self.table_id, reason, "rejecting malformed DML chunk");
+            }
+            true
+        } else {
+            false
         }
     }
 
@@ -108,6 +201,15 @@ impl DmlExecutor {
             .into_stream()
             .map_err(StreamExecutorError::from)
             .boxed();
+        let reader = match self.rate_limit.and_then(NonZeroU32::new) {
+            Some(rate_limit) => {
+                let rate_limiter = self
+                    .dml_manager
+                    .rate_limiter_for_table(self.table_id, rate_limit);
+                apply_rate_limit(reader, rate_limiter, rate_limit).boxed()
+            }
+            None => reader,
+        };
 
         // Merge the two streams using `StreamReaderWithPause` because when we receive a pause
         // barrier, we should stop receiving the data from DML. We poll data from the two streams in
@@ -170,6 +272,16 @@ impl DmlExecutor {
                     // Batch data.
                     match txn_msg {
                         TxnMsg::Begin(txn_id) => {
+                            if let Some(max_active_txn_count) = self.max_active_txn_count
+                                && active_txn_map.len() >= max_active_txn_count
+                            {
+                                Err(anyhow::anyhow!(
+                                    "too many active transactions on table_id = {}: {} active, limit is {}",
+                                    self.table_id,
+                                    active_txn_map.len(),
+                                    max_active_txn_count
+                                ))?;
+                            }
                             active_txn_map
                                 .try_insert(txn_id, TxnBuffer::default())
                                 .unwrap_or_else(|_| {
@@ -213,6 +325,24 @@ impl DmlExecutor {
                             {
                                 // txn buffer is small and batch group has space.
                                 batch_group.extend(txn_buffer.vec);
+
+                                // Even though the row-count threshold wasn't hit, wide rows may
+                                // already make the batch group consume significant memory; flush
+                                // immediately instead of waiting for `chunk_size` rows or the next
+                                // barrier.
+                                if self.batch_group_over_byte_cap(&batch_group) {
+                                    let vec = mem::take(&mut batch_group);
+                                    for chunk in vec {
+                                        for (op, row) in chunk.rows() {
+                                            if let Some(chunk) = builder.append_row(op, row) {
+                                                yield Message::Chunk(chunk);
+                                            }
+                                        }
+                                    }
+                                    if let Some(chunk) = builder.take() {
+                                        yield Message::Chunk(chunk);
+                                    }
+                                }
                             } else {
                                 // txn buffer is small and batch group has no space, so yield the batch group first to preserve the transaction order in the same session.
                                 if !batch_group.is_empty() {
@@ -231,32 +361,68 @@ impl DmlExecutor {
 
                                 // put txn buffer into the batch group
                                 mem::swap(&mut txn_buffer.vec, &mut batch_group);
+
+                                if self.batch_group_over_byte_cap(&batch_group) {
+                                    let vec = mem::take(&mut batch_group);
+                                    for chunk in vec {
+                                        for (op, row) in chunk.rows() {
+                                            if let Some(chunk) = builder.append_row(op, row) {
+                                                yield Message::Chunk(chunk);
+                                            }
+                                        }
+                                    }
+                                    if let Some(chunk) = builder.take() {
+                                        yield Message::Chunk(chunk);
+                                    }
+                                }
                             }
                         }
                         TxnMsg::Rollback(txn_id) => {
                             let txn_buffer = active_txn_map.remove(&txn_id)
                                 .unwrap_or_else(|| panic!("Receive an unexpected transaction rollback message. Active transaction map doesn't contain this transaction txn_id = {}.", txn_id));
                             if txn_buffer.overflow {
-                                tracing::warn!("txn_id={} large transaction tries to rollback, but part of its data has already been sent to the downstream.", txn_id);
+                                tracing::warn!(
+                                    txn_id,
+                                    chunk_count = txn_buffer.sent_chunk_count,
+                                    "large transaction tries to rollback, but part of its data has already been sent to the downstream"
+                                );
                             }
                         }
                         TxnMsg::Data(txn_id, chunk) => {
+                            if self.reject_if_malformed(&chunk) {
+                                continue;
+                            }
                             match active_txn_map.get_mut(&txn_id) {
                                 Some(txn_buffer) => {
                                     // This transaction is too large, we can't provide atomicity,
                                     // so yield chunk ASAP.
                                     if txn_buffer.overflow {
+                                        txn_buffer.sent_chunk_count += 1;
                                         yield Message::Chunk(chunk);
                                         continue;
                                     }
                                     txn_buffer.vec.push(chunk);
                                     if txn_buffer.vec.len() > MAX_CHUNK_FOR_ATOMICITY {
                                         // Too many chunks for atomicity. Drain and yield them.
-                                        tracing::warn!("txn_id={} Too many chunks for atomicity. Sent them to the downstream anyway.", txn_id);
+                                        let chunk_count = txn_buffer.vec.len();
+                                        tracing::warn!(
+                                            txn_id,
+                                            chunk_count,
+                                            "too many chunks for atomicity, sent them to the downstream anyway"
+                                        );
+                                        self.metrics_info
+                                            .metrics
+                                            .dml_lost_atomicity_count
+                                            .with_label_values(&[
+                                                &self.metrics_info.table_id,
+                                                &self.metrics_info.actor_id,
+                                            ])
+                                            .inc();
                                         for chunk in txn_buffer.vec.drain(..) {
                                             yield Message::Chunk(chunk);
                                         }
                                         txn_buffer.overflow = true;
+                                        txn_buffer.sent_chunk_count = chunk_count;
                                     }
                                 }
                                 None => panic!("Receive an unexpected transaction data message. Active transaction map doesn't contain this transaction txn_id = {}.", txn_id),
@@ -269,6 +435,43 @@ impl DmlExecutor {
     }
 }
 
+/// Throttles batch data (DML from users) read off `reader` to `rate_limit` rows per second,
+/// using the shared `rate_limiter` so the budget carries over across a `DmlExecutor` rebuild.
+/// Mirrors the chunk-splitting approach in [`super::flow_control::FlowControlExecutor`] so a
+/// chunk larger than `rate_limit` doesn't hit `governor`'s `InsufficientCapacity` error.
+#[try_stream(ok = TxnMsg, error = StreamExecutorError)]
+async fn apply_rate_limit(
+    reader: impl Stream<Item = Result<TxnMsg, StreamExecutorError>>,
+    rate_limiter: Arc<DmlRateLimiter>,
+    rate_limit: NonZeroU32,
+) {
+    #[for_await]
+    for msg in reader {
+        let msg = msg?;
+        let TxnMsg::Data(txn_id, chunk) = msg else {
+            yield msg;
+            continue;
+        };
+
+        let Some(n) = NonZeroU32::new(chunk.cardinality() as u32) else {
+            yield TxnMsg::Data(txn_id, chunk);
+            continue;
+        };
+        if n <= rate_limit {
+            // `InsufficientCapacity` should never happen because we have done the check above.
+            rate_limiter.until_n_ready(n).await.unwrap();
+            yield TxnMsg::Data(txn_id, chunk);
+        } else {
+            for chunk in chunk.split(rate_limit.get() as usize) {
+                let n = NonZeroU32::new(chunk.cardinality() as u32).unwrap();
+                // Ditto.
+                rate_limiter.until_n_ready(n).await.unwrap();
+                yield TxnMsg::Data(txn_id, chunk);
+            }
+        }
+    }
+}
+
 impl Execute for DmlExecutor {
     fn execute(self: Box<Self>) -> BoxedMessageStream {
         self.execute_inner().boxed()
@@ -427,4 +630,212 @@ mod tests {
         let msg = dml_executor.next().await.unwrap().unwrap();
         assert!(matches!(msg, Message::Barrier(_)));
     }
+
+    #[tokio::test]
+    async fn test_dml_executor_deadletter_callback() {
+        let table_id = TableId::default();
+        let schema = Schema::new(vec![Field::unnamed(DataType::Int64)]);
+        let column_descs = vec![
+            ColumnDesc::unnamed(ColumnId::new(0), DataType::Int64),
+            ColumnDesc::unnamed(ColumnId::new(1), DataType::Int64),
+        ];
+        let pk_indices = vec![0];
+        let dml_manager = Arc::new(DmlManager::for_test());
+
+        let (mut tx, source) = MockSource::channel();
+        let source = source.into_executor(schema, pk_indices);
+
+        let rejected: Arc<std::sync::Mutex<Vec<String>>> = Arc::new(std::sync::Mutex::new(vec![]));
+        let rejected_inner = rejected.clone();
+
+        let dml_executor = DmlExecutor::new(
+            source,
+            dml_manager.clone(),
+            table_id,
+            INITIAL_TABLE_VERSION_ID,
+            column_descs,
+            1024,
+        )
+        .with_deadletter_callback(Arc::new(move |_chunk, reason| {
+            rejected_inner.lock().unwrap().push(reason.to_string());
+        }));
+        let mut dml_executor = dml_executor.boxed().execute();
+
+        tx.push_barrier(test_epoch(1), false);
+        let msg = dml_executor.next().await.unwrap().unwrap();
+        assert!(matches!(msg, Message::Barrier(_)));
+
+        let table_dml_handle = dml_manager
+            .table_dml_handle(table_id, INITIAL_TABLE_VERSION_ID)
+            .unwrap();
+        let mut write_handle = table_dml_handle
+            .write_handle(TEST_SESSION_ID, TEST_TRANSACTION_ID)
+            .unwrap();
+
+        // this chunk has only one column, while the table has two, so it is malformed
+        let malformed_chunk = StreamChunk::from_pretty(
+            " I
+            + 1",
+        );
+
+        write_handle.begin().unwrap();
+        write_handle.write_chunk(malformed_chunk).await.unwrap();
+        tokio::spawn(async move {
+            write_handle.end().await.unwrap();
+            tx.push_barrier(test_epoch(2), false);
+        });
+
+        // the malformed batch chunk is rejected rather than forwarded, so the next message is
+        // the barrier that follows it
+        let msg = dml_executor.next().await.unwrap().unwrap();
+        assert!(matches!(msg, Message::Barrier(_)));
+        assert_eq!(rejected.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_dml_executor_batch_group_flushes_on_byte_cap() {
+        use risingwave_common::row::OwnedRow;
+        use risingwave_common::types::ScalarImpl;
+
+        let table_id = TableId::default();
+        let schema = Schema::new(vec![Field::unnamed(DataType::Varchar)]);
+        let column_descs = vec![ColumnDesc::unnamed(ColumnId::new(0), DataType::Varchar)];
+        let pk_indices = vec![0];
+        let dml_manager = Arc::new(DmlManager::for_test());
+
+        let (mut tx, source) = MockSource::channel();
+        let source = source.into_executor(schema, pk_indices);
+
+        // A large `chunk_size` (in rows) so the row-count threshold is never hit, and a tiny
+        // `batch_group_max_bytes` so a single wide row already exceeds it.
+        let dml_executor = DmlExecutor::new(
+            source,
+            dml_manager.clone(),
+            table_id,
+            INITIAL_TABLE_VERSION_ID,
+            column_descs,
+            1024,
+        )
+        .with_batch_group_max_bytes(64);
+        let mut dml_executor = dml_executor.boxed().execute();
+
+        tx.push_barrier(test_epoch(1), false);
+        let msg = dml_executor.next().await.unwrap().unwrap();
+        assert!(matches!(msg, Message::Barrier(_)));
+
+        let table_dml_handle = dml_manager
+            .table_dml_handle(table_id, INITIAL_TABLE_VERSION_ID)
+            .unwrap();
+        let mut write_handle = table_dml_handle
+            .write_handle(TEST_SESSION_ID, TEST_TRANSACTION_ID)
+            .unwrap();
+
+        let wide_value = "x".repeat(200);
+        let wide_chunk = StreamChunk::from_rows(
+            &[(
+                risingwave_common::array::Op::Insert,
+                OwnedRow::new(vec![Some(ScalarImpl::from(wide_value))]),
+            )],
+            &[DataType::Varchar],
+        );
+
+        write_handle.begin().unwrap();
+        write_handle.write_chunk(wide_chunk).await.unwrap();
+        tokio::spawn(async move {
+            write_handle.end().await.unwrap();
+            // Intentionally no barrier is pushed here: the byte cap alone should force a flush.
+        });
+
+        // The batch group should flush as soon as the byte cap is exceeded, without waiting for a
+        // barrier.
+        let msg = dml_executor.next().await.unwrap().unwrap();
+        assert!(msg.into_chunk().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_dml_executor_max_active_txn_count() {
+        let table_id = TableId::default();
+        let schema = Schema::new(vec![Field::unnamed(DataType::Int64)]);
+        let column_descs = vec![ColumnDesc::unnamed(ColumnId::new(0), DataType::Int64)];
+        let pk_indices = vec![0];
+        let dml_manager = Arc::new(DmlManager::for_test());
+
+        let (mut tx, source) = MockSource::channel();
+        let source = source.into_executor(schema, pk_indices);
+
+        let dml_executor = DmlExecutor::new(
+            source,
+            dml_manager.clone(),
+            table_id,
+            INITIAL_TABLE_VERSION_ID,
+            column_descs,
+            1024,
+        )
+        .with_max_active_txn_count(1);
+        let mut dml_executor = dml_executor.boxed().execute();
+
+        tx.push_barrier(test_epoch(1), false);
+        let msg = dml_executor.next().await.unwrap().unwrap();
+        assert!(matches!(msg, Message::Barrier(_)));
+
+        let table_dml_handle = dml_manager
+            .table_dml_handle(table_id, INITIAL_TABLE_VERSION_ID)
+            .unwrap();
+        let mut write_handle_0 = table_dml_handle.write_handle(TEST_SESSION_ID, 0).unwrap();
+        let mut write_handle_1 = table_dml_handle.write_handle(TEST_SESSION_ID, 1).unwrap();
+
+        // The first transaction is within the limit.
+        write_handle_0.begin().unwrap();
+        // The second transaction, still active concurrently with the first, exceeds the limit of
+        // 1 and should be rejected with an error propagated through the stream.
+        write_handle_1.begin().unwrap();
+
+        let msg = dml_executor.next().await.unwrap();
+        assert!(msg.is_err());
+    }
+
+    /// The budget consumed by a rate-limited `DmlExecutor` must carry over to its replacement
+    /// after a rebuild (e.g. across recovery), rather than resetting and allowing a burst. Since
+    /// the budget lives on the shared `DmlManager` (see [`DmlManager::rate_limiter_for_table`]),
+    /// we can exercise this directly through [`apply_rate_limit`] without standing up a full
+    /// actor/channel stack for two executor instances.
+    #[tokio::test]
+    async fn test_rate_limit_budget_persists_across_rebuild() {
+        use std::time::{Duration, Instant};
+
+        use futures::stream;
+
+        let table_id = TableId::new(1);
+        let dml_manager = DmlManager::for_test();
+        let rate_limit = NonZeroU32::new(2).unwrap();
+
+        let one_row_msg = || {
+            Ok(TxnMsg::Data(
+                TEST_TRANSACTION_ID,
+                StreamChunk::from_pretty(" I \n + 1"),
+            ))
+        };
+
+        // "Before recovery": the first `DmlExecutor` instance drains the initial burst of 2 rows,
+        // which should come through immediately.
+        let rate_limiter = dml_manager.rate_limiter_for_table(table_id, rate_limit);
+        let before = apply_rate_limit(
+            stream::iter(vec![one_row_msg(), one_row_msg()]),
+            rate_limiter,
+            rate_limit,
+        );
+        let start = Instant::now();
+        before.try_collect::<Vec<_>>().await.unwrap();
+        assert!(start.elapsed() < Duration::from_millis(300));
+
+        // "After recovery": a new `DmlExecutor` instance fetches the rate limiter for the same
+        // `table_id` from the same (not rebuilt) `dml_manager`, so the budget consumed above
+        // hasn't refilled yet. A further row should be throttled for close to a full token
+        // interval instead of bursting straight through.
+        let rate_limiter = dml_manager.rate_limiter_for_table(table_id, rate_limit);
+        let after = apply_rate_limit(stream::iter(vec![one_row_msg()]), rate_limiter, rate_limit);
+        let start = Instant::now();
+        after.try_collect::<Vec<_>>().await.unwrap();
+        assert!(start.elapsed() > Duration::from_millis(300));
+    }
 }
@@ -147,6 +147,12 @@ pub struct StreamingMetrics {
     pub barrier_sync_latency: Histogram,
     /// The progress made by the earliest in-flight barriers in the local barrier manager.
     pub barrier_manager_progress: IntCounter,
+    /// The duration from `send_barrier` issuing an epoch to `on_epoch_completed` firing for it
+    /// on this compute node, labeled by the barrier's `BarrierKind` (`Initial`, `Barrier`, or
+    /// `Checkpoint`). Unlike `barrier_inflight_latency`, this covers the full round trip
+    /// including the state store sync, so it can be used to alert on barrier stalls per compute
+    /// node without relying on meta-side logs.
+    pub barrier_epoch_latency: LabelGuardedHistogramVec<1>,
 
     // Sink related metrics
     pub sink_commit_duration: LabelGuardedHistogramVec<3>,
@@ -188,6 +194,9 @@ pub struct StreamingMetrics {
     pub materialize_cache_hit_count: GenericCounterVec<AtomicU64>,
     pub materialize_cache_total_count: GenericCounterVec<AtomicU64>,
 
+    // DML
+    pub dml_lost_atomicity_count: GenericCounterVec<AtomicU64>,
+
     // Memory
     pub stream_memory_usage: LabelGuardedIntGaugeVec<3>,
 }
@@ -796,6 +805,18 @@ impl StreamingMetrics {
         )
         .unwrap();
 
+        let opts = histogram_opts!(
+            "stream_barrier_epoch_duration_seconds",
+            "barrier_epoch_latency",
+            exponential_buckets(0.1, 1.5, 16).unwrap() // max 43s
+        );
+        let barrier_epoch_latency = register_guarded_histogram_vec_with_registry!(
+            opts,
+            &["barrier_kind"],
+            registry
+        )
+        .unwrap();
+
         let sink_commit_duration = register_guarded_histogram_vec_with_registry!(
             "sink_commit_duration",
             "Duration of commit op in sink",
@@ -1011,6 +1032,14 @@ impl StreamingMetrics {
         )
         .unwrap();
 
+        let dml_lost_atomicity_count = register_int_counter_vec_with_registry!(
+            "stream_dml_lost_atomicity_count",
+            "Number of DML transactions that exceeded the chunk limit for atomicity and had some of their data sent to the downstream before the transaction ended",
+            &["table_id", "actor_id"],
+            registry
+        )
+        .unwrap();
+
         let stream_memory_usage = register_guarded_int_gauge_vec_with_registry!(
             "stream_memory_usage",
             "Memory usage for stream executors",
@@ -1130,6 +1159,7 @@ impl StreamingMetrics {
             barrier_inflight_latency,
             barrier_sync_latency,
             barrier_manager_progress,
+            barrier_epoch_latency,
             sink_commit_duration,
             connector_sink_rows_received,
             log_store_first_write_epoch,
@@ -1161,6 +1191,7 @@ impl StreamingMetrics {
             jvm_active_bytes,
             materialize_cache_hit_count,
             materialize_cache_total_count,
+            dml_lost_atomicity_count,
             stream_memory_usage,
         }
     }
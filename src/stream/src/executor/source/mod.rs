@@ -60,7 +60,12 @@ pub fn get_split_offset_mapping_from_chunk(
     offset_idx: usize,
 ) -> Option<HashMap<SplitId, String>> {
     let mut split_offset_mapping = HashMap::new();
-    for (_, row) in chunk.rows() {
+    // Iterate by position rather than using `rows()` so that invisible rows are included too: a
+    // CDC heartbeat message is recorded as an invisible row (see
+    // `SourceStreamChunkRowWriter::invisible`) carrying only the offset/split-id columns, and
+    // still needs to advance the split's committed offset even though it produces no visible row.
+    for pos in 0..chunk.capacity() {
+        let (_, row, _visible) = chunk.row_at(pos);
         let split_id = row.datum_at(split_idx).unwrap().into_utf8().into();
         let offset = row.datum_at(offset_idx).unwrap().into_utf8();
         split_offset_mapping.insert(split_id, offset.to_string());
@@ -105,3 +110,38 @@ pub fn prune_additional_cols(
             .collect_vec(),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::StreamChunkTestExt;
+
+    use super::*;
+
+    #[test]
+    fn test_get_split_offset_mapping_from_chunk_includes_invisible_rows() {
+        // Mirrors how a CDC heartbeat message is recorded: an invisible row (marked `D`) carrying
+        // only the split-id (column 0) and offset (column 1), with no visible data. The split's
+        // committed offset should still advance from it.
+        let chunk = StreamChunk::from_pretty(
+            "  T    T
+             + split0 5   D
+             + split0 10",
+        );
+
+        let mapping = get_split_offset_mapping_from_chunk(&chunk, 0, 1).unwrap();
+        // The later, visible row's offset wins since both rows are under the same split id.
+        assert_eq!(mapping.get("split0").unwrap(), "10");
+    }
+
+    #[test]
+    fn test_get_split_offset_mapping_from_chunk_heartbeat_only_batch() {
+        // A batch made up entirely of heartbeat rows still yields an offset for its split.
+        let chunk = StreamChunk::from_pretty(
+            "  T    T
+             + split0 7   D",
+        );
+
+        let mapping = get_split_offset_mapping_from_chunk(&chunk, 0, 1).unwrap();
+        assert_eq!(mapping.get("split0").unwrap(), "7");
+    }
+}
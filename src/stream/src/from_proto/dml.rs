@@ -18,6 +18,7 @@ use risingwave_pb::stream_plan::DmlNode;
 use risingwave_storage::StateStore;
 
 use super::ExecutorBuilder;
+use crate::common::metrics::MetricsInfo;
 use crate::error::StreamResult;
 use crate::executor::dml::DmlExecutor;
 use crate::executor::Executor;
@@ -36,6 +37,12 @@ impl ExecutorBuilder for DmlExecutorBuilder {
         let [upstream]: [_; 1] = params.input.try_into().unwrap();
         let table_id = TableId::new(node.table_id);
         let column_descs = node.column_descs.iter().map(Into::into).collect_vec();
+        let metrics_info = MetricsInfo::new(
+            params.executor_stats,
+            node.table_id,
+            params.actor_context.id,
+            "DML",
+        );
 
         let exec = DmlExecutor::new(
             upstream,
@@ -44,6 +51,7 @@ impl ExecutorBuilder for DmlExecutorBuilder {
             node.table_version_id,
             column_descs,
             params.env.config().developer.chunk_size,
+            metrics_info,
         );
         Ok((params.info, exec).into())
     }
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::future::pending;
 use std::sync::Arc;
 use std::time::Duration;
@@ -33,6 +33,7 @@ use tokio::sync::oneshot;
 use tokio::task::JoinHandle;
 use tonic::Status;
 
+pub(crate) use self::managed_state::InflightBarrierInfo;
 use self::managed_state::ManagedBarrierState;
 use crate::error::{IntoUnexpectedExit, StreamError, StreamResult};
 use crate::task::{
@@ -64,10 +65,6 @@ use crate::executor::monitor::StreamingMetrics;
 use crate::executor::{Actor, Barrier, DispatchExecutor};
 use crate::task::barrier_manager::progress::BackfillState;
 
-/// If enabled, all actors will be grouped in the same tracing span within one epoch.
-/// Note that this option will significantly increase the overhead of tracing.
-pub const ENABLE_BARRIER_AGGREGATION: bool = false;
-
 /// Collect result of some barrier on current compute node. Will be reported to the meta service.
 #[derive(Debug)]
 pub struct BarrierCompleteResult {
@@ -109,6 +106,22 @@ impl ControlStreamHandle {
         }
     }
 
+    /// Like [`Self::reset_stream_with_err`], but first flushes `pending_responses` as `Ok`
+    /// responses on the control stream, so the meta side still observes any already-computed
+    /// completion (e.g. a just-finished barrier-complete) instead of it being silently dropped
+    /// by the reset. Best-effort: if the stream has already been reset, the responses are
+    /// simply discarded along with the error.
+    fn reset_stream_with_err_after_draining(
+        &mut self,
+        pending_responses: impl IntoIterator<Item = StreamingControlStreamResponse>,
+        err: Status,
+    ) {
+        for response in pending_responses {
+            self.send_response(response);
+        }
+        self.reset_stream_with_err(err);
+    }
+
     fn inspect_result(&mut self, result: StreamResult<()>) {
         if let Err(e) = result {
             self.reset_stream_with_err(Status::internal(format!("get error: {:?}", e.as_report())));
@@ -186,6 +199,9 @@ pub(super) enum LocalActorOperation {
         ids: UpDownActorIds,
         result_sender: oneshot::Sender<StreamResult<Receiver>>,
     },
+    /// Diagnostic: list epochs that are issued but not yet collected from all actors, along
+    /// with how many actors are still outstanding for each. Used to triage a stuck pipeline.
+    InspectBarrierState(oneshot::Sender<Vec<InflightBarrierInfo>>),
     #[cfg(test)]
     GetCurrentSharedContext(oneshot::Sender<Arc<SharedContext>>),
 }
@@ -221,6 +237,15 @@ impl StreamActorManagerState {
         }
     }
 
+    /// Cancellation-safe: this is called fresh from inside a `select!` arm on every iteration of
+    /// [`LocalBarrierWorker::run`]'s event loop, so the returned future is routinely dropped
+    /// mid-poll whenever another branch is selected instead. That's fine because the only await
+    /// point is `self.creating_actors.next()`, and dropping a `FuturesUnordered::next()` call
+    /// neither removes the pending futures from `self.creating_actors` nor stops their underlying
+    /// `JoinHandle`s (which are already running as independent tokio tasks). So a build that
+    /// finishes while we're not polling simply stays buffered in `creating_actors` and is
+    /// returned, sender included, the next time this is called and actually polled to
+    /// completion -- no sender is ever orphaned by a dropped poll.
     async fn next_created_actors(
         &mut self,
     ) -> (
@@ -252,6 +277,11 @@ pub(crate) struct StreamActorManager {
 /// [`LocalBarrierWorker`] manages barrier control flow, used by local stream manager.
 /// Specifically, [`LocalBarrierWorker`] serve barrier injection from meta server, send the
 /// barriers to and collect them from all actors, and finally report the progress.
+/// The maximum number of actor failures retained in [`LocalBarrierWorker::failure_actors`] before
+/// the oldest ones are evicted. Bounds memory growth during long-running sessions where many
+/// transient, superseded actor errors accumulate.
+const MAX_RETAINED_FAILURE_ACTORS: usize = 128;
+
 pub(super) struct LocalBarrierWorker {
     /// Stores all streaming job source sender.
     barrier_senders: HashMap<ActorId, Vec<UnboundedSender<Barrier>>>,
@@ -262,6 +292,10 @@ pub(super) struct LocalBarrierWorker {
     /// Record all unexpected exited actors.
     failure_actors: HashMap<ActorId, StreamError>,
 
+    /// Tracks the insertion order of [`Self::failure_actors`] so the oldest entries can be
+    /// evicted once the map grows past [`MAX_RETAINED_FAILURE_ACTORS`].
+    failure_actor_order: VecDeque<ActorId>,
+
     control_stream_handle: ControlStreamHandle,
 
     pub(super) actor_manager: Arc<StreamActorManager>,
@@ -275,6 +309,11 @@ pub(super) struct LocalBarrierWorker {
     actor_failure_rx: UnboundedReceiver<(ActorId, StreamError)>,
 
     root_failure: Option<StreamError>,
+
+    /// Scores candidate [`StreamError`]s when [`Self::try_find_root_failure`] picks a root cause
+    /// among several actors' failures. Defaults to [`connector_aware_stream_error_score`], but can
+    /// be overridden (e.g. in tests) to recognize deployment-specific error kinds.
+    root_failure_score_fn: fn(&StreamError) -> i32,
 }
 
 impl LocalBarrierWorker {
@@ -292,6 +331,7 @@ impl LocalBarrierWorker {
         Self {
             barrier_senders: HashMap::new(),
             failure_actors: HashMap::default(),
+            failure_actor_order: VecDeque::default(),
             state: ManagedBarrierState::new(
                 actor_manager.env.state_store(),
                 actor_manager.streaming_metrics.clone(),
@@ -303,9 +343,17 @@ impl LocalBarrierWorker {
             barrier_event_rx: event_rx,
             actor_failure_rx: failure_rx,
             root_failure: None,
+            root_failure_score_fn: connector_aware_stream_error_score,
         }
     }
 
+    /// Overrides [`Self::root_failure_score_fn`]. See [`try_find_root_actor_failure`].
+    #[cfg(test)]
+    pub(super) fn with_root_failure_score_fn(mut self, score_fn: fn(&StreamError) -> i32) -> Self {
+        self.root_failure_score_fn = score_fn;
+        self
+    }
+
     async fn run(mut self, mut actor_op_rx: UnboundedReceiver<LocalActorOperation>) {
         loop {
             select! {
@@ -439,6 +487,9 @@ impl LocalBarrierWorker {
             LocalActorOperation::TakeReceiver { ids, result_sender } => {
                 let _ = result_sender.send(self.current_shared_context.take_receiver(ids));
             }
+            LocalActorOperation::InspectBarrierState(result_sender) => {
+                let _ = result_sender.send(self.state.inflight_barriers());
+            }
             #[cfg(test)]
             LocalActorOperation::GetCurrentSharedContext(sender) => {
                 let _ = sender.send(self.current_shared_context.clone());
@@ -546,6 +597,19 @@ impl LocalBarrierWorker {
                 .watermark_epoch
                 .store(barrier.epoch.curr, std::sync::atomic::Ordering::SeqCst);
         }
+
+        // If enabled via `developer.enable_barrier_aggregation`, group all of this epoch's
+        // barrier-send activity under a single tracing span, instead of it being scattered across
+        // separate per-actor spans. Kept off by default since it significantly increases the
+        // overhead of tracing; operators can flip it at runtime for temporary debugging.
+        let aggregation_span = if self.actor_manager.env.config().developer.enable_barrier_aggregation
+        {
+            tracing::info_span!("aggregated_barrier", epoch = barrier.epoch.curr)
+        } else {
+            tracing::Span::none()
+        };
+        let _aggregation_span_guard = aggregation_span.enter();
+
         debug!(
             target: "events::stream::barrier::manager::send",
             "send barrier {:?}, senders = {:?}, actor_ids_to_collect = {:?}",
@@ -615,6 +679,9 @@ impl LocalBarrierWorker {
     /// When a [`crate::executor::StreamConsumer`] (typically [`crate::executor::DispatchExecutor`]) get a barrier, it should report
     /// and collect this barrier with its own `actor_id` using this function.
     fn collect(&mut self, actor_id: ActorId, barrier: &Barrier) {
+        // The actor is alive since it just collected this barrier, so any previously recorded
+        // failure for it (e.g. a transient error it has since recovered from) is now stale.
+        self.clear_failure(actor_id);
         self.state.collect(actor_id, barrier)
     }
 
@@ -642,6 +709,22 @@ impl LocalBarrierWorker {
                 prev_err = %prev_err.as_report(),
                 "actor error overwritten"
             );
+        } else {
+            self.failure_actor_order.push_back(actor_id);
+        }
+        while self.failure_actors.len() > MAX_RETAINED_FAILURE_ACTORS {
+            let Some(oldest) = self.failure_actor_order.pop_front() else {
+                break;
+            };
+            self.failure_actors.remove(&oldest);
+        }
+    }
+
+    /// Clears a previously recorded failure for `actor_id`, if any, e.g. because the actor has
+    /// since collected a barrier successfully.
+    fn clear_failure(&mut self, actor_id: ActorId) {
+        if self.failure_actors.remove(&actor_id).is_some() {
+            self.failure_actor_order.retain(|id| *id != actor_id);
         }
     }
 
@@ -650,13 +733,23 @@ impl LocalBarrierWorker {
             return root_failure.clone();
         }
         // fetch more actor errors within a timeout
-        let _ = tokio::time::timeout(Duration::from_secs(3), async {
+        let gather_timeout = Duration::from_millis(
+            self.actor_manager
+                .env
+                .config()
+                .developer
+                .actor_failure_gather_timeout_ms,
+        );
+        let _ = tokio::time::timeout(gather_timeout, async {
             while let Some((actor_id, error)) = self.actor_failure_rx.recv().await {
                 self.add_failure(actor_id, error);
             }
         })
         .await;
-        self.root_failure = try_find_root_actor_failure(self.failure_actors.values());
+        self.root_failure = try_find_root_actor_failure(
+            self.failure_actors.values(),
+            self.root_failure_score_fn,
+        );
         self.root_failure.clone().unwrap_or(default_err)
     }
 }
@@ -752,33 +845,62 @@ impl LocalBarrierManager {
     }
 }
 
-/// Tries to find the root cause of actor failures, based on hard-coded rules.
+/// Tries to find the root cause of actor failures, by picking the error with the highest score
+/// among `actor_errors` according to `score_fn`. See [`default_stream_error_score`] and
+/// [`connector_aware_stream_error_score`] for the scorers used in practice.
 pub fn try_find_root_actor_failure<'a>(
     actor_errors: impl IntoIterator<Item = &'a StreamError>,
+    mut score_fn: impl FnMut(&StreamError) -> i32,
 ) -> Option<StreamError> {
-    use crate::executor::StreamExecutorError;
-    let stream_executor_error_score = |e: &StreamExecutorError| {
-        use crate::executor::error::ErrorKind;
-        match e.inner() {
-            ErrorKind::ChannelClosed(_) => 0,
-            ErrorKind::Internal(_) => 1,
-            _ => 999,
-        }
-    };
-    let stream_error_score = |e: &&StreamError| {
-        use crate::error::ErrorKind;
-        match e.inner() {
-            ErrorKind::Internal(_) => 1000,
-            ErrorKind::Executor(ee) => 2000 + stream_executor_error_score(ee),
-            _ => 3000,
-        }
-    };
     actor_errors
         .into_iter()
-        .max_by_key(stream_error_score)
+        .max_by_key(|e| score_fn(e))
         .cloned()
 }
 
+fn stream_executor_error_score(e: &crate::executor::StreamExecutorError) -> i32 {
+    use crate::executor::error::ErrorKind;
+    match e.inner() {
+        ErrorKind::ChannelClosed(_) => 0,
+        ErrorKind::Internal(_) => 1,
+        _ => 999,
+    }
+}
+
+/// The hard-coded root-failure scoring rules used by default: an actor's error outranks another's
+/// the more specific/actionable it is, e.g. a classified executor error outranks a bare top-level
+/// `Internal` one, which in turn outranks a `ChannelClosed` (usually just a symptom of some other
+/// actor having already failed).
+pub fn default_stream_error_score(e: &StreamError) -> i32 {
+    use crate::error::ErrorKind;
+    match e.inner() {
+        ErrorKind::Internal(_) => 1000,
+        ErrorKind::Executor(ee) => 2000 + stream_executor_error_score(ee),
+        _ => 3000,
+    }
+}
+
+/// Like [`default_stream_error_score`], but also recognizes connector errors that custom/external
+/// connectors surface as a bare top-level `Internal` error (rather than being classified as a
+/// [`crate::executor::error::ErrorKind::ConnectorError`]), and ranks them above a plain internal
+/// error so they aren't masked by an unrelated downstream failure, e.g. a channel closing because
+/// its upstream connector actor already died.
+pub fn connector_aware_stream_error_score(e: &StreamError) -> i32 {
+    use crate::error::ErrorKind;
+    match e.inner() {
+        ErrorKind::Internal(err) if is_likely_connector_error(err) => 1500,
+        _ => default_stream_error_score(e),
+    }
+}
+
+/// Heuristic for [`connector_aware_stream_error_score`]: custom connectors commonly bubble their
+/// errors up through `anyhow`, so the only signal left by the time they reach a bare `Internal`
+/// [`StreamError`] is the error message itself.
+fn is_likely_connector_error(err: &anyhow::Error) -> bool {
+    err.chain()
+        .any(|cause| cause.to_string().to_lowercase().contains("connector"))
+}
+
 #[cfg(test)]
 impl LocalBarrierManager {
     pub(super) fn spawn_for_test() -> EventSender<LocalActorOperation> {
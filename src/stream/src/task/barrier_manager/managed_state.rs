@@ -19,6 +19,7 @@ use std::iter::once;
 use std::mem::replace;
 use std::ops::Sub;
 use std::sync::Arc;
+use std::time::Instant;
 
 use anyhow::anyhow;
 use await_tree::InstrumentAwait;
@@ -71,6 +72,9 @@ pub(super) struct BarrierState {
     curr_epoch: u64,
     inner: ManagedBarrierStateInner,
     kind: BarrierKind,
+    /// When `transform_to_issued` issued this epoch. Used to report
+    /// [`StreamingMetrics::barrier_epoch_latency`] once the epoch completes.
+    create_time: Instant,
 }
 
 type AwaitEpochCompletedFuture =
@@ -123,6 +127,14 @@ fn sync_epoch(
     }
 }
 
+/// Per-epoch summary of an in-flight (issued but not yet fully collected) barrier, used to
+/// diagnose a stuck barrier pipeline. See [`ManagedBarrierState::inflight_barriers`].
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct InflightBarrierInfo {
+    pub(crate) prev_epoch: u64,
+    pub(crate) remaining_actor_count: usize,
+}
+
 pub(super) struct ManagedBarrierState {
     /// Record barrier state for each epoch of concurrent checkpoints.
     ///
@@ -277,6 +289,23 @@ impl ManagedBarrierState {
             })
     }
 
+    /// Lists epochs that are `Issued` but not yet collected from all actors, along with how many
+    /// actors are still outstanding for each. Used to diagnose a stuck barrier pipeline.
+    pub(crate) fn inflight_barriers(&self) -> Vec<InflightBarrierInfo> {
+        self.epoch_barrier_state_map
+            .iter()
+            .filter_map(|(prev_epoch, barrier_state)| match &barrier_state.inner {
+                ManagedBarrierStateInner::Issued {
+                    remaining_actors, ..
+                } => Some(InflightBarrierInfo {
+                    prev_epoch: *prev_epoch,
+                    remaining_actor_count: remaining_actors.len(),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Collect a `barrier` from the actor with `actor_id`.
     pub(super) fn collect(&mut self, actor_id: ActorId, barrier: &Barrier) {
         tracing::debug!(
@@ -331,6 +360,9 @@ impl ManagedBarrierState {
                             collected_actors: once(actor_id).collect(),
                         },
                         kind: barrier.kind,
+                        // Overwritten once `transform_to_issued` actually issues this epoch; this
+                        // value is never observed.
+                        create_time: Instant::now(),
                     },
                 );
             }
@@ -385,6 +417,7 @@ impl ManagedBarrierState {
                 curr_epoch: barrier.epoch.curr,
                 inner,
                 kind: barrier.kind,
+                create_time: Instant::now(),
             },
         );
         self.may_have_collected_all(barrier.epoch.prev);
@@ -428,12 +461,22 @@ impl ManagedBarrierState {
             })?;
         match &state.inner {
             ManagedBarrierStateInner::Completed(_) => {
-                match self
+                let BarrierState {
+                    inner,
+                    kind,
+                    create_time,
+                    ..
+                } = self
                     .epoch_barrier_state_map
                     .remove(&prev_epoch)
-                    .expect("should exists")
-                    .inner
-                {
+                    .expect("should exists");
+
+                self.streaming_metrics
+                    .barrier_epoch_latency
+                    .with_guarded_label_values(&[kind.as_str_name()])
+                    .observe(create_time.elapsed().as_secs_f64());
+
+                match inner {
                     ManagedBarrierStateInner::Completed(result) => Ok(Some(result)),
                     _ => unreachable!(),
                 }
@@ -21,10 +21,12 @@ use assert_matches::assert_matches;
 use futures::future::join_all;
 use futures::FutureExt;
 use itertools::Itertools;
+use risingwave_common::config::StreamingConfig;
 use risingwave_common::util::epoch::test_epoch;
 use risingwave_pb::stream_service::{streaming_control_stream_request, InjectBarrierRequest};
 use tokio::sync::mpsc::unbounded_channel;
 use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing_test::traced_test;
 
 use super::*;
 
@@ -215,3 +217,320 @@ async fn test_managed_barrier_collection_before_send_request() -> StreamResult<(
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_inspect_barrier_state_reports_pending_actors() -> StreamResult<()> {
+    let actor_op_tx = LocalBarrierManager::spawn_for_test();
+
+    let (request_tx, request_rx) = unbounded_channel();
+    let (response_tx, mut response_rx) = unbounded_channel();
+
+    actor_op_tx.send_event(LocalActorOperation::NewControlStream {
+        handle: ControlStreamHandle::new(
+            response_tx,
+            UnboundedReceiverStream::new(request_rx).boxed(),
+        ),
+        init_request: InitRequest { prev_epoch: 0 },
+    });
+
+    assert_matches!(
+        response_rx.recv().await.unwrap().unwrap().response.unwrap(),
+        streaming_control_stream_response::Response::Init(_)
+    );
+
+    let context = actor_op_tx
+        .send_and_await(LocalActorOperation::GetCurrentSharedContext)
+        .await
+        .unwrap();
+
+    let manager = &context.local_barrier_manager;
+
+    let register_sender = |actor_id: u32| {
+        let (barrier_tx, barrier_rx) = unbounded_channel();
+        manager.register_sender(actor_id, barrier_tx);
+        (actor_id, barrier_rx)
+    };
+
+    // Before any barrier is issued, there should be nothing in flight.
+    let inflight = actor_op_tx
+        .send_and_await(LocalActorOperation::InspectBarrierState)
+        .await
+        .unwrap();
+    assert!(inflight.is_empty());
+
+    let actor_ids = vec![233, 234, 235];
+    let mut rxs = actor_ids
+        .clone()
+        .into_iter()
+        .map(register_sender)
+        .collect_vec();
+
+    let curr_epoch = test_epoch(2);
+    let barrier = Barrier::new_test_barrier(curr_epoch);
+    let epoch = barrier.epoch.prev;
+
+    request_tx
+        .send(Ok(StreamingControlStreamRequest {
+            request: Some(streaming_control_stream_request::Request::InjectBarrier(
+                InjectBarrierRequest {
+                    request_id: "".to_string(),
+                    barrier: Some(barrier.to_protobuf()),
+                    actor_ids_to_send: actor_ids.clone(),
+                    actor_ids_to_collect: actor_ids,
+                },
+            )),
+        }))
+        .unwrap();
+
+    let collected_barriers = join_all(rxs.iter_mut().map(|(actor_id, rx)| async move {
+        let barrier = rx.recv().await.unwrap();
+        assert_eq!(barrier.epoch.prev, epoch);
+        (*actor_id, barrier)
+    }))
+    .await;
+
+    // Collect from only one of the three actors, leaving the barrier in flight.
+    let (actor_id, barrier) = &collected_barriers[0];
+    manager.collect(*actor_id, barrier);
+    manager.flush_all_events().await;
+
+    let inflight = actor_op_tx
+        .send_and_await(LocalActorOperation::InspectBarrierState)
+        .await
+        .unwrap();
+    assert_eq!(
+        inflight,
+        vec![InflightBarrierInfo {
+            prev_epoch: epoch,
+            remaining_actor_count: 2,
+        }]
+    );
+
+    // Collect the rest so the barrier completes and is no longer reported as in flight.
+    for (actor_id, barrier) in &collected_barriers[1..] {
+        manager.collect(*actor_id, barrier);
+    }
+    manager.flush_all_events().await;
+    let _ = response_rx.recv().await.unwrap().unwrap();
+
+    let inflight = actor_op_tx
+        .send_and_await(LocalActorOperation::InspectBarrierState)
+        .await
+        .unwrap();
+    assert!(inflight.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_control_stream_handle_reset_after_draining() {
+    let (response_tx, mut response_rx) = unbounded_channel();
+    let (_request_tx, request_rx) = unbounded_channel();
+    let mut handle = ControlStreamHandle::new(
+        response_tx,
+        UnboundedReceiverStream::new(request_rx).boxed(),
+    );
+
+    let pending_response = StreamingControlStreamResponse {
+        response: Some(streaming_control_stream_response::Response::Init(
+            InitResponse {},
+        )),
+    };
+    handle.reset_stream_with_err_after_draining(
+        once(pending_response),
+        Status::internal("test reset"),
+    );
+
+    // The already-computed response is delivered to the meta side before the reset error.
+    assert_matches!(
+        response_rx.recv().await.unwrap().unwrap().response.unwrap(),
+        streaming_control_stream_response::Response::Init(_)
+    );
+    assert_matches!(response_rx.recv().await.unwrap(), Err(_));
+    assert_eq!(response_rx.recv().await, None);
+}
+
+#[tokio::test]
+async fn test_failure_actors_cleared_on_recovery() {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let actor_manager = Arc::new(StreamActorManager {
+        env: StreamEnvironment::for_test(),
+        streaming_metrics: Arc::new(StreamingMetrics::unused()),
+        watermark_epoch: Arc::new(std::sync::atomic::AtomicU64::new(0)),
+        await_tree_reg: None,
+        runtime: runtime.into(),
+    });
+    let mut worker = LocalBarrierWorker::new(actor_manager);
+
+    let actor_id = 233;
+    worker.add_failure(actor_id, StreamError::from(anyhow::anyhow!("actor exited")));
+    assert!(worker.failure_actors.contains_key(&actor_id));
+
+    // The actor recovers and successfully collects a later barrier: its stale failure should be
+    // cleared so that future barrier injections are no longer rejected because of it.
+    let barrier = Barrier::new_test_barrier(test_epoch(1));
+    worker.collect(actor_id, &barrier);
+    assert!(!worker.failure_actors.contains_key(&actor_id));
+}
+
+#[tokio::test]
+async fn test_next_created_actors_survives_dropped_polls() {
+    let mut state = StreamActorManagerState::new();
+
+    let mut receivers = vec![];
+    for _ in 0..3 {
+        let (tx, rx) = oneshot::channel();
+        receivers.push(rx);
+        let handle = tokio::spawn(async { Ok(vec![]) });
+        state.creating_actors.push(AttachedFuture::new(handle, tx));
+    }
+
+    // Let the spawned tasks actually finish running in the background, independent of whether
+    // `next_created_actors` is ever polled -- this is the property that makes the pattern
+    // cancellation-safe.
+    tokio::task::yield_now().await;
+
+    let mut drops = 0;
+    let mut received = 0;
+    while received < 3 {
+        tokio::select! {
+            biased;
+            // Simulates `LocalBarrierWorker::run`'s `select!` repeatedly choosing an unrelated
+            // branch instead, dropping the `next_created_actors()` future mid-poll each time.
+            _ = std::future::ready(()), if drops < 5 => {
+                drops += 1;
+            }
+            (sender, result) = state.next_created_actors() => {
+                assert!(result.is_ok());
+                assert!(sender.send(Ok(())).is_ok());
+                received += 1;
+            }
+        }
+    }
+
+    for rx in receivers {
+        assert_matches!(rx.await, Ok(Ok(())));
+    }
+}
+
+fn new_test_worker_with_barrier_aggregation(
+    enable_barrier_aggregation: bool,
+) -> LocalBarrierWorker {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let config = StreamingConfig {
+        developer: risingwave_common::config::StreamingDeveloperConfig {
+            enable_barrier_aggregation,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let actor_manager = Arc::new(StreamActorManager {
+        env: StreamEnvironment::for_test_with_config(config),
+        streaming_metrics: Arc::new(StreamingMetrics::unused()),
+        watermark_epoch: Arc::new(std::sync::atomic::AtomicU64::new(0)),
+        await_tree_reg: None,
+        runtime: runtime.into(),
+    });
+    LocalBarrierWorker::new(actor_manager)
+}
+
+#[traced_test]
+#[test]
+fn test_send_barrier_records_aggregated_span_when_enabled() {
+    let mut worker = new_test_worker_with_barrier_aggregation(true);
+
+    let actor_id = 233;
+    let (tx, _rx) = unbounded_channel();
+    worker.register_sender(actor_id, tx);
+
+    let barrier = Barrier::new_test_barrier(test_epoch(1));
+    worker
+        .send_barrier(
+            &barrier,
+            HashSet::from([actor_id]),
+            HashSet::from([actor_id]),
+        )
+        .unwrap();
+
+    assert!(logs_contain("aggregated_barrier"));
+}
+
+#[traced_test]
+#[test]
+fn test_send_barrier_has_no_aggregated_span_when_disabled() {
+    let mut worker = new_test_worker_with_barrier_aggregation(false);
+
+    let actor_id = 233;
+    let (tx, _rx) = unbounded_channel();
+    worker.register_sender(actor_id, tx);
+
+    let barrier = Barrier::new_test_barrier(test_epoch(1));
+    worker
+        .send_barrier(
+            &barrier,
+            HashSet::from([actor_id]),
+            HashSet::from([actor_id]),
+        )
+        .unwrap();
+
+    assert!(!logs_contain("aggregated_barrier"));
+}
+
+#[test]
+fn test_default_stream_error_score_ranks_channel_closed_lowest() {
+    let channel_closed: StreamError =
+        crate::executor::StreamExecutorError::channel_closed("peer exited").into();
+    let internal = StreamError::from(anyhow::anyhow!("boom"));
+    assert!(default_stream_error_score(&internal) > default_stream_error_score(&channel_closed));
+}
+
+#[test]
+fn test_connector_aware_score_outranks_plain_internal_for_connector_errors() {
+    let plain_internal = StreamError::from(anyhow::anyhow!("boom"));
+    let connector_internal = StreamError::from(anyhow::anyhow!("my-connector: boom"));
+    assert_eq!(
+        default_stream_error_score(&plain_internal),
+        default_stream_error_score(&connector_internal),
+        "the default scorer can't tell these apart"
+    );
+    assert!(
+        connector_aware_stream_error_score(&connector_internal)
+            > connector_aware_stream_error_score(&plain_internal)
+    );
+}
+
+#[tokio::test]
+async fn test_try_find_root_failure_uses_the_installed_scorer() {
+    let mut worker = new_test_worker_with_barrier_aggregation(false)
+        .with_root_failure_score_fn(|e: &StreamError| if e.to_string().contains('a') { 1 } else { 0 });
+
+    worker.add_failure(1, StreamError::from(anyhow::anyhow!("a")));
+    worker.add_failure(2, StreamError::from(anyhow::anyhow!("b")));
+
+    let root = worker
+        .try_find_root_failure(StreamError::from(anyhow::anyhow!("default")))
+        .await;
+    assert!(root.to_string().contains('a'));
+}
+
+#[test]
+fn test_try_find_root_actor_failure_uses_the_given_scorer() {
+    let a = StreamError::from(anyhow::anyhow!("a"));
+    let b = StreamError::from(anyhow::anyhow!("b"));
+
+    // With a scorer that always prefers `a`, `a` is picked regardless of iteration order.
+    let root = try_find_root_actor_failure([&b, &a], |e: &StreamError| {
+        if e.to_string().contains('a') {
+            1
+        } else {
+            0
+        }
+    });
+    assert_eq!(root.unwrap().to_string(), a.to_string());
+}
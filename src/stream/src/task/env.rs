@@ -91,6 +91,12 @@ impl StreamEnvironment {
     // Create an instance for testing purpose.
     #[cfg(test)]
     pub fn for_test() -> Self {
+        Self::for_test_with_config(StreamingConfig::default())
+    }
+
+    /// Like [`Self::for_test`], but with a caller-provided [`StreamingConfig`], e.g. to exercise
+    /// a `developer` config flag that's off by default.
+    pub fn for_test_with_config(config: StreamingConfig) -> Self {
         use risingwave_common::system_param::local_manager::LocalSystemParamsManager;
         use risingwave_dml::dml_manager::DmlManager;
         use risingwave_pb::connector_service::SinkPayloadFormat;
@@ -98,7 +104,7 @@ impl StreamEnvironment {
         StreamEnvironment {
             server_addr: "127.0.0.1:5688".parse().unwrap(),
             connector_params: ConnectorParams::new(None, SinkPayloadFormat::Json),
-            config: Arc::new(StreamingConfig::default()),
+            config: Arc::new(config),
             worker_id: WorkerNodeId::default(),
             state_store: StateStoreImpl::shared_in_memory_store(Arc::new(
                 MonitoredStorageMetrics::unused(),
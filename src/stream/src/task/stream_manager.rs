@@ -58,7 +58,7 @@ use crate::executor::{
 };
 use crate::from_proto::create_executor;
 use crate::task::barrier_manager::{
-    ControlStreamHandle, EventSender, LocalActorOperation, LocalBarrierWorker,
+    ControlStreamHandle, EventSender, InflightBarrierInfo, LocalActorOperation, LocalBarrierWorker,
 };
 use crate::task::{
     ActorId, FragmentId, LocalBarrierManager, SharedContext, StreamActorManager,
@@ -267,6 +267,14 @@ impl LocalStreamManager {
             })
             .await?
     }
+
+    /// Diagnostic: list epochs that are issued but not yet collected from all actors, along with
+    /// how many actors are still outstanding for each. Used to triage a stuck barrier pipeline.
+    pub async fn inspect_barrier_state(&self) -> StreamResult<Vec<InflightBarrierInfo>> {
+        self.actor_op_tx
+            .send_and_await(LocalActorOperation::InspectBarrierState)
+            .await
+    }
 }
 
 impl LocalBarrierWorker {